@@ -78,6 +78,10 @@ pub enum Command {
     /// Processes an input file to extract provided metadata.
     Query(QueryCommand),
 
+    /// Compiles an input file and reports its diagnostics without exporting
+    /// it.
+    Check(CheckCommand),
+
     /// Lists all discovered fonts in system and custom font paths.
     Fonts(FontsCommand),
 
@@ -173,6 +177,29 @@ pub struct QueryCommand {
     pub process: ProcessArgs,
 }
 
+/// Compiles an input file and reports its diagnostics without exporting it.
+#[derive(Debug, Clone, Parser)]
+pub struct CheckCommand {
+    /// Path to input Typst file. Use `-` to read input from stdin.
+    #[clap(value_parser = input_value_parser(), value_hint = ValueHint::FilePath)]
+    pub input: Input,
+
+    /// Also checks the document for accessibility issues, such as images
+    /// without alternative descriptions, skipped heading levels, tables
+    /// without header rows, and text with low contrast. Fails if any are
+    /// found.
+    #[clap(long = "a11y")]
+    pub a11y: bool,
+
+    /// World arguments.
+    #[clap(flatten)]
+    pub world: WorldArgs,
+
+    /// Processing arguments.
+    #[clap(flatten)]
+    pub process: ProcessArgs,
+}
+
 /// Lists all discovered fonts in system and custom font paths.
 #[derive(Debug, Clone, Parser)]
 pub struct FontsCommand {
@@ -242,8 +269,8 @@ pub struct CompileArgs {
     #[clap(value_parser = input_value_parser(), value_hint = ValueHint::FilePath)]
     pub input: Input,
 
-    /// Path to output file (PDF, PNG, SVG, or HTML). Use `-` to write output to
-    /// stdout.
+    /// Path to output file (PDF, PNG, SVG, HTML, or EPUB). Use `-` to write
+    /// output to stdout.
     ///
     /// For output formats emitting one file per page (PNG & SVG), a page number
     /// template must be present if the source document renders to multiple
@@ -290,6 +317,52 @@ pub struct CompileArgs {
     #[arg(long = "no-pdf-tags")]
     pub no_pdf_tags: bool,
 
+    /// The maximum resolution of raster images in the exported PDF, in PPI
+    /// (pixels per inch) at their size on the page. Images with a higher
+    /// resolution are downsampled.
+    #[arg(long = "pdf-max-ppi", value_name = "PPI", value_parser = parse_pdf_max_ppi)]
+    pub pdf_max_ppi: Option<f64>,
+
+    /// The quality between 1 and 100 with which JPEG images in the exported PDF
+    /// are re-encoded if that makes them smaller.
+    #[arg(
+        long = "pdf-jpeg-quality",
+        value_name = "QUALITY",
+        value_parser = clap::value_parser!(u8).range(1..=100),
+    )]
+    pub pdf_jpeg_quality: Option<u8>,
+
+    /// Re-encodes downsampled JPEG images in the exported PDF losslessly
+    /// instead of as JPEG.
+    #[arg(long = "pdf-lossless-images")]
+    pub pdf_lossless_images: bool,
+
+    /// How to handle fonts whose license restricts embedding them into the
+    /// exported PDF.
+    #[arg(long = "pdf-restricted-fonts", value_name = "POLICY", default_value_t)]
+    pub pdf_restricted_fonts: PdfRestrictedFonts,
+
+    /// Lists the fonts embedded into the exported PDF with the number of
+    /// glyphs used from each.
+    #[arg(long = "pdf-font-report")]
+    pub pdf_font_report: bool,
+
+    /// Splits the exported HTML into multiple files, starting a new one at each
+    /// level-1 heading or `html.split` marker. The output path then names a
+    /// directory into which the files and a table of contents are written.
+    #[arg(long = "html-split")]
+    pub html_split: bool,
+
+    /// Where to put the CSS styles of elements in HTML export.
+    #[arg(long = "html-css", default_value_t)]
+    pub html_css: HtmlCss,
+
+    /// Writes images and frames in HTML export to separate files in an
+    /// `assets` directory next to the HTML instead of embedding them. Raster
+    /// images get downscaled variants for smaller screens.
+    #[arg(long = "html-assets")]
+    pub html_assets: bool,
+
     /// The PPI (pixels per inch) to use for PNG export.
     #[arg(long = "ppi", default_value_t = 144.0)]
     pub ppi: f32,
@@ -348,6 +421,11 @@ pub struct WorldArgs {
     )]
     pub inputs: Vec<(String, String)>,
 
+    /// Adds a JSON schema of custom HTML elements, which then become available
+    /// as typed functions in the `html` module (e.g. `html.my-widget`).
+    #[clap(long = "html-schema", value_name = "PATH", action = ArgAction::Append)]
+    pub html_schemas: Vec<PathBuf>,
+
     /// Common font arguments.
     #[clap(flatten)]
     pub font: FontArgs,
@@ -433,19 +511,30 @@ pub struct FontArgs {
 #[derive(Debug, Clone, Parser)]
 pub struct ServerArgs {
     /// Disables the built-in HTTP server for HTML export.
-    #[clap(long)]
+    #[clap(long, conflicts_with = "serve")]
     pub no_serve: bool,
 
+    /// Serves a live preview of PDF, PNG, or SVG output in the browser. HTML
+    /// output is served by default.
+    #[clap(long)]
+    pub serve: bool,
+
     /// Disables the injected live reload script for HTML export. The HTML that
     /// is written to disk isn't affected either way.
     #[clap(long)]
     pub no_reload: bool,
 
-    /// The port where HTML is served.
+    /// The port where the output is served.
     ///
     /// Defaults to the first free port in the range 3000-3005.
     #[clap(long)]
     pub port: Option<u16>,
+
+    /// Opens the source of what is clicked with Ctrl or Cmd held in the
+    /// served output in the editor from the `VISUAL` or `EDITOR` environment
+    /// variable. The source position is printed either way.
+    #[clap(long)]
+    pub open_in_editor: bool,
 }
 
 macro_rules! display_possible_values {
@@ -545,6 +634,7 @@ pub enum OutputFormat {
     Png,
     Svg,
     Html,
+    Epub,
 }
 
 display_possible_values!(OutputFormat);
@@ -563,6 +653,20 @@ pub enum DepsFormat {
 
 display_possible_values!(DepsFormat);
 
+/// Where to put CSS styles in HTML export.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum HtmlCss {
+    /// In `style` attributes on the elements.
+    #[default]
+    Inline,
+    /// In a `<style>` element, with one class per distinct style.
+    Embedded,
+    /// In a separate CSS file, with one class per distinct style.
+    External,
+}
+
+display_possible_values!(HtmlCss);
+
 /// The target to compile for.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
 pub enum Target {
@@ -644,6 +748,20 @@ pub enum PdfStandard {
 
 display_possible_values!(PdfStandard);
 
+/// How to handle fonts whose license restricts embedding them.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum PdfRestrictedFonts {
+    /// Embed the fonts anyway and emit a warning.
+    #[default]
+    Warn,
+    /// Fail the export.
+    Error,
+    /// Convert text set in the fonts to outlines instead of embedding them.
+    Outline,
+}
+
+display_possible_values!(PdfRestrictedFonts);
+
 /// Output file format for query and info commands
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, ValueEnum)]
 pub enum SerializationFormat {
@@ -741,6 +859,16 @@ fn parse_sys_input_pair(raw: &str) -> Result<(String, String), String> {
     Ok((key, val))
 }
 
+/// Parses the maximum resolution of images in PDF export, which must be a
+/// positive number.
+fn parse_pdf_max_ppi(raw: &str) -> Result<f64, String> {
+    let ppi: f64 = raw.parse().map_err(|err| format!("invalid number ({err})"))?;
+    if !ppi.is_finite() || ppi <= 0.0 {
+        return Err("resolution must be a positive number".into());
+    }
+    Ok(ppi)
+}
+
 /// Parses a UNIX timestamp according to <https://reproducible-builds.org/specs/source-date-epoch/>
 fn parse_source_date_epoch(raw: &str) -> Result<DateTime<Utc>, String> {
     let timestamp: i64 = raw
@@ -0,0 +1,118 @@
+use ecow::{EcoVec, eco_format};
+use typst::diag::{HintedStrResult, SourceDiagnostic, Warned};
+use typst::layout::PagedDocument;
+
+use crate::args::CheckCommand;
+use crate::compile::print_diagnostics;
+use crate::set_failed;
+use crate::world::SystemWorld;
+
+/// Execute a check command.
+pub fn check(command: &CheckCommand) -> HintedStrResult<()> {
+    let mut world = SystemWorld::new(&command.input, &command.world, &command.process)?;
+
+    // Reset everything and ensure that the main file is present.
+    world.reset();
+    world.source(world.main()).map_err(|err| err.to_string())?;
+
+    let Checked { failed, errors, warnings } = diagnose(&world, command.a11y);
+    if failed {
+        set_failed();
+    }
+
+    print_diagnostics(&world, &errors, &warnings, command.process.diagnostic_format)
+        .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
+
+    Ok(())
+}
+
+/// The outcome of checking a document.
+struct Checked {
+    /// Whether the document has errors or, if requested, accessibility
+    /// issues.
+    failed: bool,
+    errors: EcoVec<SourceDiagnostic>,
+    /// The compiler's warnings, followed by any accessibility issues.
+    warnings: EcoVec<SourceDiagnostic>,
+}
+
+/// Compiles the document and collects its diagnostics, including its
+/// accessibility issues if `a11y` is set.
+fn diagnose(world: &SystemWorld, a11y: bool) -> Checked {
+    let Warned { output, mut warnings } = typst::compile::<PagedDocument>(world);
+    match output {
+        Ok(document) => {
+            let mut failed = false;
+            if a11y {
+                let issues = typst_pdf::check_accessibility(&document);
+                failed = !issues.is_empty();
+                warnings.extend(issues);
+            }
+            Checked { failed, errors: EcoVec::new(), warnings }
+        }
+        Err(errors) => Checked { failed: true, errors, warnings },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    /// Checks a document with the given source.
+    fn check_source(source: &str, a11y: bool) -> Checked {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.typ");
+        std::fs::write(&path, source).unwrap();
+
+        let mut args = vec!["check", path.to_str().unwrap()];
+        if a11y {
+            args.push("--a11y");
+        }
+        let command = CheckCommand::try_parse_from(args).unwrap();
+        let world =
+            SystemWorld::new(&command.input, &command.world, &command.process).unwrap();
+        diagnose(&world, command.a11y)
+    }
+
+    const SOURCE: &str = "\
+        #set document(title: [Report])\n\
+        #set text(lang: \"en\")\n\
+        = Results\n\
+        === Details\n\
+        #table[A]\n";
+
+    #[test]
+    fn test_check_without_a11y() {
+        let checked = check_source(SOURCE, false);
+        assert!(!checked.failed);
+        assert!(checked.errors.is_empty());
+        assert!(checked.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_check_a11y() {
+        let checked = check_source(SOURCE, true);
+        assert!(checked.failed);
+        assert!(checked.errors.is_empty());
+        let messages: Vec<_> =
+            checked.warnings.iter().map(|diag| diag.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["skipped from heading level 1 to 3", "table has no header row"]
+        );
+    }
+
+    #[test]
+    fn test_check_a11y_clean() {
+        let source = "\
+            #set document(title: [Report])\n\
+            #set text(lang: \"en\")\n\
+            = Results\n\
+            #table(table.header[Name], [A])\n";
+        let checked = check_source(source, true);
+        assert!(!checked.failed);
+        assert!(checked.warnings.is_empty());
+    }
+}
@@ -1,10 +1,12 @@
 use std::ffi::OsStr;
+use std::io::{self, Write};
 use std::path::Path;
 
 use chrono::{DateTime, Datelike, Timelike, Utc};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term;
-use ecow::eco_format;
+use codespan_reporting::term::termcolor::WriteColor;
+use ecow::{EcoString, EcoVec, eco_format};
 use parking_lot::RwLock;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use typst::WorldExt;
@@ -15,15 +17,18 @@ use typst::foundations::{Datetime, Smart};
 use typst::layout::{Page, PageRanges, PagedDocument};
 use typst::syntax::{FileId, Lines, Span};
 use typst_html::HtmlDocument;
-use typst_pdf::{PdfOptions, PdfStandards, Timestamp};
+use typst_pdf::{
+    PdfFontReport, PdfImageOptions, PdfImageSummary, PdfOptions, PdfStandards, Timestamp,
+};
 
 use crate::args::{
-    CompileArgs, CompileCommand, DepsFormat, DiagnosticFormat, Input, Output,
-    OutputFormat, PdfStandard, WatchCommand,
+    CompileArgs, CompileCommand, DepsFormat, DiagnosticFormat, HtmlCss, Input, Output,
+    OutputFormat, PdfRestrictedFonts, PdfStandard, WatchCommand,
 };
 use crate::deps::write_deps;
+use crate::download::as_bytes_unit;
 #[cfg(feature = "http-server")]
-use crate::server::HtmlServer;
+use crate::server::Server;
 use crate::timings::Timer;
 
 use crate::watch::Status;
@@ -50,7 +55,7 @@ pub struct CompileConfig {
     pub watching: bool,
     /// Path to input Typst file or stdin.
     pub input: Input,
-    /// Path to output file (PDF, PNG, SVG, or HTML).
+    /// Path to output file (PDF, PNG, SVG, HTML, or EPUB).
     pub output: Output,
     /// The format of the output file.
     pub output_format: OutputFormat,
@@ -67,6 +72,18 @@ pub struct CompileConfig {
     pub pdf_standards: PdfStandards,
     /// Whether to write PDF (accessibility) tags.
     pub disable_pdf_tags: bool,
+    /// How to reduce the size of raster images in the PDF.
+    pub pdf_images: PdfImageOptions,
+    /// How to handle fonts whose license restricts embedding them.
+    pub pdf_restricted_fonts: typst_pdf::PdfRestrictedFonts,
+    /// Whether to list the fonts embedded into the PDF.
+    pub pdf_font_report: bool,
+    /// Whether to split the HTML into multiple files in an output directory.
+    pub html_split: bool,
+    /// Where to put the CSS styles of elements in HTML export.
+    pub html_css: HtmlCss,
+    /// Whether to write images and frames to separate files in HTML export.
+    pub html_assets: bool,
     /// A destination to write a list of dependencies to.
     pub deps: Option<Output>,
     /// The format to use for dependencies.
@@ -76,9 +93,9 @@ pub struct CompileConfig {
     /// The export cache for images, used for caching output files in `typst
     /// watch` sessions with images.
     pub export_cache: ExportCache,
-    /// Server for `typst watch` to HTML.
+    /// Server for previews in `typst watch`.
     #[cfg(feature = "http-server")]
-    pub server: Option<HtmlServer>,
+    pub server: Option<Server>,
 }
 
 impl CompileConfig {
@@ -100,12 +117,15 @@ impl CompileConfig {
 
         let output_format = if let Some(specified) = args.format {
             specified
+        } else if args.html_split {
+            OutputFormat::Html
         } else if let Some(Output::Path(output)) = &args.output {
             match output.extension() {
                 Some(ext) if ext.eq_ignore_ascii_case("pdf") => OutputFormat::Pdf,
                 Some(ext) if ext.eq_ignore_ascii_case("png") => OutputFormat::Png,
                 Some(ext) if ext.eq_ignore_ascii_case("svg") => OutputFormat::Svg,
                 Some(ext) if ext.eq_ignore_ascii_case("html") => OutputFormat::Html,
+                Some(ext) if ext.eq_ignore_ascii_case("epub") => OutputFormat::Epub,
                 _ => bail!(
                     "could not infer output format for path {}.\n\
                      consider providing the format manually with `--format/-f`",
@@ -120,12 +140,16 @@ impl CompileConfig {
             let Input::Path(path) = &input else {
                 panic!("output must be specified when input is from stdin, as guarded by the CLI");
             };
+            if args.html_split {
+                return Output::Path(path.with_extension(""));
+            }
             Output::Path(path.with_extension(
                 match output_format {
                     OutputFormat::Pdf => "pdf",
                     OutputFormat::Png => "png",
                     OutputFormat::Svg => "svg",
                     OutputFormat::Html => "html",
+                    OutputFormat::Epub => "epub",
                 },
             ))
         });
@@ -134,6 +158,30 @@ impl CompileConfig {
             PageRanges::new(export_ranges.iter().map(|r| r.0.clone()).collect())
         });
 
+        if args.html_split && output_format != OutputFormat::Html {
+            bail!("--html-split requires HTML output");
+        }
+
+        if args.html_split && matches!(output, Output::Stdout) {
+            bail!("cannot write split HTML to stdout");
+        }
+
+        if args.html_css != HtmlCss::Inline && output_format != OutputFormat::Html {
+            bail!("--html-css requires HTML output");
+        }
+
+        if args.html_css == HtmlCss::External && matches!(output, Output::Stdout) {
+            bail!("cannot write an external stylesheet when writing HTML to stdout");
+        }
+
+        if args.html_assets && output_format != OutputFormat::Html {
+            bail!("--html-assets requires HTML output");
+        }
+
+        if args.html_assets && matches!(output, Output::Stdout) {
+            bail!("cannot write assets to separate files when writing HTML to stdout");
+        }
+
         if args.no_pdf_tags && args.pdf_standard.contains(&PdfStandard::UA_1) {
             bail!("cannot disable PDF tags when exporting a PDF/UA-1 document");
         }
@@ -145,9 +193,15 @@ impl CompileConfig {
         #[cfg(feature = "http-server")]
         let server = match watch {
             Some(command)
-                if output_format == OutputFormat::Html && !command.server.no_serve =>
+                if output_format == OutputFormat::Epub && command.server.serve =>
+            {
+                bail!("cannot serve EPUB output");
+            }
+            Some(command)
+                if (output_format == OutputFormat::Html || command.server.serve)
+                    && !command.server.no_serve =>
             {
-                Some(HtmlServer::new(&input, &command.server)?)
+                Some(Server::new(&input, &command.server)?)
             }
             _ => None,
         };
@@ -186,6 +240,16 @@ impl CompileConfig {
             pages,
             pdf_standards,
             disable_pdf_tags: args.no_pdf_tags,
+            pdf_images: PdfImageOptions {
+                max_ppi: args.pdf_max_ppi,
+                jpeg_quality: args.pdf_jpeg_quality,
+                lossless: args.pdf_lossless_images,
+            },
+            pdf_restricted_fonts: args.pdf_restricted_fonts.into(),
+            pdf_font_report: args.pdf_font_report,
+            html_split: args.html_split,
+            html_css: args.html_css,
+            html_assets: args.html_assets,
             creation_timestamp: args.world.creation_timestamp,
             ppi: args.ppi,
             diagnostic_format: args.process.diagnostic_format,
@@ -266,32 +330,197 @@ fn compile_and_export(
     match config.output_format {
         OutputFormat::Html => {
             let Warned { output, warnings } = typst::compile::<HtmlDocument>(world);
-            let result = output.and_then(|document| export_html(&document, config));
+            let result = output.and_then(|document| {
+                if config.html_split {
+                    export_html_split(world, &document, config)
+                } else {
+                    export_html(world, &document, config)
+                }
+            });
+            Warned { output: result, warnings }
+        }
+        OutputFormat::Epub => {
+            let Warned { output, warnings } = typst::compile::<HtmlDocument>(world);
+            let result = output.and_then(|document| export_epub(&document, config));
             Warned {
                 output: result.map(|()| vec![config.output.clone()]),
                 warnings,
             }
         }
         _ => {
-            let Warned { output, warnings } = typst::compile::<PagedDocument>(world);
-            let result = output.and_then(|document| export_paged(&document, config));
+            let Warned { output, mut warnings } = typst::compile::<PagedDocument>(world);
+            let result = output.and_then(|document| {
+                let outputs = export_paged(&document, config, &mut warnings)?;
+                #[cfg(feature = "http-server")]
+                if let Some(server) = &config.server {
+                    server.update_paged(&document, world);
+                }
+                Ok(outputs)
+            });
             Warned { output: result, warnings }
         }
     }
 }
 
 /// Export to HTML.
-fn export_html(document: &HtmlDocument, config: &CompileConfig) -> SourceResult<()> {
-    let html = typst_html::html(document)?;
-    let result = config.output.write(html.as_bytes());
+fn export_html(
+    #[cfg_attr(not(feature = "http-server"), allow(unused_variables))]
+    world: &SystemWorld,
+    document: &HtmlDocument,
+    config: &CompileConfig,
+) -> SourceResult<Vec<Output>> {
+    // An external stylesheet is placed next to the HTML file.
+    let stylesheet = match &config.output {
+        Output::Path(path) if config.html_css == HtmlCss::External => {
+            Some(path.with_extension("css"))
+        }
+        _ => None,
+    };
 
+    let css = match (config.html_css, &stylesheet) {
+        (HtmlCss::Embedded, _) => typst_html::HtmlCss::Embedded,
+        (HtmlCss::External, Some(path)) => typst_html::HtmlCss::External(
+            path.file_name().unwrap_or_default().to_string_lossy().into(),
+        ),
+        _ => typst_html::HtmlCss::Inline,
+    };
+
+    let options = typst_html::HtmlOptions {
+        css,
+        assets: html_assets(config),
+        spans: serves(config),
+    };
+    let output = typst_html::html_with_options(document, &options)?;
+    let result = config.output.write(output.html.as_bytes());
+
+    // The served HTML is annotated with source spans for jumping to the
+    // source.
     #[cfg(feature = "http-server")]
-    if let Some(server) = &config.server {
-        server.update(html);
+    if let (Some(server), Some(annotated)) = (&config.server, output.annotated) {
+        server.update_html(annotated, &output.assets, &document.root, world);
     }
 
     result
         .map_err(|err| eco_format!("failed to write HTML file ({err})"))
+        .at(Span::detached())?;
+
+    let mut outputs = vec![config.output.clone()];
+    if let (Some(path), Some(css)) = (stylesheet, output.css) {
+        let stylesheet = Output::Path(path);
+        stylesheet
+            .write(css.as_bytes())
+            .map_err(|err| eco_format!("failed to write CSS file ({err})"))
+            .at(Span::detached())?;
+        outputs.push(stylesheet);
+    }
+
+    // Assets are placed in a directory next to the HTML file.
+    if let Output::Path(path) = &config.output {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        outputs.extend(write_html_files(dir, &output.assets)?);
+    }
+
+    Ok(outputs)
+}
+
+/// Export to multiple HTML files in the output directory.
+fn export_html_split(
+    #[cfg_attr(not(feature = "http-server"), allow(unused_variables))]
+    world: &SystemWorld,
+    document: &HtmlDocument,
+    config: &CompileConfig,
+) -> SourceResult<Vec<Output>> {
+    let Output::Path(dir) = &config.output else {
+        bail!(Span::detached(), "cannot write split HTML to stdout");
+    };
+
+    let css = match config.html_css {
+        HtmlCss::Inline => typst_html::HtmlCss::Inline,
+        HtmlCss::Embedded => typst_html::HtmlCss::Embedded,
+        HtmlCss::External => typst_html::HtmlCss::External("style.css".into()),
+    };
+
+    let options = typst_html::HtmlOptions {
+        css,
+        assets: html_assets(config),
+        spans: serves(config),
+    };
+    let files = typst_html::html_split(document, &options)?;
+    std::fs::create_dir_all(dir)
+        .map_err(|err| eco_format!("failed to create output directory ({err})"))
+        .at(Span::detached())?;
+
+    // Serve the table of contents, which is the first file, annotated with
+    // source spans, and the other files by their paths.
+    #[cfg(feature = "http-server")]
+    if let Some(server) = &config.server
+        && let Some(annotated) = files.first().and_then(|index| index.annotated.clone())
+    {
+        server.update_html(annotated, &files, &document.root, world);
+    }
+
+    write_html_files(dir, &files)
+}
+
+/// Whether the HTML is served, in which case it is additionally annotated with
+/// source spans.
+fn serves(
+    #[cfg_attr(not(feature = "http-server"), allow(unused_variables))]
+    config: &CompileConfig,
+) -> bool {
+    #[cfg(feature = "http-server")]
+    if config.server.is_some() {
+        return true;
+    }
+
+    false
+}
+
+/// The directory, relative to the HTML file(s), into which HTML export writes
+/// images and frames, if it should write them to separate files.
+fn html_assets(config: &CompileConfig) -> Option<EcoString> {
+    config.html_assets.then(|| "assets".into())
+}
+
+/// Writes files produced by HTML export into a directory, creating
+/// subdirectories as needed.
+fn write_html_files(
+    dir: &Path,
+    files: &[typst_html::HtmlFile],
+) -> SourceResult<Vec<Output>> {
+    files
+        .iter()
+        .map(|file| {
+            let path = dir.join(file.path.as_str());
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|err| eco_format!("failed to create directory ({err})"))
+                    .at(Span::detached())?;
+            }
+
+            let output = Output::Path(path);
+            output
+                .write(&file.contents)
+                .map_err(|err| eco_format!("failed to write file ({err})"))
+                .at(Span::detached())?;
+            Ok(output)
+        })
+        .collect()
+}
+
+/// Export to an EPUB publication.
+fn export_epub(document: &HtmlDocument, config: &CompileConfig) -> SourceResult<()> {
+    // The modification date should reflect the time of export unless a fixed
+    // timestamp is given, e.g. for reproducible builds.
+    let options = typst_html::EpubOptions {
+        ident: Smart::Auto,
+        modified: convert_datetime(config.creation_timestamp.unwrap_or_else(Utc::now)),
+    };
+    let buffer = typst_html::epub(document, &options)?;
+    config
+        .output
+        .write(&buffer)
+        .map_err(|err| eco_format!("failed to write EPUB file ({err})"))
         .at(Span::detached())
 }
 
@@ -299,10 +528,11 @@ fn export_html(document: &HtmlDocument, config: &CompileConfig) -> SourceResult<
 fn export_paged(
     document: &PagedDocument,
     config: &CompileConfig,
+    warnings: &mut EcoVec<SourceDiagnostic>,
 ) -> SourceResult<Vec<Output>> {
     match config.output_format {
         OutputFormat::Pdf => {
-            export_pdf(document, config).map(|()| vec![config.output.clone()])
+            export_pdf(document, config, warnings).map(|()| vec![config.output.clone()])
         }
         OutputFormat::Png => {
             export_image(document, config, ImageExportFormat::Png).at(Span::detached())
@@ -310,12 +540,16 @@ fn export_paged(
         OutputFormat::Svg => {
             export_image(document, config, ImageExportFormat::Svg).at(Span::detached())
         }
-        OutputFormat::Html => unreachable!(),
+        OutputFormat::Html | OutputFormat::Epub => unreachable!(),
     }
 }
 
 /// Export to a PDF.
-fn export_pdf(document: &PagedDocument, config: &CompileConfig) -> SourceResult<()> {
+fn export_pdf(
+    document: &PagedDocument,
+    config: &CompileConfig,
+    warnings: &mut EcoVec<SourceDiagnostic>,
+) -> SourceResult<()> {
     // If the timestamp is provided through the CLI, use UTC suffix,
     // else, use the current local time and timezone.
     let timestamp = match config.creation_timestamp {
@@ -337,13 +571,71 @@ fn export_pdf(document: &PagedDocument, config: &CompileConfig) -> SourceResult<
         page_ranges: config.pages.clone(),
         standards: config.pdf_standards.clone(),
         disable_tags: config.disable_pdf_tags,
+        images: config.pdf_images.clone(),
+        restricted_fonts: config.pdf_restricted_fonts,
     };
-    let buffer = typst_pdf::pdf(document, &options)?;
+    let (buffer, report) = typst_pdf::pdf_with_report(document, &options)?;
+    warnings.extend(report.warnings);
     config
         .output
         .write(&buffer)
         .map_err(|err| eco_format!("failed to write PDF file ({err})"))
         .at(Span::detached())?;
+
+    if report.images.count > 0 {
+        print_image_summary(&report.images)
+            .map_err(|err| eco_format!("failed to print image summary ({err})"))
+            .at(Span::detached())?;
+    }
+
+    if config.pdf_font_report {
+        print_font_report(&report.fonts)
+            .map_err(|err| eco_format!("failed to print font report ({err})"))
+            .at(Span::detached())?;
+    }
+
+    Ok(())
+}
+
+/// Prints how much the PDF's raster images were reduced in size.
+fn print_image_summary(summary: &PdfImageSummary) -> io::Result<()> {
+    let styles = term::Styles::default();
+    let mut out = terminal::out();
+    out.set_color(&styles.header_help)?;
+    write!(out, "images")?;
+    out.reset()?;
+    writeln!(
+        out,
+        ": reduced {} {} from {} to {}",
+        summary.count,
+        if summary.count == 1 { "image" } else { "images" },
+        as_bytes_unit(summary.original_bytes).trim(),
+        as_bytes_unit(summary.new_bytes).trim(),
+    )
+}
+
+/// Prints the fonts used in the PDF and how many glyphs of each were embedded.
+fn print_font_report(fonts: &[PdfFontReport]) -> io::Result<()> {
+    let styles = term::Styles::default();
+    let mut out = terminal::out();
+    for font in fonts {
+        out.set_color(&styles.header_help)?;
+        write!(out, "font")?;
+        out.reset()?;
+        write!(
+            out,
+            ": {} ({} {})",
+            font.name,
+            font.glyphs,
+            if font.glyphs == 1 { "glyph" } else { "glyphs" },
+        )?;
+        if !font.embedded {
+            write!(out, ", converted to outlines")?;
+        } else if let Some(restriction) = font.restriction {
+            write!(out, ", restricted: {}", restriction.description())?;
+        }
+        writeln!(out)?;
+    }
     Ok(())
 }
 
@@ -545,6 +837,9 @@ fn open_output(config: &mut CompileConfig) -> StrResult<()> {
     // Can't open stdout.
     let Output::Path(path) = &config.output else { return Ok(()) };
 
+    // For split HTML, open the table of contents.
+    let path = if config.html_split { path.join("index.html") } else { path.clone() };
+
     // Some resource openers require the path to be canonicalized.
     let path = path
         .canonicalize()
@@ -709,3 +1004,13 @@ impl From<PdfStandard> for typst_pdf::PdfStandard {
         }
     }
 }
+
+impl From<PdfRestrictedFonts> for typst_pdf::PdfRestrictedFonts {
+    fn from(policy: PdfRestrictedFonts) -> Self {
+        match policy {
+            PdfRestrictedFonts::Warn => typst_pdf::PdfRestrictedFonts::Warn,
+            PdfRestrictedFonts::Error => typst_pdf::PdfRestrictedFonts::Error,
+            PdfRestrictedFonts::Outline => typst_pdf::PdfRestrictedFonts::Outline,
+        }
+    }
+}
@@ -97,7 +97,7 @@ pub fn display_download_progress(
 
 /// Format a given size as a unit of time. Setting `include_suffix` to true
 /// appends a '/s' (per second) suffix.
-fn as_bytes_unit(size: usize) -> String {
+pub fn as_bytes_unit(size: usize) -> String {
     const KI: f64 = 1024.0;
     const MI: f64 = KI * KI;
     const GI: f64 = KI * KI * KI;
@@ -66,7 +66,7 @@ impl Settings {
 
         [
             ("self-update", self_update, "Update typst via `typst update`"),
-            ("http-server", http_server, "Serve HTML via `typst watch`"),
+            ("http-server", http_server, "Serve previews via `typst watch`"),
         ]
         .into_iter()
         .map(|(key, val, desc)| KeyValDesc { key, val: Value::Bool(*val), desc })
@@ -1,47 +1,352 @@
 use std::io::{self, Write};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::Arc;
 
-use ecow::eco_format;
+use codespan_reporting::files::Files;
+use ecow::{EcoString, eco_format};
 use parking_lot::{Condvar, Mutex, MutexGuard};
-use tiny_http::{Header, Request, Response, StatusCode};
-use typst::diag::{StrResult, bail};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rustc_hash::FxHashMap;
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+use typst::diag::{FileError, FileResult, StrResult, bail};
+use typst::foundations::{Bytes, Datetime};
+use typst::layout::{Abs, PagedDocument, Point};
+use typst::syntax::{FileId, Source, Span};
+use typst::text::{Font, FontBook};
+use typst::utils::LazyHash;
+use typst::{Library, World};
+use typst_html::{HtmlElement, HtmlFile, HtmlNode};
+use typst_ide::{IdeWorld, Jump};
 
 use crate::args::{Input, ServerArgs};
+use crate::terminal;
+use crate::world::SystemWorld;
 
-/// Serves HTML with live reload.
-pub struct HtmlServer {
+/// Serves a live preview of HTML or paged output.
+pub struct Server {
     addr: SocketAddr,
-    bucket: Arc<Bucket<String>>,
+    state: Arc<State>,
 }
 
-impl HtmlServer {
-    /// Create a new HTTP server that serves live HTML.
+impl Server {
+    /// Create a new HTTP server that serves a live preview.
     pub fn new(input: &Input, args: &ServerArgs) -> StrResult<Self> {
-        let reload = !args.no_reload;
         let (addr, server) = start_server(args.port)?;
 
-        let placeholder = PLACEHOLDER_HTML.replace("{INPUT}", &input.to_string());
-        let bucket = Arc::new(Bucket::new(placeholder));
-        let bucket2 = bucket.clone();
+        let input = input.to_string();
+        let placeholder = PLACEHOLDER_HTML.replace("{INPUT}", &input);
+        let state = Arc::new(State {
+            addr,
+            reload: !args.no_reload,
+            editor: args.open_in_editor,
+            viewer: VIEWER_HTML.replace("{INPUT}", &input),
+            bucket: Bucket::new(Preview::Html(placeholder)),
+            sources: Mutex::new(Sources::default()),
+            files: Mutex::new(FxHashMap::default()),
+        });
+        let state2 = state.clone();
 
         std::thread::spawn(move || {
             for req in server.incoming_requests() {
-                let _ = handle(req, reload, &bucket2);
+                let _ = handle(req, &state2);
             }
         });
 
-        Ok(Self { addr, bucket })
+        Ok(Self { addr, state })
     }
 
-    /// The address that we serve the HTML on.
+    /// The address that we serve the preview on.
     pub fn addr(&self) -> SocketAddr {
         self.addr
     }
 
-    /// Updates the HTML, triggering a reload all connected browsers.
-    pub fn update(&self, html: String) {
-        self.bucket.put(html);
+    /// Updates the HTML, triggering an update in all connected browsers.
+    ///
+    /// The HTML should be annotated with source spans, which the injected
+    /// script reports back when an element is clicked. To resolve them, the
+    /// source positions of the elements in the root element the HTML was
+    /// encoded from are looked up in the world.
+    ///
+    /// The given files, like images written to separate files or the other
+    /// files of split HTML, are served at their paths. HTML files are served
+    /// annotated with source spans, like the main HTML.
+    pub fn update_html<'a>(
+        &self,
+        html: String,
+        files: impl IntoIterator<Item = &'a HtmlFile>,
+        root: &HtmlElement,
+        world: &SystemWorld,
+    ) {
+        let mut sources = Sources::default();
+        sources.collect(&root.children, world);
+        *self.state.sources.lock() = sources;
+        *self.state.files.lock() = files
+            .into_iter()
+            .map(|file| {
+                let data = match &file.annotated {
+                    Some(html) => Bytes::new(html.clone().into_bytes()),
+                    None => Bytes::new(file.contents.clone()),
+                };
+                (file.path.clone(), data)
+            })
+            .collect();
+        self.state.bucket.put(Preview::Html(html));
+    }
+
+    /// Updates the pages, triggering an update in all connected browsers.
+    ///
+    /// Only pages that changed since the last update are rendered again.
+    pub fn update_paged(&self, document: &PagedDocument, world: &SystemWorld) {
+        let previous = match &*self.state.bucket.get() {
+            Preview::Paged(pages) => pages.rendered.clone(),
+            Preview::Html(_) => vec![],
+        };
+
+        let rendered = document
+            .pages
+            .par_iter()
+            .enumerate()
+            .map(|(i, page)| {
+                let hash = typst::utils::hash128(page);
+                if let Some(prev) = previous.get(i)
+                    && prev.hash == hash
+                {
+                    return prev.clone();
+                }
+
+                let size = page.frame.size();
+                RenderedPage {
+                    hash,
+                    width: size.x.to_pt(),
+                    height: size.y.to_pt(),
+                    svg: typst_svg::svg(page).into(),
+                }
+            })
+            .collect();
+
+        let pages = Pages {
+            rendered,
+            document: document.clone(),
+            snapshot: Snapshot::new(world),
+        };
+        self.state.bucket.put(Preview::Paged(Arc::new(pages)));
+    }
+}
+
+/// State shared between the server and the threads that handle requests.
+struct State {
+    /// The address that we serve the preview on.
+    addr: SocketAddr,
+    /// Whether to inject the live reload script into served HTML.
+    reload: bool,
+    /// Whether to open clicked source positions in the editor.
+    editor: bool,
+    /// The page that shows paged output.
+    viewer: String,
+    /// The output that is currently served.
+    bucket: Bucket<Preview>,
+    /// The source positions of the elements in the served HTML.
+    sources: Mutex<Sources>,
+    /// The files the served HTML refers to, by their path.
+    files: Mutex<FxHashMap<EcoString, Bytes>>,
+}
+
+impl State {
+    /// The served pages, if the output is paged.
+    fn pages(&self) -> Option<Arc<Pages>> {
+        match &*self.bucket.get() {
+            Preview::Paged(pages) => Some(pages.clone()),
+            Preview::Html(_) => None,
+        }
+    }
+}
+
+/// Output that is served.
+enum Preview {
+    /// HTML, or the placeholder before compilation is finished.
+    Html(String),
+    /// Paged output.
+    Paged(Arc<Pages>),
+}
+
+impl Preview {
+    /// The kind of output, which is sent along with server-sent events so
+    /// that the browser can reload when it changes.
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Html(_) => "html",
+            Self::Paged(_) => "paged",
+        }
+    }
+}
+
+/// Paged output rendered to SVG.
+struct Pages {
+    /// The rendered pages.
+    rendered: Vec<RenderedPage>,
+    /// The document, for resolving clicks on its pages.
+    document: PagedDocument,
+    /// The sources of the compilation, for resolving clicks on pages.
+    snapshot: Snapshot,
+}
+
+/// A page rendered to SVG.
+#[derive(Clone)]
+struct RenderedPage {
+    /// A hash of the page. Unchanged pages are not rendered again and keep
+    /// their URL, so that the browser can use its cached copy.
+    hash: u128,
+    /// The page's width in points.
+    width: f64,
+    /// The page's height in points.
+    height: f64,
+    /// The page as SVG.
+    svg: Arc<str>,
+}
+
+/// The source positions of the elements in the served HTML, by the raw
+/// number of their span.
+#[derive(Default)]
+struct Sources(FxHashMap<NonZeroU64, Position>);
+
+/// A position in a source file.
+struct Position {
+    /// The file's name for display.
+    name: String,
+    /// The file's path on the system, if it is not part of a package.
+    path: Option<PathBuf>,
+    /// The one-based line.
+    line: usize,
+    /// The one-based column.
+    column: usize,
+}
+
+impl Sources {
+    /// Looks up the source positions of the given nodes and their
+    /// descendants.
+    fn collect(&mut self, nodes: &[HtmlNode], world: &SystemWorld) {
+        for node in nodes {
+            let HtmlNode::Element(element) = node else { continue };
+            if let Some(position) = resolve(world, element.span) {
+                self.0.insert(element.span.into_raw(), position);
+            }
+            self.collect(&element.children, world);
+        }
+    }
+}
+
+/// Resolves a span to a position in its source file.
+fn resolve(world: &SystemWorld, span: Span) -> Option<Position> {
+    let source = World::source(world, span.id()?).ok()?;
+    let range = source.range(span)?;
+    SourceFile::new(world, source)?.position(range.start)
+}
+
+/// A source file along with how to point the user to it.
+struct SourceFile {
+    /// The file's contents.
+    source: Source,
+    /// The file's name for display.
+    name: String,
+    /// The file's path on the system, if it is not part of a package.
+    path: Option<PathBuf>,
+}
+
+impl SourceFile {
+    /// Looks up the name and path of a source file.
+    fn new(world: &SystemWorld, source: Source) -> Option<Self> {
+        let id = source.id();
+        Some(Self {
+            name: Files::name(world, id).ok()?,
+            path: if id.package().is_none() {
+                id.vpath().resolve(world.root())
+            } else {
+                None
+            },
+            source,
+        })
+    }
+
+    /// Finds the position of a byte offset in the file.
+    fn position(&self, offset: usize) -> Option<Position> {
+        let (line, column) = self.source.lines().byte_to_line_column(offset)?;
+        Some(Position {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            line: line + 1,
+            column: column + 1,
+        })
+    }
+}
+
+/// A world with the sources of a compilation.
+///
+/// The threads that handle requests have no access to the system world, but
+/// need a world to resolve clicks on pages.
+struct Snapshot {
+    library: LazyHash<Library>,
+    book: LazyHash<FontBook>,
+    main: FileId,
+    files: FxHashMap<FileId, SourceFile>,
+}
+
+impl Snapshot {
+    /// Takes a snapshot of the sources the last compilation parsed.
+    fn new(world: &SystemWorld) -> Self {
+        Self {
+            library: world.library().clone(),
+            // Resolving clicks doesn't need any fonts.
+            book: LazyHash::new(FontBook::new()),
+            main: world.main(),
+            files: world
+                .sources()
+                .into_iter()
+                .filter_map(|source| SourceFile::new(world, source))
+                .map(|file| (file.source.id(), file))
+                .collect(),
+        }
+    }
+}
+
+impl World for Snapshot {
+    fn library(&self) -> &LazyHash<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        &self.book
+    }
+
+    fn main(&self) -> FileId {
+        self.main
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        match self.files.get(&id) {
+            Some(file) => Ok(file.source.clone()),
+            None => Err(FileError::NotFound(id.vpath().as_rootless_path().into())),
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+    }
+
+    fn font(&self, _: usize) -> Option<Font> {
+        None
+    }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        None
+    }
+}
+
+impl IdeWorld for Snapshot {
+    fn upcast(&self) -> &dyn World {
+        self
     }
 }
 
@@ -84,42 +389,268 @@ fn start_server(port: Option<u16>) -> StrResult<(SocketAddr, tiny_http::Server)>
 }
 
 /// Handles a request.
-fn handle(req: Request, reload: bool, bucket: &Arc<Bucket<String>>) -> io::Result<()> {
-    let path = req.url();
+fn handle(req: Request, state: &Arc<State>) -> io::Result<()> {
+    let url = req.url().to_owned();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+    let post = *req.method() == Method::Post;
+
+    // Routes with side effects only accept requests from the preview itself.
+    if post && !is_same_origin(&req, state.addr) {
+        return req.respond(Response::new_empty(StatusCode(403)));
+    }
+
+    if let Some(index) = path.strip_prefix("/page/").and_then(|p| p.strip_suffix(".svg"))
+    {
+        return handle_page(req, index.parse().ok(), state);
+    }
+
     match path {
-        "/" => handle_root(req, reload, bucket),
-        "/events" => handle_events(req, bucket.clone()),
-        _ => req.respond(Response::new_empty(StatusCode(404))),
+        "/" => handle_root(req, state),
+        "/events" => handle_events(req, state.clone()),
+        "/pages" => handle_pages(req, state),
+        "/jump" if post => {
+            let span = param(query, "span").and_then(|span| span.parse().ok());
+            handle_jump(req, span, state)
+        }
+        "/click" if post => handle_click(req, query, state),
+        _ => handle_file(req, path, state),
+    }
+}
+
+/// Whether a request comes from a page served by us.
+///
+/// Browsers send cross-origin requests from other websites along with their
+/// `Origin` header. Checking the `Host` header additionally guards against DNS
+/// rebinding, where another website's domain resolves to our address.
+fn is_same_origin(req: &Request, addr: SocketAddr) -> bool {
+    let header = |name: &str| {
+        req.headers()
+            .iter()
+            .find(|header| header.field.equiv(name))
+            .map(|header| header.value.as_str())
+    };
+
+    let Some(host) = header("Host") else { return false };
+    let hosts = [format!("localhost:{}", addr.port()), addr.to_string()];
+    if !hosts.iter().any(|known| known == host) {
+        return false;
     }
+
+    header("Origin").is_none_or(|origin| origin.strip_prefix("http://") == Some(host))
+}
+
+/// Extracts a parameter from the query part of a URL.
+fn param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+}
+
+/// Handler for the `/` route. Serves the compiled HTML or, for paged output,
+/// the viewer.
+fn handle_root(req: Request, state: &State) -> io::Result<()> {
+    let html = match &*state.bucket.get() {
+        Preview::Html(html) if state.reload => {
+            let mut html = html.clone();
+            inject_live_reload_script(&mut html);
+            html
+        }
+        Preview::Html(html) => html.clone(),
+        Preview::Paged(_) => state.viewer.clone(),
+    };
+    respond(req, "text/html", html.as_bytes())
+}
+
+/// Handler for the `/pages` route. Lists the hash and size of each page as
+/// JSON.
+fn handle_pages(req: Request, state: &State) -> io::Result<()> {
+    let Some(pages) = state.pages() else {
+        return req.respond(Response::new_empty(StatusCode(404)));
+    };
+
+    let list: Vec<_> = pages
+        .rendered
+        .iter()
+        .map(|page| {
+            serde_json::json!({
+                "hash": format!("{:032x}", page.hash),
+                "width": page.width,
+                "height": page.height,
+            })
+        })
+        .collect();
+
+    let json = serde_json::to_string(&list)?;
+    respond(req, "application/json", json.as_bytes())
+}
+
+/// Handler for the `/page/{index}.svg` route. Serves a rendered page.
+fn handle_page(req: Request, index: Option<usize>, state: &State) -> io::Result<()> {
+    let svg = state
+        .pages()
+        .and_then(|pages| Some(pages.rendered.get(index?)?.svg.clone()));
+    let Some(svg) = svg else {
+        return req.respond(Response::new_empty(StatusCode(404)));
+    };
+    respond(req, "image/svg+xml", svg.as_bytes())
 }
 
-/// Handles for the `/` route. Serves the compiled HTML.
-fn handle_root(req: Request, reload: bool, bucket: &Bucket<String>) -> io::Result<()> {
-    let mut html = bucket.get().clone();
-    if reload {
+/// Handler for all other routes. Serves a file the HTML refers to or, for
+/// split HTML, one of its other files.
+fn handle_file(req: Request, path: &str, state: &State) -> io::Result<()> {
+    let file = path
+        .strip_prefix('/')
+        .and_then(|path| state.files.lock().get(path).cloned());
+    let Some(data) = file else {
+        return req.respond(Response::new_empty(StatusCode(404)));
+    };
+
+    let content_type = content_type(path);
+    if content_type == "text/html" && state.reload {
+        let mut html = String::from_utf8_lossy(&data).into_owned();
         inject_live_reload_script(&mut html);
+        return respond(req, content_type, html.as_bytes());
+    }
+
+    respond(req, content_type, &data)
+}
+
+/// The MIME type of a file, determined from its extension.
+fn content_type(path: &str) -> &'static str {
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html",
+        Some("css") => "text/css",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
     }
+}
+
+/// Handler for the `/jump` route. Reports the source position of a clicked
+/// element and opens it in the editor if desired.
+fn handle_jump(req: Request, span: Option<NonZeroU64>, state: &State) -> io::Result<()> {
+    let sources = state.sources.lock();
+    let Some(position) = span.and_then(|span| sources.0.get(&span)) else {
+        return req.respond(Response::new_empty(StatusCode(404)));
+    };
+
+    jump_to_source(position, state.editor)?;
+    req.respond(Response::new_empty(StatusCode(204)))
+}
+
+/// Handler for the `/click` route. Resolves a click on a page, whose index
+/// and coordinates in points are given in the query.
+///
+/// Clicks on text or shapes are handled like in [`handle_jump`]. For clicks
+/// on links, the viewer receives the URL or the position on a page to go to
+/// as JSON.
+fn handle_click(req: Request, query: &str, state: &State) -> io::Result<()> {
+    let Some(pages) = state.pages() else {
+        return req.respond(Response::new_empty(StatusCode(404)));
+    };
+
+    let json = match click(&pages, query) {
+        Some(Jump::File(id, offset)) => {
+            if let Some(position) =
+                pages.snapshot.files.get(&id).and_then(|file| file.position(offset))
+            {
+                jump_to_source(&position, state.editor)?;
+            }
+            return req.respond(Response::new_empty(StatusCode(204)));
+        }
+        Some(Jump::Url(url)) => serde_json::json!({ "url": url.as_str() }),
+        Some(Jump::Position(pos)) => serde_json::json!({
+            "page": pos.page.get() - 1,
+            "y": pos.point.y.to_pt(),
+        }),
+        None => return req.respond(Response::new_empty(StatusCode(204))),
+    };
+
+    respond(req, "application/json", json.to_string().as_bytes())
+}
+
+/// Determines where to jump to for a click on a page.
+fn click(pages: &Pages, query: &str) -> Option<Jump> {
+    let coordinate = |key| {
+        param(query, key)?
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(Abs::pt)
+    };
+
+    let page = pages
+        .document
+        .pages
+        .get(param(query, "page")?.parse::<usize>().ok()?)?;
+    let click = Point::new(coordinate("x")?, coordinate("y")?);
+    typst_ide::jump_from_click(&pages.snapshot, &pages.document, &page.frame, click)
+}
+
+/// Prints a source position and opens it in the editor if desired.
+fn jump_to_source(position: &Position, editor: bool) -> io::Result<()> {
+    let mut out = terminal::out();
+    writeln!(out, "jumping to {}:{}:{}", position.name, position.line, position.column)?;
+
+    if editor
+        && let Some(path) = &position.path
+        && let Err(err) = open_in_editor(path, position.line)
+    {
+        writeln!(out, "failed to open editor: {err}")?;
+    }
+
+    Ok(())
+}
+
+/// Opens a file at a line with the editor from the `VISUAL` or `EDITOR`
+/// environment variable, which is called as `<editor> +<line> <path>`.
+fn open_in_editor(path: &Path, line: usize) -> StrResult<()> {
+    let Some(editor) = std::env::var_os("VISUAL").or_else(|| std::env::var_os("EDITOR"))
+    else {
+        bail!("neither VISUAL nor EDITOR is set");
+    };
+
+    // The variable may contain arguments, like `code --wait`.
+    let editor = editor.to_string_lossy();
+    let mut parts = editor.split_whitespace();
+    let Some(program) = parts.next() else { bail!("editor command is empty") };
+
+    Command::new(program)
+        .args(parts)
+        .arg(format!("+{line}"))
+        .arg(path)
+        .stdin(Stdio::null())
+        .spawn()
+        .map_err(|err| eco_format!("{err}"))?;
+    Ok(())
+}
+
+/// Responds with the given data.
+fn respond(req: Request, content_type: &str, data: &[u8]) -> io::Result<()> {
     req.respond(Response::new(
         StatusCode(200),
-        vec![Header::from_bytes("Content-Type", "text/html").unwrap()],
-        html.as_bytes(),
-        Some(html.len()),
+        vec![Header::from_bytes("Content-Type", content_type).unwrap()],
+        data,
+        Some(data.len()),
         None,
     ))
 }
 
 /// Handler for the `/events` route.
-fn handle_events(req: Request, bucket: Arc<Bucket<String>>) -> io::Result<()> {
+fn handle_events(req: Request, state: Arc<State>) -> io::Result<()> {
     std::thread::spawn(move || {
         // When this returns an error, the client is disconnected and we can
         // terminate the thread.
-        let _ = handle_events_blocking(req, &bucket);
+        let _ = handle_events_blocking(req, &state.bucket);
     });
     Ok(())
 }
 
 /// Event stream for the `/events` route.
-fn handle_events_blocking(req: Request, bucket: &Bucket<String>) -> io::Result<()> {
+fn handle_events_blocking(req: Request, bucket: &Bucket<Preview>) -> io::Result<()> {
     let mut writer = req.into_writer();
     let writer: &mut dyn Write = &mut *writer;
 
@@ -136,9 +667,12 @@ fn handle_events_blocking(req: Request, bucket: &Bucket<String>) -> io::Result<(
     // tries to write to the dead socket for the first time.
     loop {
         bucket.wait();
+        let kind = bucket.get().kind();
         // Trigger a server-sent event. The browser is listening to it via
-        // an `EventSource` listener` (see `inject_script`).
-        write!(writer, "event: reload\ndata:\n\n")?;
+        // an `EventSource` listener and then fetches and patches in the new
+        // output (see `LIVE_RELOAD_SCRIPT` and `VIEWER_HTML`). If the kind of
+        // output changed, it reloads the page instead.
+        write!(writer, "event: reload\ndata: {kind}\n\n")?;
         writer.flush()?;
     }
 }
@@ -208,11 +742,215 @@ const PLACEHOLDER_HTML: &str = "\
 </html>
 ";
 
-/// Reloads the page whenever it receives a "reload" server-sent event
-/// on the `/events` route.
+/// Updates the page whenever it receives a "reload" server-sent event on the
+/// `/events` route. If the output isn't HTML anymore, the page is reloaded.
+///
+/// Otherwise, instead of reloading, the script fetches the new HTML and patches the
+/// differences into the current page, so that the scroll position and the
+/// state of form fields survive. Elements whose tag changed are replaced.
+///
+/// When an element is clicked with Ctrl or Cmd held, its source span is sent
+/// to the `/jump` route.
 const LIVE_RELOAD_SCRIPT: &str = "\
 <script>\
-  new EventSource(\"/events\")\
-    .addEventListener(\"reload\", () => location.reload())\
+  const patch = (from, to) => {\
+    if (from.nodeType !== to.nodeType || from.nodeName !== to.nodeName) {\
+      from.replaceWith(to.cloneNode(true));\
+    } else if (from.nodeType === Node.TEXT_NODE || from.nodeType === Node.COMMENT_NODE) {\
+      if (from.data !== to.data) from.data = to.data;\
+    } else if (from.nodeType === Node.ELEMENT_NODE) {\
+      for (const { name } of [...from.attributes]) {\
+        if (!to.hasAttribute(name)) from.removeAttribute(name);\
+      }\
+      for (const { name, value } of to.attributes) {\
+        if (from.getAttribute(name) !== value) from.setAttribute(name, value);\
+      }\
+      const old = [...from.childNodes];\
+      [...to.childNodes].forEach((child, i) => {\
+        if (i < old.length) patch(old[i], child);\
+        else from.appendChild(child.cloneNode(true));\
+      });\
+      old.slice(to.childNodes.length).forEach(child => child.remove());\
+    }\
+  };\
+  new EventSource(\"/events\").addEventListener(\"reload\", async event => {\
+    if (event.data !== \"html\") return location.reload();\
+    try {\
+      const response = await fetch(location.pathname);\
+      if (!response.ok) return location.reload();\
+      const html = await response.text();\
+      const doc = new DOMParser().parseFromString(html, \"text/html\");\
+      patch(document.documentElement, doc.documentElement);\
+    } catch {\
+      location.reload();\
+    }\
+  });\
+  addEventListener(\"click\", event => {\
+    if (!event.ctrlKey && !event.metaKey) return;\
+    const elem = event.target.closest(\"[data-typst-span]\");\
+    if (!elem) return;\
+    event.preventDefault();\
+    fetch(`/jump?span=${elem.dataset.typstSpan}`, { method: \"POST\" });\
+  });\
 </script>\
 ";
+
+/// The page that shows paged output.
+///
+/// It fetches the list of pages from the `/pages` route whenever it receives
+/// a "reload" server-sent event and only reloads images of pages whose hash
+/// changed. Pages can be zoomed and are listed as thumbnails in a sidebar.
+///
+/// When a page is clicked with Ctrl or Cmd held, the position is sent to the
+/// `/click` route, which responds with a link target to go to, if any.
+const VIEWER_HTML: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8">
+    <title>{INPUT}</title>
+    <style>
+      body {
+        display: grid;
+        grid-template: "toolbar toolbar" auto "thumbnails pages" 1fr / 160px 1fr;
+        height: 100vh;
+        margin: 0;
+        font-family: sans-serif;
+        color: #565565;
+        background: #eff0f3;
+      }
+
+      #toolbar {
+        grid-area: toolbar;
+        display: flex;
+        gap: 8px;
+        align-items: center;
+        justify-content: center;
+        padding: 6px;
+        background: white;
+        border-bottom: 1px solid #dcdce0;
+      }
+
+      #thumbnails {
+        grid-area: thumbnails;
+        overflow-y: auto;
+        padding: 12px;
+        border-right: 1px solid #dcdce0;
+      }
+
+      #pages {
+        grid-area: pages;
+        position: relative;
+        overflow: auto;
+        padding: 16px;
+      }
+
+      img {
+        display: block;
+        background: white;
+        box-shadow: 0 1px 3px #0003;
+      }
+
+      #thumbnails img {
+        width: 100%;
+        margin-bottom: 12px;
+        cursor: pointer;
+      }
+
+      #pages img {
+        margin: 0 auto 16px;
+      }
+    </style>
+  </head>
+  <body>
+    <div id="toolbar">
+      <button id="zoom-out" title="Zoom out">−</button>
+      <span id="zoom">100%</span>
+      <button id="zoom-in" title="Zoom in">+</button>
+      <button id="fit" title="Fit to width">Fit</button>
+    </div>
+    <nav id="thumbnails"></nav>
+    <main id="pages"></main>
+    <script>
+      const main = document.getElementById("pages");
+      const nav = document.getElementById("thumbnails");
+      let pages = [];
+      let zoom = 1;
+
+      const resize = (container, count, create) => {
+        while (container.children.length > count) container.lastChild.remove();
+        while (container.children.length < count) {
+          container.appendChild(create(container.children.length));
+        }
+      };
+
+      const render = () => {
+        resize(main, pages.length, i => {
+          const img = document.createElement("img");
+          img.addEventListener("click", event => click(i, img, event));
+          return img;
+        });
+        resize(nav, pages.length, i => {
+          const img = document.createElement("img");
+          img.title = `Page ${i + 1}`;
+          img.addEventListener("click", () => main.children[i].scrollIntoView());
+          return img;
+        });
+        pages.forEach((page, i) => {
+          for (const img of [main.children[i], nav.children[i]]) {
+            if (img.dataset.hash !== page.hash) {
+              img.dataset.hash = page.hash;
+              img.src = `/page/${i}.svg?v=${page.hash}`;
+            }
+          }
+          main.children[i].style.width = `${page.width * zoom}pt`;
+          main.children[i].style.height = `${page.height * zoom}pt`;
+          nav.children[i].style.aspectRatio = `${page.width} / ${page.height}`;
+        });
+        document.getElementById("zoom").textContent = `${Math.round(zoom * 100)}%`;
+      };
+
+      const update = async () => {
+        pages = await (await fetch("/pages")).json();
+        render();
+      };
+
+      const setZoom = value => {
+        zoom = Math.min(Math.max(value, 0.25), 5);
+        render();
+      };
+
+      const click = async (i, img, event) => {
+        if (!event.ctrlKey && !event.metaKey) return;
+        event.preventDefault();
+        const x = event.offsetX / img.clientWidth * pages[i].width;
+        const y = event.offsetY / img.clientHeight * pages[i].height;
+        const response = await fetch(`/click?page=${i}&x=${x}&y=${y}`, { method: "POST" });
+        if (response.status !== 200) return;
+        const target = await response.json();
+        if (target.url) {
+          window.open(target.url, "_blank");
+        } else if (target.page < pages.length) {
+          const img = main.children[target.page];
+          const offset = target.y / pages[target.page].height * img.clientHeight;
+          main.scrollTo({ top: img.offsetTop + offset });
+        }
+      };
+
+      document.getElementById("zoom-out").onclick = () => setZoom(zoom / 1.25);
+      document.getElementById("zoom-in").onclick = () => setZoom(zoom * 1.25);
+      document.getElementById("fit").onclick = () => {
+        // One point is 4/3 CSS pixels.
+        const widest = Math.max(0, ...pages.map(page => page.width)) * 4 / 3;
+        if (widest > 0) setZoom((main.clientWidth - 32) / widest);
+      };
+
+      new EventSource("/events").addEventListener("reload", event => {
+        if (event.data !== "paged") return location.reload();
+        update();
+      });
+
+      update();
+    </script>
+  </body>
+</html>
+"#;
@@ -121,7 +121,24 @@ impl SystemWorld {
                 })
                 .collect();
 
-            Library::builder().with_inputs(inputs).with_features(features).build()
+            // Read and check the schemas of custom HTML elements.
+            let html_schemas = world_args
+                .html_schemas
+                .iter()
+                .map(|path| {
+                    let error = |err| WorldCreationError::HtmlSchema(path.clone(), err);
+                    let data =
+                        fs::read(path).map_err(|err| error(eco_format!("{err}")))?;
+                    typst_html::HtmlSchema::parse(&data).map_err(error)?;
+                    Ok(Bytes::new(data))
+                })
+                .collect::<Result<_, WorldCreationError>>()?;
+
+            Library::builder()
+                .with_inputs(inputs)
+                .with_features(features)
+                .with_html_schemas(html_schemas)
+                .build()
         };
 
         let mut fonts = Fonts::searcher();
@@ -174,6 +191,17 @@ impl SystemWorld {
             })
     }
 
+    /// Return all source files the last compilation parsed.
+    #[cfg(feature = "http-server")]
+    pub fn sources(&self) -> Vec<Source> {
+        self.slots
+            .lock()
+            .values()
+            .filter(|slot| slot.source.accessed())
+            .filter_map(|slot| slot.source.get()?.as_ref().ok().cloned())
+            .collect()
+    }
+
     /// Reset the compilation state in preparation of a new compilation.
     pub fn reset(&mut self) {
         #[allow(clippy::iter_over_hash_type, reason = "order does not matter")]
@@ -473,6 +501,8 @@ pub enum WorldCreationError {
     InputOutsideRoot,
     /// The root directory does not appear to exist.
     RootNotFound(PathBuf),
+    /// A schema of custom HTML elements could not be loaded.
+    HtmlSchema(PathBuf, EcoString),
     /// Another type of I/O error.
     Io(io::Error),
 }
@@ -489,6 +519,9 @@ impl fmt::Display for WorldCreationError {
             WorldCreationError::RootNotFound(path) => {
                 write!(f, "root directory not found (searched at {})", path.display())
             }
+            WorldCreationError::HtmlSchema(path, err) => {
+                write!(f, "failed to load HTML schema at {} ({err})", path.display())
+            }
             WorldCreationError::Io(err) => write!(f, "{err}"),
         }
     }
@@ -0,0 +1,332 @@
+use std::fmt::Write;
+use std::io::Cursor;
+
+use base64::Engine;
+use ecow::{EcoString, EcoVec, eco_format};
+use image::ImageFormat;
+use image::imageops::FilterType;
+use rustc_hash::{FxHashMap, FxHashSet};
+use typst_library::layout::{Frame, FrameItem};
+
+use crate::{HtmlElement, HtmlFile, HtmlFrame, HtmlNode, attr, tag};
+
+/// The widths, in pixels, of the downscaled variants that are offered for
+/// raster images in addition to the original.
+const VARIANT_WIDTHS: [u32; 3] = [480, 960, 1920];
+
+/// Images and frames that are written to separate files instead of being
+/// embedded into the HTML.
+pub(crate) struct Assets {
+    /// The directory, relative to the HTML file(s), that contains the assets.
+    dir: EcoString,
+    /// The paths of the assets written so far.
+    paths: FxHashSet<EcoString>,
+    /// The assets written so far.
+    files: Vec<HtmlFile>,
+    /// The `srcset` entries of the variants written for raster images so far,
+    /// keyed by a hash of the original image's data.
+    variants: FxHashMap<u128, Option<(EcoString, u32)>>,
+}
+
+impl Assets {
+    /// Creates an empty set of assets in the given directory.
+    pub fn new(dir: EcoString) -> Self {
+        Self {
+            dir,
+            paths: FxHashSet::default(),
+            files: vec![],
+            variants: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the files of all assets.
+    pub fn finish(self) -> Vec<HtmlFile> {
+        self.files
+    }
+
+    /// Moves embedded images and frames in the given nodes into separate
+    /// files.
+    pub fn extract(&mut self, nodes: &mut EcoVec<HtmlNode>) {
+        for node in nodes.make_mut() {
+            match node {
+                HtmlNode::Element(element) => {
+                    if element.tag == tag::img {
+                        self.extract_image(element);
+                    }
+                    self.extract(&mut element.children);
+                }
+                HtmlNode::Frame(frame) => {
+                    if let Some(image) = self.extract_frame(frame) {
+                        *node = image.into();
+                    }
+                }
+                HtmlNode::Tag(_) | HtmlNode::Text(..) => {}
+            }
+        }
+    }
+
+    /// Moves an image embedded as a data URL into a separate file and lets
+    /// the browser load it lazily.
+    fn extract_image(&mut self, element: &mut HtmlElement) {
+        let Some(src) = element.attrs.get(attr::src) else { return };
+        let Some((media_type, data)) = decode_data_url(src) else { return };
+        let Some(ext) = extension(media_type) else { return };
+
+        // Smoothed variants would defeat the purpose of pixelated scaling.
+        let format = match media_type {
+            "image/png" => Some(ImageFormat::Png),
+            "image/jpeg" => Some(ImageFormat::Jpeg),
+            _ => None,
+        }
+        .filter(|_| {
+            element
+                .attrs
+                .get(attr::style)
+                .is_none_or(|style| !style.contains("image-rendering"))
+        });
+
+        // The same image may occur many times, but should only be scaled once.
+        let srcset = format.and_then(|format| {
+            let hash = typst_utils::hash128(&data);
+            if let Some(srcset) = self.variants.get(&hash) {
+                return srcset.clone();
+            }
+            let srcset = self.variants(&data, format, ext);
+            self.variants.insert(hash, srcset.clone());
+            srcset
+        });
+        let path = self.add(data, ext);
+        for (key, value) in element.attrs.0.make_mut() {
+            if *key == attr::src {
+                *value = path.clone();
+            }
+        }
+
+        lazy(element);
+
+        // Without sizes, the browser assumes that the image fills the
+        // viewport's width. Lazily loaded images can instead be sized
+        // according to their layout.
+        if let Some((variants, width)) = srcset {
+            element
+                .attrs
+                .push(attr::srcset, eco_format!("{variants}{path} {width}w"));
+            if element.attrs.get(attr::sizes).is_none() {
+                let lazy = element.attrs.get(attr::loading).is_some_and(|v| v == "lazy");
+                element
+                    .attrs
+                    .push(attr::sizes, if lazy { "auto, 100vw" } else { "100vw" });
+            }
+        }
+    }
+
+    /// Writes downscaled variants of a raster image that is wider than some
+    /// of the variant widths.
+    ///
+    /// Returns the `srcset` entries of the variants and the original width.
+    fn variants(
+        &mut self,
+        data: &[u8],
+        format: ImageFormat,
+        ext: &str,
+    ) -> Option<(EcoString, u32)> {
+        let image = image::load_from_memory_with_format(data, format).ok()?;
+        let (width, height) = (image.width(), image.height());
+
+        let mut srcset = EcoString::new();
+        for target in VARIANT_WIDTHS.into_iter().filter(|&w| w < width) {
+            let scaled = (f64::from(height) * f64::from(target) / f64::from(width))
+                .round()
+                .max(1.0) as u32;
+            let mut buf = Cursor::new(vec![]);
+            image
+                .resize_exact(target, scaled, FilterType::Lanczos3)
+                .write_to(&mut buf, format)
+                .ok()?;
+            let path = self.add(buf.into_inner(), ext);
+            write!(srcset, "{path} {target}w, ").ok()?;
+        }
+
+        (!srcset.is_empty()).then_some((srcset, width))
+    }
+
+    /// Writes a frame to an SVG file and returns an image that shows it.
+    ///
+    /// Frames with links stay inline because links in images are inert.
+    fn extract_frame(&mut self, frame: &HtmlFrame) -> Option<HtmlElement> {
+        if !frame.link_points.is_empty() || has_links(&frame.inner) {
+            return None;
+        }
+
+        let svg = typst_svg::svg_frame(&frame.inner);
+        let path = self.add(svg.into_bytes(), "svg");
+        let mut image = HtmlElement::new(tag::img)
+            .with_attr(attr::src, path)
+            .with_attr(attr::class, "typst-frame")
+            .with_attr(
+                attr::style,
+                eco_format!(
+                    "width: {}em; height: {}em",
+                    frame.inner.width() / frame.text_size,
+                    frame.inner.height() / frame.text_size,
+                ),
+            )
+            .spanned(frame.span);
+        if let Some(id) = &frame.id {
+            image.attrs.push_front(attr::id, id.clone());
+        }
+
+        lazy(&mut image);
+        Some(image)
+    }
+
+    /// Adds an asset with a filename derived from its contents and returns
+    /// its path.
+    fn add(&mut self, data: Vec<u8>, ext: &str) -> EcoString {
+        let hash = typst_utils::hash128(&data);
+        let path = eco_format!("{}/{hash:032x}.{ext}", self.dir);
+        if self.paths.insert(path.clone()) {
+            self.files.push(HtmlFile {
+                path: path.clone(),
+                contents: data,
+                annotated: None,
+            });
+        }
+        path
+    }
+}
+
+/// Lets the browser load and decode an image only once it is needed, unless
+/// the document already specifies otherwise.
+fn lazy(element: &mut HtmlElement) {
+    if element.attrs.get(attr::loading).is_none() {
+        element.attrs.push(attr::loading, "lazy");
+    }
+    if element.attrs.get(attr::decoding).is_none() {
+        element.attrs.push(attr::decoding, "async");
+    }
+}
+
+/// Whether a frame contains links.
+fn has_links(frame: &Frame) -> bool {
+    frame.items().any(|(_, item)| match item {
+        FrameItem::Group(group) => has_links(&group.frame),
+        FrameItem::Link(..) => true,
+        _ => false,
+    })
+}
+
+/// The file extension for an image MIME type, if the image can be written to
+/// a separate file.
+pub(crate) fn extension(media_type: &str) -> Option<&'static str> {
+    Some(match media_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        _ => return None,
+    })
+}
+
+/// Decodes a base64-encoded data URL into its MIME type and data.
+pub(crate) fn decode_data_url(url: &str) -> Option<(&str, Vec<u8>)> {
+    let (media_type, data) = url.strip_prefix("data:")?.split_once(";base64,")?;
+    let data = base64::engine::general_purpose::STANDARD.decode(data).ok()?;
+    Some((media_type, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use ecow::eco_vec;
+
+    use super::*;
+    use crate::HtmlAttr;
+
+    fn png(width: u32) -> EcoString {
+        let mut buf = Cursor::new(vec![]);
+        image::RgbImage::new(width, 10)
+            .write_to(&mut buf, ImageFormat::Png)
+            .unwrap();
+        let data = base64::engine::general_purpose::STANDARD.encode(buf.into_inner());
+        eco_format!("data:image/png;base64,{data}")
+    }
+
+    fn extract(assets: &mut Assets, src: &str) -> HtmlElement {
+        let image = HtmlElement::new(tag::img).with_attr(attr::src, src);
+        let mut nodes = eco_vec![HtmlNode::from(image)];
+        assets.extract(&mut nodes);
+        match &nodes[0] {
+            HtmlNode::Element(element) => element.clone(),
+            _ => panic!("expected element"),
+        }
+    }
+
+    fn get(element: &HtmlElement, key: HtmlAttr) -> Option<&str> {
+        element.attrs.get(key).map(EcoString::as_str)
+    }
+
+    #[test]
+    fn test_decode_data_url() {
+        assert_eq!(
+            decode_data_url("data:image/gif;base64,AAEC"),
+            Some(("image/gif", vec![0, 1, 2]))
+        );
+        assert_eq!(decode_data_url("data:image/gif,AAEC"), None);
+        assert_eq!(decode_data_url("images/a.gif"), None);
+    }
+
+    #[test]
+    fn test_extract_small_image() {
+        let mut assets = Assets::new("assets".into());
+        let image = extract(&mut assets, &png(100));
+        let src = get(&image, attr::src).unwrap();
+        assert!(src.starts_with("assets/") && src.ends_with(".png"));
+        assert_eq!(get(&image, attr::loading), Some("lazy"));
+        assert_eq!(get(&image, attr::decoding), Some("async"));
+        assert_eq!(get(&image, attr::srcset), None);
+        assert_eq!(get(&image, attr::sizes), None);
+
+        let files = assets.finish();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, src);
+    }
+
+    #[test]
+    fn test_extract_large_image() {
+        let mut assets = Assets::new("assets".into());
+        let image = extract(&mut assets, &png(1000));
+        let src = get(&image, attr::src).unwrap();
+        let srcset: Vec<_> = get(&image, attr::srcset).unwrap().split(", ").collect();
+        assert_eq!(srcset.len(), 3);
+        assert!(srcset[0].ends_with(" 480w"));
+        assert!(srcset[1].ends_with(" 960w"));
+        assert_eq!(srcset[2], format!("{src} 1000w"));
+        assert_eq!(get(&image, attr::sizes), Some("auto, 100vw"));
+        assert_eq!(assets.files.len(), 3);
+    }
+
+    #[test]
+    fn test_extract_same_image_twice() {
+        let mut assets = Assets::new("assets".into());
+        let src = png(1000);
+        let first = extract(&mut assets, &src);
+        let second = extract(&mut assets, &src);
+        assert_eq!(first.attrs, second.attrs);
+        assert_eq!(assets.files.len(), 3);
+        assert_eq!(assets.variants.len(), 1);
+    }
+
+    #[test]
+    fn test_extract_eager_image() {
+        let mut assets = Assets::new("assets".into());
+        let image = HtmlElement::new(tag::img)
+            .with_attr(attr::src, png(1000))
+            .with_attr(attr::loading, "eager");
+        let mut nodes = eco_vec![HtmlNode::from(image)];
+        assets.extract(&mut nodes);
+        let HtmlNode::Element(image) = &nodes[0] else { panic!("expected element") };
+        assert_eq!(get(image, attr::loading), Some("eager"));
+        assert_eq!(get(image, attr::sizes), Some("100vw"));
+    }
+}
@@ -193,3 +193,18 @@ pub const value: HtmlAttr = HtmlAttr::constant("value");
 pub const width: HtmlAttr = HtmlAttr::constant("width");
 pub const wrap: HtmlAttr = HtmlAttr::constant("wrap");
 pub const writingsuggestions: HtmlAttr = HtmlAttr::constant("writingsuggestions");
+
+// MathML Core.
+pub const accent: HtmlAttr = HtmlAttr::constant("accent");
+pub const accentunder: HtmlAttr = HtmlAttr::constant("accentunder");
+pub const alttext: HtmlAttr = HtmlAttr::constant("alttext");
+pub const display: HtmlAttr = HtmlAttr::constant("display");
+pub const linethickness: HtmlAttr = HtmlAttr::constant("linethickness");
+pub const mathvariant: HtmlAttr = HtmlAttr::constant("mathvariant");
+pub const stretchy: HtmlAttr = HtmlAttr::constant("stretchy");
+
+// XHTML and EPUB.
+pub const epub_type: HtmlAttr = HtmlAttr::constant("epub:type");
+pub const xml_lang: HtmlAttr = HtmlAttr::constant("xml:lang");
+pub const xmlns: HtmlAttr = HtmlAttr::constant("xmlns");
+pub const xmlns_epub: HtmlAttr = HtmlAttr::constant("xmlns:epub");
@@ -1,7 +1,9 @@
 use ecow::{EcoString, EcoVec, eco_vec};
 use typst_library::diag::{SourceResult, warning};
 use typst_library::engine::Engine;
-use typst_library::foundations::{Content, Packed, StyleChain, Target, TargetElem};
+use typst_library::foundations::{
+    Content, Packed, StyleChain, Styles, Target, TargetElem,
+};
 use typst_library::introspection::{SplitLocator, TagElem};
 use typst_library::layout::{Abs, Axes, HElem, Region, Size};
 use typst_library::routines::Pair;
@@ -11,8 +13,12 @@ use typst_library::text::{
 };
 use typst_syntax::Span;
 
+use crate::css::Appearance;
 use crate::fragment::{html_block_fragment, html_inline_fragment};
-use crate::{FrameElem, HtmlElem, HtmlElement, HtmlFrame, HtmlNode, attr, css, tag};
+use crate::{
+    CssElem, FrameElem, HtmlElem, HtmlElement, HtmlFrame, HtmlNode, HtmlTag, attr, css,
+    tag,
+};
 
 /// What and how to convert.
 pub enum ConversionLevel<'a> {
@@ -70,6 +76,7 @@ pub fn convert_to_nodes<'a>(
         whitespace,
         output: EcoVec::new(),
         trailing: None,
+        run: None,
     };
 
     for (child, styles) in children {
@@ -92,7 +99,18 @@ fn handle(
 ) -> SourceResult<()> {
     if let Some(elem) = child.to_packed::<TagElem>() {
         converter.push(elem.tag.clone());
-    } else if let Some(elem) = child.to_packed::<HtmlElem>() {
+        return Ok(());
+    }
+
+    // Text-like content with styles that differ from the surrounding element
+    // is grouped into runs that are wrapped in styled spans.
+    let textual = child.is::<TextElem>()
+        || child.is::<SpaceElem>()
+        || child.is::<SmartQuoteElem>()
+        || child.is::<LinebreakElem>();
+    converter.restyle(if textual { text_styles(styles) } else { None });
+
+    if let Some(elem) = child.to_packed::<HtmlElem>() {
         handle_html_elem(converter, elem, styles)?;
     } else if child.is::<SpaceElem>() {
         converter.push(HtmlNode::text(' ', child.span()));
@@ -170,15 +188,28 @@ fn handle_html_elem(
 
         // The `role` attribute should only apply to the first element in the
         // hierarchy. Thus, we unset it for children if it is currently set.
-        let unset;
-        let styles = if role.is_some() {
-            unset = HtmlElem::role.set(None).wrap();
-            styles.chain(&unset)
-        } else {
-            styles
-        };
+        let mut local = Styles::new();
+        if role.is_some() {
+            local.set(HtmlElem::role, None);
+        }
+
+        // The appearance of the children is compared against this element's
+        // to determine which of their styles to map to CSS.
+        if styles.get(CssElem::text) || styles.get(CssElem::spacing) {
+            local.set(CssElem::base, Some(Appearance::resolve(styles)));
+        }
+
+        let styles = styles.chain(&local);
 
-        if tag::is_block_by_default(elem.tag) {
+        if elem.tag == tag::math {
+            // The contents of `<math>` elements are MathML instead of HTML.
+            children = crate::math::convert_math(
+                converter.engine,
+                body,
+                converter.locator,
+                styles,
+            )?;
+        } else if tag::is_block_by_default(elem.tag) {
             children = html_block_fragment(
                 converter.engine,
                 body,
@@ -209,6 +240,16 @@ fn handle_html_elem(
         attrs.push(attr::role, role);
     }
 
+    if let Some(css) = element_styles(elem.tag, styles).into_inline_styles() {
+        match attrs.0.make_mut().iter_mut().find(|(key, _)| *key == attr::style) {
+            Some((_, value)) => {
+                value.push_str("; ");
+                value.push_str(&css);
+            }
+            None => attrs.push(attr::style, css),
+        }
+    }
+
     converter.push(HtmlElement {
         tag: elem.tag,
         attrs,
@@ -221,6 +262,46 @@ fn handle_html_elem(
     Ok(())
 }
 
+/// Determines the CSS styles of an element that differ from its surrounding
+/// element, if enabled with [`CssElem`].
+fn element_styles(tag: HtmlTag, styles: StyleChain) -> css::Properties {
+    let mut props = css::Properties::new();
+    let text = styles.get(CssElem::text);
+    let spacing = styles.get(CssElem::spacing);
+    if text || spacing {
+        let base = base_appearance(styles);
+        let appearance = Appearance::resolve(styles);
+        if text {
+            appearance.push_text(&base, Some(tag), &mut props);
+        }
+        if spacing {
+            appearance.push_spacing(&base, tag, &mut props);
+        }
+    }
+    props
+}
+
+/// Determines the CSS styles of text that differ from its surrounding
+/// element, if enabled with [`CssElem`].
+fn text_styles(styles: StyleChain) -> Option<EcoString> {
+    if !styles.get(CssElem::text) {
+        return None;
+    }
+
+    let mut props = css::Properties::new();
+    Appearance::resolve(styles).push_text(&base_appearance(styles), None, &mut props);
+    props.into_inline_styles()
+}
+
+/// The appearance of the element surrounding content with the given styles.
+///
+/// At the top level, this is the default appearance.
+fn base_appearance(styles: StyleChain) -> Appearance {
+    styles
+        .get_cloned(CssElem::base)
+        .unwrap_or_else(|| Appearance::resolve(StyleChain::default()))
+}
+
 /// Handles arbitrary text while taking care that no whitespace within will be
 /// collapsed by browsers.
 fn handle_text(converter: &mut Converter, text: EcoString, span: Span) {
@@ -315,6 +396,7 @@ struct Converter<'a, 'y, 'z> {
     whitespace: Whitespace,
     output: EcoVec<HtmlNode>,
     trailing: Option<TrailingWhitespace>,
+    run: Option<StyledRun>,
 }
 
 /// Keeps track of a trailing whitespace in the output.
@@ -325,13 +407,45 @@ struct TrailingWhitespace {
     from: usize,
 }
 
+/// Keeps track of a run of text with CSS styles in the output.
+struct StyledRun {
+    /// The run's inline styles.
+    styles: EcoString,
+    /// The run starts at `output[from..]`.
+    from: usize,
+}
+
 impl Converter<'_, '_, '_> {
     /// Returns the converted nodes.
     fn finish(mut self) -> EcoVec<HtmlNode> {
+        self.restyle(None);
         self.flush_whitespace();
         self.output
     }
 
+    /// Continues or ends the current run of styled text, depending on whether
+    /// the given styles match it. An ended run is wrapped in a `<span>`.
+    fn restyle(&mut self, styles: Option<EcoString>) {
+        if self.run.as_ref().map(|run| &run.styles) == styles.as_ref() {
+            return;
+        }
+
+        // Whitespace protection must not cross the boundaries of the run.
+        self.flush_whitespace();
+
+        if let Some(run) = self.run.take() {
+            let nodes: EcoVec<_> = self.output[run.from..].iter().cloned().collect();
+            self.output.truncate(run.from);
+            self.output.push(HtmlNode::Element(
+                HtmlElement::new(tag::span)
+                    .with_attr(attr::style, run.styles)
+                    .with_children(nodes),
+            ));
+        }
+
+        self.run = styles.map(|styles| StyledRun { styles, from: self.output.len() });
+    }
+
     /// Pushes a node, taking care to protect consecutive whitespace.
     fn push(&mut self, node: impl Into<HtmlNode>) {
         let node = node.into();
@@ -1,12 +1,19 @@
-//! Conversion from Typst data types into CSS data types.
+//! Conversion from Typst data types into CSS data types and collection of
+//! inline styles into stylesheets.
 
 use std::fmt::{self, Display, Write};
 
-use ecow::EcoString;
-use typst_library::layout::{Length, Rel};
-use typst_library::visualize::{Color, Hsl, LinearRgb, Oklab, Oklch, Rgb};
+use ecow::{EcoString, EcoVec, eco_format};
+use rustc_hash::FxHashMap;
+use typst_library::foundations::{Smart, StyleChain};
+use typst_library::layout::{Abs, BlockElem, Length, Rel, Spacing};
+use typst_library::model::ParElem;
+use typst_library::text::{FontWeight, TextElem};
+use typst_library::visualize::{Color, Hsl, LinearRgb, Oklab, Oklch, Paint, Rgb};
 use typst_utils::Numeric;
 
+use crate::{HtmlElement, HtmlNode, HtmlTag, attr, tag};
+
 /// A list of CSS properties with values.
 #[derive(Debug, Default)]
 pub struct Properties(EcoString);
@@ -38,12 +45,176 @@ impl Properties {
     }
 }
 
+/// The styles of an element or a text run that can be mapped to CSS.
+///
+/// See [`CssElem`](crate::CssElem) for when this happens.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Appearance {
+    /// The text color, if the text fill is a solid color.
+    fill: Option<Color>,
+    /// The resolved font size.
+    size: Abs,
+    /// The font weight, including the weight delta.
+    weight: FontWeight,
+    /// The spacing between paragraphs.
+    par_spacing: Length,
+    /// The spacing above a block, if set to a fixed length.
+    above: Option<Length>,
+    /// The spacing below a block, if set to a fixed length.
+    below: Option<Length>,
+}
+
+impl Appearance {
+    /// Determines the appearance at a point in the style chain.
+    pub fn resolve(styles: StyleChain) -> Self {
+        Self {
+            fill: match styles.get_ref(TextElem::fill) {
+                Paint::Solid(color) => Some(*color),
+                _ => None,
+            },
+            size: styles.resolve(TextElem::size),
+            weight: typst_library::text::variant(styles).weight,
+            par_spacing: styles.get(ParElem::spacing),
+            above: fixed_spacing(styles.get(BlockElem::above)),
+            below: fixed_spacing(styles.get(BlockElem::below)),
+        }
+    }
+
+    /// Adds properties for the text styles in which this appearance differs
+    /// from the `base` appearance of the surrounding element.
+    ///
+    /// If the properties are for an element, its `tag` is given. The font
+    /// size and weight are then only added if browsers don't already style
+    /// them for the tag on their own, like for headings.
+    pub fn push_text(&self, base: &Self, tag: Option<HtmlTag>, props: &mut Properties) {
+        if self.fill != base.fill
+            && let Some(fill) = self.fill
+        {
+            props.push("color", color(fill));
+        }
+
+        if self.size != base.size
+            && !base.size.is_zero()
+            && !tag.is_some_and(tag::is_sized_by_default)
+        {
+            let ratio = typst_utils::round_with_precision(self.size / base.size, 4);
+            props.push("font-size", eco_format!("{ratio}em"));
+        }
+
+        if self.weight != base.weight && !tag.is_some_and(tag::is_weighted_by_default) {
+            props.push("font-weight", self.weight.to_number());
+        }
+    }
+
+    /// Adds margins for the spacing in which this appearance differs from
+    /// the `base` appearance of the surrounding element.
+    ///
+    /// Paragraphs use the paragraph spacing and other block-level elements the
+    /// spacing of blocks.
+    pub fn push_spacing(&self, base: &Self, tag: HtmlTag, props: &mut Properties) {
+        if tag == tag::p {
+            if self.par_spacing != base.par_spacing {
+                props.push("margin-block", length(self.par_spacing));
+            }
+        } else if tag::is_block_by_default(tag) {
+            if self.above != base.above
+                && let Some(above) = self.above
+            {
+                props.push("margin-top", length(above));
+            }
+            if self.below != base.below
+                && let Some(below) = self.below
+            {
+                props.push("margin-bottom", length(below));
+            }
+        }
+    }
+}
+
+/// Extracts a fixed length from block spacing.
+fn fixed_spacing(spacing: Smart<Spacing>) -> Option<Length> {
+    match spacing {
+        Smart::Custom(Spacing::Rel(rel)) if rel.rel.is_zero() => Some(rel.abs),
+        _ => None,
+    }
+}
+
+/// A stylesheet collected from the inline styles of elements.
+///
+/// Each distinct inline style becomes a class, so elements with the same
+/// styles share one class.
+#[derive(Debug, Default)]
+pub struct Stylesheet {
+    /// Maps from inline styles to class names.
+    classes: FxHashMap<EcoString, EcoString>,
+    /// The classes and their styles, in order of first use.
+    rules: Vec<(EcoString, EcoString)>,
+}
+
+impl Stylesheet {
+    /// Creates an empty stylesheet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether no styles were collected.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Replaces the `style` attributes of the nodes and their descendants
+    /// with classes.
+    pub fn extract(&mut self, nodes: &mut EcoVec<HtmlNode>) {
+        for node in nodes.make_mut() {
+            let HtmlNode::Element(element) = node else { continue };
+            self.extract_element(element);
+        }
+    }
+
+    /// Replaces the `style` attribute of an element and its descendants with
+    /// classes.
+    fn extract_element(&mut self, element: &mut HtmlElement) {
+        let attrs = &mut element.attrs.0;
+        if let Some(i) = attrs.iter().position(|(key, _)| *key == attr::style) {
+            let (_, style) = attrs.remove(i);
+            let class = self.class(style);
+            match attrs.make_mut().iter_mut().find(|(key, _)| *key == attr::class) {
+                Some((_, value)) => {
+                    value.push(' ');
+                    value.push_str(&class);
+                }
+                None => attrs.push((attr::class, class)),
+            }
+        }
+        self.extract(&mut element.children);
+    }
+
+    /// Returns the class for an inline style, creating it if necessary.
+    fn class(&mut self, style: EcoString) -> EcoString {
+        if let Some(class) = self.classes.get(&style) {
+            return class.clone();
+        }
+        let class = eco_format!("typst-{}", self.rules.len() + 1);
+        self.classes.insert(style.clone(), class.clone());
+        self.rules.push((class.clone(), style));
+        class
+    }
+
+    /// Encodes the stylesheet as CSS.
+    pub fn to_css(&self) -> String {
+        let mut css = String::new();
+        for (class, style) in &self.rules {
+            writeln!(css, ".{class} {{ {style} }}").unwrap();
+        }
+        css
+    }
+}
+
 pub fn rel(rel: Rel) -> impl Display {
+    let ratio = typst_utils::round_with_precision(rel.rel.get() * 100.0, 4);
     typst_utils::display(move |f| match (rel.abs.is_zero(), rel.rel.is_zero()) {
-        (false, false) => {
-            write!(f, "calc({}% + {})", rel.rel.get(), length(rel.abs))
-        }
-        (true, false) => write!(f, "{}%", rel.rel.get()),
+        (false, false) => write!(f, "calc({ratio}% + {})", length(rel.abs)),
+        (true, false) => write!(f, "{ratio}%"),
         (_, true) => write!(f, "{}", length(rel.abs)),
     })
 }
@@ -13,6 +13,7 @@ use typst_library::introspection::{
 use typst_library::layout::{Point, Position, Transform};
 use typst_library::model::DocumentInfo;
 use typst_library::routines::{Arenas, RealizationKind, Routines};
+use typst_library::text::{Font, FontStyle, FontVariant, FontWeight};
 use typst_syntax::Span;
 use typst_utils::NonZeroExt;
 
@@ -92,6 +93,9 @@ fn html_document_impl(
         Whitespace::Normal,
     )?;
 
+    let root_styles = Styles::root(&children, styles);
+    let fonts = document_fonts(engine.world, StyleChain::new(&root_styles));
+
     let mut output = classify_output(nodes.clone())?;
     let introspectibles = if let OutputKind::Leaves(leaves) = &mut output {
         // Add a footnote container at the end, but only if the user did not
@@ -100,7 +104,7 @@ fn html_document_impl(
             &mut engine,
             FootnoteContainer::shared(),
             footnote_locator,
-            StyleChain::new(&Styles::root(&children, styles)),
+            StyleChain::new(&root_styles),
             Whitespace::Normal,
         )?;
         leaves.extend(notes);
@@ -115,7 +119,33 @@ fn html_document_impl(
     let mut root = root_element(output, &info);
     crate::link::identify_link_targets(&mut root, &mut introspector, link_targets);
 
-    Ok(HtmlDocument { info, root, introspector })
+    Ok(HtmlDocument { info, root, introspector, fonts })
+}
+
+/// Selects the fonts in which the document's text is set: The regular, bold,
+/// italic, and bold italic variants of the first available font family.
+fn document_fonts(world: Tracked<dyn World + '_>, styles: StyleChain) -> Vec<Font> {
+    let book = world.book();
+    let Some(family) = typst_library::text::families(styles)
+        .find(|family| book.contains_family(family.as_str()))
+    else {
+        return vec![];
+    };
+
+    let base = typst_library::text::variant(styles);
+    let mut fonts = vec![];
+    for style in [FontStyle::Normal, FontStyle::Italic] {
+        for weight in [base.weight, FontWeight::BOLD] {
+            let variant = FontVariant::new(style, weight, base.stretch);
+            if let Some(font) =
+                book.select(family.as_str(), variant).and_then(|id| world.font(id))
+                && !fonts.contains(&font)
+            {
+                fonts.push(font);
+            }
+        }
+    }
+    fonts
 }
 
 /// Introspects HTML nodes.
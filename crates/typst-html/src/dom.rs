@@ -6,7 +6,7 @@ use typst_library::foundations::{Dict, Repr, Str, StyleChain, cast};
 use typst_library::introspection::{Introspector, Location, Tag};
 use typst_library::layout::{Abs, Frame, Point};
 use typst_library::model::DocumentInfo;
-use typst_library::text::TextElem;
+use typst_library::text::{Font, TextElem};
 use typst_syntax::Span;
 use typst_utils::{PicoStr, ResolvedPicoStr};
 
@@ -21,6 +21,10 @@ pub struct HtmlDocument {
     pub info: DocumentInfo,
     /// Provides the ability to execute queries on the document.
     pub introspector: Introspector,
+    /// The fonts in which the document's text is set, in the regular, bold,
+    /// italic, and bold italic variants. Exports that package the document
+    /// with its resources, like EPUB, embed them.
+    pub fonts: Vec<Font>,
 }
 
 /// A child of an HTML element.
@@ -1,21 +1,175 @@
 use std::fmt::Write;
 
-use ecow::{EcoString, eco_format};
+use ecow::{EcoString, eco_format, eco_vec};
 use typst_library::diag::{At, SourceResult, StrResult, bail};
 use typst_library::foundations::Repr;
 use typst_library::introspection::Introspector;
 use typst_syntax::Span;
 
+use crate::assets::Assets;
+use crate::css::Stylesheet;
 use crate::{
-    HtmlDocument, HtmlElement, HtmlFrame, HtmlNode, HtmlTag, attr, charsets, tag,
+    HtmlDocument, HtmlElement, HtmlFile, HtmlFrame, HtmlNode, HtmlTag, attr, charsets,
+    tag,
 };
 
+/// Settings for HTML export.
+#[derive(Debug, Default, Clone)]
+pub struct HtmlOptions {
+    /// Where to put the CSS styles of elements.
+    pub css: HtmlCss,
+    /// The directory, relative to the HTML file(s), into which images and
+    /// frames are written as separate files. If `None`, they are embedded into
+    /// the HTML.
+    pub assets: Option<EcoString>,
+    /// Whether to additionally encode the HTML with elements annotated with
+    /// the source spans they originate from in a `data-typst-span` attribute,
+    /// holding the span's raw number. This is meant for tools like live
+    /// previews that jump to the source.
+    pub spans: bool,
+}
+
+/// Where HTML export puts the CSS styles of elements.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum HtmlCss {
+    /// In `style` attributes on the elements themselves.
+    #[default]
+    Inline,
+    /// In a `<style>` element in the document's head, with one class per
+    /// distinct style.
+    Embedded,
+    /// In an external stylesheet with one class per distinct style. The path
+    /// of the stylesheet, relative to the HTML file(s), is given.
+    External(EcoString),
+}
+
+/// An HTML document encoded with [`html_with_options`].
+#[derive(Debug, Clone)]
+pub struct HtmlOutput {
+    /// The document's HTML.
+    pub html: String,
+    /// The document's HTML annotated with source spans, if the options ask
+    /// for it.
+    pub annotated: Option<String>,
+    /// The contents of the external stylesheet, if there are any styles and
+    /// they go into one.
+    pub css: Option<String>,
+    /// Images and frames written to separate files, if the options ask for
+    /// it.
+    pub assets: Vec<HtmlFile>,
+}
+
 /// Encodes an HTML document into a string.
 pub fn html(document: &HtmlDocument) -> SourceResult<String> {
-    let mut w = Writer::new(&document.introspector, true);
+    html_root(&document.root, &document.introspector, false)
+}
+
+/// Encodes an HTML document into a string with the given options.
+pub fn html_with_options(
+    document: &HtmlDocument,
+    options: &HtmlOptions,
+) -> SourceResult<HtmlOutput> {
+    let mut root = document.root.clone();
+    let mut assets = options.assets.clone().map(Assets::new);
+    if let Some(assets) = &mut assets {
+        assets.extract(&mut root.children);
+    }
+
+    let mut sheet = Stylesheet::new();
+    if options.css != HtmlCss::Inline {
+        sheet.extract(&mut root.children);
+    }
+
+    let css = link_stylesheet(&mut root, &sheet, &options.css);
+    let html = html_root(&root, &document.introspector, false)?;
+    let annotated = options
+        .spans
+        .then(|| html_root(&root, &document.introspector, true))
+        .transpose()?;
+    let assets = assets.map(Assets::finish).unwrap_or_default();
+    Ok(HtmlOutput { html, annotated, css, assets })
+}
+
+/// Makes a stylesheet available to a document by adding a `<style>` or
+/// `<link>` element to the `<head>` of its root element.
+///
+/// Returns the stylesheet's contents if it is external.
+pub(crate) fn link_stylesheet(
+    root: &mut HtmlElement,
+    sheet: &Stylesheet,
+    css: &HtmlCss,
+) -> Option<String> {
+    if sheet.is_empty() {
+        return None;
+    }
+
+    match css {
+        HtmlCss::Inline => None,
+        HtmlCss::Embedded => {
+            let text = HtmlNode::text(sheet.to_css(), Span::detached());
+            add_to_head(root, HtmlElement::new(tag::style).with_children(eco_vec![text]));
+            None
+        }
+        HtmlCss::External(path) => {
+            add_to_head(
+                root,
+                HtmlElement::new(tag::link)
+                    .with_attr(attr::rel, "stylesheet")
+                    .with_attr(attr::href, path.clone()),
+            );
+            Some(sheet.to_css())
+        }
+    }
+}
+
+/// Adds an element to the `<head>` of a root element, creating the `<head>`
+/// if there is none.
+pub(crate) fn add_to_head(root: &mut HtmlElement, element: HtmlElement) {
+    for child in root.children.make_mut() {
+        if let HtmlNode::Element(head) = child
+            && head.tag == tag::head
+        {
+            head.children.push(element.into());
+            return;
+        }
+    }
+
+    let head = HtmlElement::new(tag::head).with_children(eco_vec![element.into()]);
+    root.children.insert(0, head.into());
+}
+
+/// Encodes a root element into a string, resolving links in frames with the
+/// given introspector.
+///
+/// If `spans` is true, elements are annotated with their source spans.
+pub(crate) fn html_root(
+    root: &HtmlElement,
+    introspector: &Introspector,
+    spans: bool,
+) -> SourceResult<String> {
+    let mut w = Writer::new(introspector, true, false, spans);
     w.buf.push_str("<!DOCTYPE html>");
     write_indent(&mut w);
-    write_element(&mut w, &document.root)?;
+    write_element(&mut w, root)?;
+    if w.pretty {
+        w.buf.push('\n');
+    }
+    Ok(w.buf)
+}
+
+/// Encodes a root element into an XHTML string, resolving links in frames
+/// with the given introspector.
+///
+/// The root element must declare the XHTML namespace.
+pub(crate) fn xhtml_root(
+    root: &HtmlElement,
+    introspector: &Introspector,
+) -> SourceResult<String> {
+    let mut w = Writer::new(introspector, true, true, false);
+    w.buf
+        .push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE html>");
+    write_indent(&mut w);
+    write_element(&mut w, root)?;
     if w.pretty {
         w.buf.push('\n');
     }
@@ -32,12 +186,29 @@ struct Writer<'a> {
     introspector: &'a Introspector,
     /// Whether pretty printing is enabled.
     pretty: bool,
+    /// Whether to write XHTML, i.e. the XML syntax of HTML.
+    xhtml: bool,
+    /// Whether to annotate elements with their source spans in a
+    /// `data-typst-span` attribute.
+    spans: bool,
 }
 
 impl<'a> Writer<'a> {
     /// Creates a new writer.
-    fn new(introspector: &'a Introspector, pretty: bool) -> Self {
-        Self { buf: String::new(), level: 0, introspector, pretty }
+    fn new(
+        introspector: &'a Introspector,
+        pretty: bool,
+        xhtml: bool,
+        spans: bool,
+    ) -> Self {
+        Self {
+            buf: String::new(),
+            level: 0,
+            introspector,
+            pretty,
+            xhtml,
+            spans,
+        }
     }
 }
 
@@ -65,7 +236,11 @@ fn write_node(w: &mut Writer, node: &HtmlNode, escape_text: bool) -> SourceResul
 /// Encodes plain text into the writer.
 fn write_text(w: &mut Writer, text: &str, span: Span, escape: bool) -> SourceResult<()> {
     for c in text.chars() {
-        if escape || !charsets::is_valid_in_normal_element_text(c) {
+        // In XML, `]]>` must not appear in text.
+        if escape
+            || !charsets::is_valid_in_normal_element_text(c)
+            || (w.xhtml && c == '>')
+        {
             write_escape(w, c).at(span)?;
         } else {
             w.buf.push(c);
@@ -85,11 +260,13 @@ fn write_element(w: &mut Writer, element: &HtmlElement) -> SourceResult<()> {
 
         // If the string is empty, we can use shorthand syntax.
         // `<elem attr="">..</div` is equivalent to `<elem attr>..</div>`
-        if !value.is_empty() {
+        // XML does not have this shorthand.
+        if !value.is_empty() || w.xhtml {
             w.buf.push('=');
             w.buf.push('"');
             for c in value.chars() {
-                if charsets::is_valid_in_attribute_value(c) {
+                // XML additionally disallows `<` in attribute values.
+                if charsets::is_valid_in_attribute_value(c) && !(w.xhtml && c == '<') {
                     w.buf.push(c);
                 } else {
                     write_escape(w, c).at(element.span)?;
@@ -99,6 +276,13 @@ fn write_element(w: &mut Writer, element: &HtmlElement) -> SourceResult<()> {
         }
     }
 
+    if w.spans && !element.span.is_detached() {
+        write!(w.buf, " data-typst-span=\"{}\"", element.span.into_raw()).unwrap();
+    }
+
+    if w.xhtml && tag::is_void(element.tag) {
+        w.buf.push('/');
+    }
     w.buf.push('>');
 
     if tag::is_void(element.tag) {
@@ -108,14 +292,18 @@ fn write_element(w: &mut Writer, element: &HtmlElement) -> SourceResult<()> {
         return Ok(());
     }
 
-    // See HTML spec § 13.1.2.5.
-    if matches!(element.tag, tag::pre | tag::textarea) && starts_with_newline(element) {
+    // See HTML spec § 13.1.2.5. XML parsers, in contrast, keep the newline.
+    if !w.xhtml
+        && matches!(element.tag, tag::pre | tag::textarea)
+        && starts_with_newline(element)
+    {
         w.buf.push('\n');
     }
 
-    if tag::is_raw(element.tag) {
+    // XML has no raw text elements, so their text is escaped instead.
+    if tag::is_raw(element.tag) && !w.xhtml {
         write_raw(w, element)?;
-    } else if tag::is_escapable_raw(element.tag) {
+    } else if tag::is_raw(element.tag) || tag::is_escapable_raw(element.tag) {
         write_escapable_raw(w, element)?;
     } else if !element.children.is_empty() {
         write_children(w, element)?;
@@ -0,0 +1,945 @@
+use std::fmt::Write as _;
+use std::io::{Cursor, Write};
+
+use ecow::{EcoString, EcoVec, eco_format, eco_vec};
+use rustc_hash::{FxHashMap, FxHashSet};
+use typst_library::diag::{At, SourceResult, StrResult, bail, error};
+use typst_library::foundations::{Datetime, NativeElement, Smart, StyleChain};
+use typst_library::introspection::{Location, Tag};
+use typst_library::model::{HeadingElem, OutlineElem};
+use typst_library::text::{EmbeddingRestriction, Font, FontStyle, LocalName, Locale};
+use typst_syntax::Span;
+use xmlwriter::XmlWriter;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::assets::{decode_data_url, extension};
+use crate::split::Split;
+use crate::{HtmlDocument, HtmlElement, HtmlNode, attr, tag};
+
+/// The directory within the container that holds the publication.
+const ROOT: &str = "EPUB";
+
+/// The path of the navigation document.
+const NAV: &str = "nav.xhtml";
+
+/// The path of the stylesheet that declares the embedded fonts.
+const FONTS: &str = "fonts.css";
+
+/// Settings for EPUB export.
+#[derive(Debug, Default)]
+pub struct EpubOptions<'a> {
+    /// If not `Smart::Auto`, shall be a string that uniquely and stably
+    /// identifies the publication, for example a URN with its ISBN. It is
+    /// written to the package as is. If `Auto`, an identifier is derived from
+    /// a hash of the document's title and author.
+    pub ident: Smart<&'a str>,
+    /// If not `None`, shall be the time at which the publication was last
+    /// modified, which every EPUB must declare. If `None`, the document's date
+    /// is used instead.
+    pub modified: Option<Datetime>,
+}
+
+/// Export a document into an EPUB 3 publication.
+///
+/// The document is split into XHTML content documents like for multi-file
+/// HTML export. Images become separate resources and footnotes are turned into
+/// EPUB footnotes, which reading systems typically show in a popup. The fonts
+/// in which the document's text is set are embedded, as far as their licenses
+/// permit, since reading systems otherwise fall back to their own fonts.
+#[typst_macros::time(name = "epub")]
+pub fn epub(document: &HtmlDocument, options: &EpubOptions) -> SourceResult<Vec<u8>> {
+    let info = &document.info;
+    let Some(title) = &info.title else {
+        bail!(
+            Span::detached(),
+            "EPUB export requires a document title";
+            hint: "set the title with `set document(title: ..)`"
+        );
+    };
+
+    let Some(modified) = options
+        .modified
+        .or(info.date.custom().flatten())
+        .and_then(|datetime| format_datetime(datetime, true))
+    else {
+        bail!(
+            Span::detached(),
+            "EPUB export requires the date of the last modification";
+            hint: "set the date with `set document(date: ..)`"
+        );
+    };
+
+    let locale = info.locale.unwrap_or_default();
+    let mut split = crate::split::split(document, "xhtml");
+    move_footnotes(&mut split);
+    split.relink();
+
+    // Give headings IDs so that the navigation document can link to them.
+    let mut ids = FxHashMap::default();
+    for (path, nodes) in split.files_mut() {
+        crate::split::collect_ids(nodes, path, &mut ids);
+    }
+    let mut taken: FxHashSet<EcoString> = ids.into_keys().collect();
+    let mut targets = FxHashMap::default();
+    for (path, nodes) in split.files_mut() {
+        identify_headings(nodes, path, &mut taken, &mut targets, &mut vec![]);
+    }
+
+    // The index is only worth a file if there is something before the first
+    // chapter.
+    let skip_index = !split.chapters.is_empty() && !has_content(&split.front);
+
+    let mut package = Package::default();
+    let mut references = vec![];
+
+    // Embed the fonts and make every content document use them.
+    if let Some(css) = embed_fonts(&document.fonts, &mut package) {
+        package.add(Item {
+            id: "fonts".into(),
+            path: FONTS.into(),
+            media_type: "text/css".into(),
+            properties: vec![],
+            data: css.into_bytes(),
+        });
+        crate::encode::add_to_head(
+            &mut split.root,
+            HtmlElement::new(tag::link)
+                .with_attr(attr::rel, "stylesheet")
+                .with_attr(attr::href, FONTS),
+        );
+    }
+    let files: Vec<_> = split
+        .files_mut()
+        .skip(skip_index as usize)
+        .map(|(path, nodes)| (path.clone(), std::mem::take(nodes)))
+        .collect();
+
+    for (path, mut nodes) in files {
+        let mut features = Features::default();
+        prepare(&mut nodes, &mut package, &mut features, &mut references);
+
+        let chapter = split.chapters.iter().find(|chapter| chapter.path == path);
+        let root = split.root(chapter.map(|chapter| chapter.title.as_str()), nodes);
+        let xhtml = crate::encode::xhtml_root(
+            &declare_namespaces(root, locale),
+            &document.introspector,
+        )?;
+        let id: EcoString = path.trim_end_matches(".xhtml").into();
+        package.spine.push(id.clone());
+        package.add(Item {
+            id,
+            path,
+            media_type: "application/xhtml+xml".into(),
+            properties: features.properties(),
+            data: xhtml.into_bytes(),
+        });
+    }
+
+    let contents = OutlineElem::local_name(locale.lang, locale.region);
+    let nav = nav_document(document, &split, &targets, title, contents, locale);
+    package.add(Item {
+        id: "nav".into(),
+        path: NAV.into(),
+        media_type: "application/xhtml+xml".into(),
+        properties: vec!["nav"],
+        data: crate::encode::xhtml_root(&nav, &document.introspector)?.into_bytes(),
+    });
+
+    package.validate(&references)?;
+
+    let ident = match options.ident {
+        Smart::Custom(ident) => ident.into(),
+        Smart::Auto => {
+            let hash = typst_utils::hash128(&(title, &info.author));
+            let hex = format!("{hash:032x}");
+            eco_format!(
+                "urn:uuid:{}-{}-{}-{}-{}",
+                &hex[..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..]
+            )
+        }
+    };
+
+    let opf = package_document(document, &package, &ident, &modified, locale);
+    write_container(&package, opf).at(Span::detached())
+}
+
+/// The files of a publication.
+#[derive(Default)]
+struct Package {
+    /// The publication resources, in manifest order.
+    items: Vec<Item>,
+    /// The IDs of the content documents in reading order.
+    spine: Vec<EcoString>,
+    /// The paths of all resources.
+    paths: FxHashSet<EcoString>,
+}
+
+/// A publication resource.
+struct Item {
+    /// The resource's ID in the manifest.
+    id: EcoString,
+    /// The resource's path relative to the package document.
+    path: EcoString,
+    /// The resource's MIME type.
+    media_type: EcoString,
+    /// The resource's manifest properties.
+    properties: Vec<&'static str>,
+    /// The resource's contents.
+    data: Vec<u8>,
+}
+
+impl Package {
+    /// Adds a resource.
+    fn add(&mut self, item: Item) {
+        self.paths.insert(item.path.clone());
+        self.items.push(item);
+    }
+
+    /// Checks that the package is structurally valid and contains all
+    /// referenced resources.
+    fn validate(&self, references: &[(EcoString, Span)]) -> SourceResult<()> {
+        let mut ids = FxHashSet::default();
+        for item in &self.items {
+            if !ids.insert(&item.id) {
+                bail!(
+                    Span::detached(),
+                    "EPUB manifest contains duplicate ID `{}`",
+                    item.id
+                );
+            }
+        }
+
+        if self.spine.is_empty() {
+            bail!(Span::detached(), "EPUB publication has no content documents");
+        }
+
+        if let Some(id) = self.spine.iter().find(|id| !ids.contains(id)) {
+            bail!(Span::detached(), "EPUB spine refers to unknown item `{id}`");
+        }
+
+        let mut errors = EcoVec::new();
+        for (href, span) in references {
+            let path = href.split(['#', '?']).next().unwrap_or_default();
+            if !path.is_empty() && !self.paths.contains(path) {
+                errors.push(error!(
+                    *span,
+                    "EPUB publication refers to `{path}`, which is not part of it";
+                    hint: "link to web resources with an absolute URL instead"
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
+/// Which features a content document uses. These must be declared in the
+/// manifest.
+#[derive(Default)]
+struct Features {
+    svg: bool,
+    mathml: bool,
+    scripted: bool,
+}
+
+impl Features {
+    /// The manifest properties for the features.
+    fn properties(&self) -> Vec<&'static str> {
+        [(self.svg, "svg"), (self.mathml, "mathml"), (self.scripted, "scripted")]
+            .into_iter()
+            .filter(|&(used, _)| used)
+            .map(|(_, property)| property)
+            .collect()
+    }
+}
+
+/// Prepares the nodes of a content document for the publication.
+///
+/// This moves images into separate resources, declares the MathML namespace,
+/// records which features are used, and collects references to other files.
+fn prepare(
+    nodes: &mut EcoVec<HtmlNode>,
+    package: &mut Package,
+    features: &mut Features,
+    references: &mut Vec<(EcoString, Span)>,
+) {
+    for node in nodes.make_mut() {
+        match node {
+            HtmlNode::Element(element) => {
+                match element.tag {
+                    tag::img => extract_image(element, package),
+                    tag::math => {
+                        features.mathml = true;
+                        if element.attrs.get(attr::xmlns).is_none() {
+                            element.attrs.push_front(
+                                attr::xmlns,
+                                "http://www.w3.org/1998/Math/MathML",
+                            );
+                        }
+                    }
+                    tag::script => features.scripted = true,
+                    _ if element.tag.resolve().as_str() == "svg" => features.svg = true,
+                    _ => {}
+                }
+
+                for (key, value) in &element.attrs.0 {
+                    if matches!(*key, attr::href | attr::src) && is_relative(value) {
+                        references.push((value.clone(), element.span));
+                    }
+                }
+
+                prepare(&mut element.children, package, features, references);
+            }
+            HtmlNode::Frame(_) => features.svg = true,
+            HtmlNode::Tag(_) | HtmlNode::Text(..) => {}
+        }
+    }
+}
+
+/// Whether a URL is relative to the current file and not just a fragment.
+fn is_relative(url: &str) -> bool {
+    !url.is_empty()
+        && !url.starts_with(['#', '/'])
+        && !url.split(['/', '?', '#']).next().unwrap_or_default().contains(':')
+}
+
+/// Moves an image embedded as a data URL into a separate resource.
+fn extract_image(element: &mut HtmlElement, package: &mut Package) {
+    for (key, value) in element.attrs.0.make_mut() {
+        if *key != attr::src {
+            continue;
+        }
+
+        let Some((media_type, data)) = decode_data_url(value) else { continue };
+        let Some(ext) = extension(media_type) else { continue };
+
+        let hash = typst_utils::hash128(&data);
+        let path = eco_format!("images/{hash:032x}.{ext}");
+        if !package.paths.contains(&path) {
+            package.add(Item {
+                id: eco_format!("image-{hash:032x}"),
+                path: path.clone(),
+                media_type: media_type.into(),
+                properties: vec![],
+                data,
+            });
+        }
+        *value = path;
+    }
+}
+
+/// Adds the fonts whose licenses permit embedding to the package.
+///
+/// Returns a stylesheet that declares the added fonts and sets the text in
+/// them, or `None` if no font was added.
+fn embed_fonts(fonts: &[Font], package: &mut Package) -> Option<String> {
+    let mut css = String::new();
+    let mut family = None;
+    for font in fonts {
+        // Font collections are not supported by reading systems.
+        let data = font.data().as_slice();
+        if font.index() != 0 || data.starts_with(b"ttcf") {
+            continue;
+        }
+
+        if matches!(
+            font.embedding_restriction(),
+            Some(EmbeddingRestriction::Restricted | EmbeddingRestriction::BitmapOnly)
+        ) {
+            continue;
+        }
+
+        let (ext, media_type) = if data.starts_with(b"OTTO") {
+            ("otf", "font/otf")
+        } else {
+            ("ttf", "font/ttf")
+        };
+        let hash = typst_utils::hash128(data);
+        let path = eco_format!("fonts/{hash:032x}.{ext}");
+        if package.paths.contains(&path) {
+            continue;
+        }
+
+        package.add(Item {
+            id: eco_format!("font-{hash:032x}"),
+            path: path.clone(),
+            media_type: media_type.into(),
+            properties: vec![],
+            data: data.to_vec(),
+        });
+
+        let info = font.info();
+        let name = info.family.replace('\\', "\\\\").replace('"', "\\\"");
+        let style = match info.variant.style {
+            FontStyle::Normal => "normal",
+            FontStyle::Italic | FontStyle::Oblique => "italic",
+        };
+        writeln!(
+            css,
+            "@font-face {{ font-family: \"{name}\"; font-weight: {}; \
+             font-style: {style}; src: url(\"{path}\"); }}",
+            info.variant.weight.to_number(),
+        )
+        .unwrap();
+        family.get_or_insert(name);
+    }
+
+    let family = family?;
+    writeln!(css, "body {{ font-family: \"{family}\"; }}").unwrap();
+    Some(css)
+}
+
+/// Turns footnotes into EPUB footnotes.
+///
+/// HTML export collects all footnotes in a section at the end of the document.
+/// Reading systems only show footnotes in a popup if they are in the same file
+/// as their reference, so each footnote is moved to the end of the file that
+/// first references it.
+fn move_footnotes(split: &mut Split) {
+    let mut notes = vec![];
+    for (_, nodes) in split.files_mut() {
+        nodes.retain(|node| match node {
+            HtmlNode::Element(element)
+                if element.tag == tag::section
+                    && element
+                        .attrs
+                        .get(attr::role)
+                        .is_some_and(|role| role == "doc-endnotes") =>
+            {
+                collect_footnotes(&element.children, &mut notes);
+                false
+            }
+            _ => true,
+        });
+    }
+
+    if notes.is_empty() {
+        return;
+    }
+
+    let mut notes: Vec<_> = notes.into_iter().map(Some).collect();
+    let positions: FxHashMap<EcoString, usize> = notes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, note)| Some((note.as_ref()?.attrs.get(attr::id)?.clone(), i)))
+        .collect();
+
+    for (_, nodes) in split.files_mut() {
+        let mut refs = vec![];
+        mark_noterefs(nodes, &mut refs);
+        for id in refs {
+            if let Some(note) = positions.get(&id).and_then(|&i| notes[i].take()) {
+                nodes.push(note.into());
+            }
+        }
+    }
+
+    // Footnotes without a reference end up in the last file.
+    if let Some((_, nodes)) = split.files_mut().last() {
+        nodes.extend(notes.into_iter().flatten().map(Into::into));
+    }
+}
+
+/// Turns the list items of the footnote section into EPUB footnotes.
+fn collect_footnotes(nodes: &[HtmlNode], notes: &mut Vec<HtmlElement>) {
+    for node in nodes {
+        let HtmlNode::Element(element) = node else { continue };
+        if element.tag == tag::li {
+            let mut note = HtmlElement::new(tag::aside)
+                .with_attr(attr::epub_type, "footnote")
+                .with_attr(attr::role, "doc-footnote")
+                .with_children(element.children.clone())
+                .spanned(element.span);
+            if let Some(id) = element.attrs.get(attr::id) {
+                note.attrs.push_front(attr::id, id.clone());
+            }
+            notes.push(note);
+        } else {
+            collect_footnotes(&element.children, notes);
+        }
+    }
+}
+
+/// Marks footnote references as such for reading systems and collects the IDs
+/// of the footnotes they refer to.
+fn mark_noterefs(nodes: &mut EcoVec<HtmlNode>, refs: &mut Vec<EcoString>) {
+    for node in nodes.make_mut() {
+        let HtmlNode::Element(element) = node else { continue };
+        if element.tag == tag::a
+            && element
+                .attrs
+                .get(attr::role)
+                .is_some_and(|role| role == "doc-noteref")
+        {
+            if let Some((_, id)) =
+                element.attrs.get(attr::href).and_then(|href| href.split_once('#'))
+            {
+                refs.push(id.into());
+            }
+            element.attrs.push(attr::epub_type, "noteref");
+        }
+        mark_noterefs(&mut element.children, refs);
+    }
+}
+
+/// Assigns IDs to the elements produced by headings and records links to
+/// them.
+fn identify_headings(
+    nodes: &mut EcoVec<HtmlNode>,
+    path: &str,
+    taken: &mut FxHashSet<EcoString>,
+    targets: &mut FxHashMap<Location, EcoString>,
+    pending: &mut Vec<Location>,
+) {
+    for node in nodes.make_mut() {
+        match node {
+            HtmlNode::Tag(Tag::Start(elem, _)) if elem.is::<HeadingElem>() => {
+                pending.extend(elem.location());
+            }
+            HtmlNode::Element(element) => {
+                if !pending.is_empty() {
+                    let id = match element.attrs.get(attr::id) {
+                        Some(id) => id.clone(),
+                        None => {
+                            let id = (1..)
+                                .map(|i| eco_format!("heading-{i}"))
+                                .find(|id| !taken.contains(id))
+                                .unwrap();
+                            taken.insert(id.clone());
+                            element.attrs.push_front(attr::id, id.clone());
+                            id
+                        }
+                    };
+                    for loc in pending.drain(..) {
+                        targets.insert(loc, eco_format!("{path}#{id}"));
+                    }
+                }
+                identify_headings(&mut element.children, path, taken, targets, pending);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether there is anything but whitespace among the nodes.
+fn has_content(nodes: &[HtmlNode]) -> bool {
+    nodes.iter().any(|node| match node {
+        HtmlNode::Tag(_) => false,
+        HtmlNode::Text(text, _) => !text.trim().is_empty(),
+        HtmlNode::Element(_) | HtmlNode::Frame(_) => true,
+    })
+}
+
+/// Declares the namespaces and language on the root element of a content
+/// document.
+fn declare_namespaces(mut root: HtmlElement, locale: Locale) -> HtmlElement {
+    let lang = root.attrs.get(attr::lang).cloned().unwrap_or_else(|| {
+        let lang = format_locale(locale);
+        root.attrs.push_front(attr::lang, lang.clone());
+        lang
+    });
+    root.attrs.push_front(attr::xml_lang, lang);
+    root.attrs
+        .push_front(attr::xmlns_epub, "http://www.idpf.org/2007/ops");
+    root.attrs.push_front(attr::xmlns, "http://www.w3.org/1999/xhtml");
+    root
+}
+
+/// Creates the navigation document with the table of contents.
+///
+/// The table of contents lists the headings that are part of the outline. If
+/// there are none, it lists the content documents instead.
+fn nav_document(
+    document: &HtmlDocument,
+    split: &Split,
+    targets: &FxHashMap<Location, EcoString>,
+    title: &EcoString,
+    contents: &str,
+    locale: Locale,
+) -> HtmlElement {
+    let mut entries = vec![];
+    for elem in document.introspector.query(&HeadingElem::ELEM.select()) {
+        let heading = elem.to_packed::<HeadingElem>().unwrap();
+        if !heading.outlined.get(StyleChain::default()) {
+            continue;
+        }
+        let Some(href) = heading.location().and_then(|loc| targets.get(&loc)) else {
+            continue;
+        };
+        let level = heading.resolve_level(StyleChain::default()).get();
+        entries.push((level, heading.body.plain_text(), href.clone()));
+    }
+
+    if entries.is_empty() {
+        entries = split
+            .chapters
+            .iter()
+            .map(|chapter| (1, chapter.title.clone(), chapter.path.clone()))
+            .collect();
+    }
+
+    if entries.is_empty() {
+        entries.push((1, title.clone(), split.index.clone()));
+    }
+
+    let text = |text: &str| eco_vec![HtmlNode::text(text, Span::detached())];
+    let head = HtmlElement::new(tag::head).with_children(eco_vec![
+        HtmlElement::new(tag::meta).with_attr(attr::charset, "utf-8").into(),
+        HtmlElement::new(tag::title).with_children(text(contents)).into(),
+    ]);
+
+    let nav = HtmlElement::new(tag::nav)
+        .with_attr(attr::epub_type, "toc")
+        .with_attr(attr::role, "doc-toc")
+        .with_children(eco_vec![
+            HtmlElement::new(tag::h1).with_children(text(contents)).into(),
+            nav_list(&entries).into(),
+        ]);
+
+    let body = HtmlElement::new(tag::body).with_children(eco_vec![nav.into()]);
+    let root =
+        HtmlElement::new(tag::html).with_children(eco_vec![head.into(), body.into()]);
+    declare_namespaces(root, locale)
+}
+
+/// Creates a nested list of navigation entries, each consisting of a level, a
+/// title, and a link.
+fn nav_list(entries: &[(usize, EcoString, EcoString)]) -> HtmlElement {
+    let mut items = EcoVec::new();
+    let mut i = 0;
+    while i < entries.len() {
+        let (level, title, href) = &entries[i];
+        let end = entries[i + 1..]
+            .iter()
+            .position(|(next, ..)| next <= level)
+            .map_or(entries.len(), |n| i + 1 + n);
+
+        let link = HtmlElement::new(tag::a)
+            .with_attr(attr::href, href.clone())
+            .with_children(eco_vec![HtmlNode::text(title.clone(), Span::detached())]);
+        let mut children = eco_vec![link.into()];
+        if end > i + 1 {
+            children.push(nav_list(&entries[i + 1..end]).into());
+        }
+
+        items.push(HtmlElement::new(tag::li).with_children(children).into());
+        i = end;
+    }
+    HtmlElement::new(tag::ol).with_children(items)
+}
+
+/// Creates the package document, which describes the publication.
+fn package_document(
+    document: &HtmlDocument,
+    package: &Package,
+    ident: &str,
+    modified: &str,
+    locale: Locale,
+) -> String {
+    let info = &document.info;
+    let mut xml = XmlWriter::new(xmlwriter::Options::default());
+    xml.write_declaration();
+    xml.start_element("package");
+    xml.write_attribute("xmlns", "http://www.idpf.org/2007/opf");
+    xml.write_attribute("version", "3.0");
+    xml.write_attribute("unique-identifier", "uid");
+    xml.write_attribute("xml:lang", &format_locale(locale));
+
+    xml.start_element("metadata");
+    xml.write_attribute("xmlns:dc", "http://purl.org/dc/elements/1.1/");
+    xml.start_element("dc:identifier");
+    xml.write_attribute("id", "uid");
+    xml.write_text(ident);
+    xml.end_element();
+    write_text_element(&mut xml, "dc:title", info.title.as_deref().unwrap_or_default());
+    write_text_element(&mut xml, "dc:language", &format_locale(locale));
+    for author in &info.author {
+        write_text_element(&mut xml, "dc:creator", author);
+    }
+    if let Some(description) = &info.description {
+        write_text_element(&mut xml, "dc:description", description);
+    }
+    for keyword in &info.keywords {
+        write_text_element(&mut xml, "dc:subject", keyword);
+    }
+    if let Some(date) = info
+        .date
+        .custom()
+        .flatten()
+        .and_then(|date| format_datetime(date, false))
+    {
+        write_text_element(&mut xml, "dc:date", &date);
+    }
+    xml.start_element("meta");
+    xml.write_attribute("property", "dcterms:modified");
+    xml.write_text(modified);
+    xml.end_element();
+    xml.end_element();
+
+    xml.start_element("manifest");
+    for item in &package.items {
+        xml.start_element("item");
+        xml.write_attribute("id", &item.id);
+        xml.write_attribute("href", &item.path);
+        xml.write_attribute("media-type", &item.media_type);
+        if !item.properties.is_empty() {
+            xml.write_attribute("properties", &item.properties.join(" "));
+        }
+        xml.end_element();
+    }
+    xml.end_element();
+
+    xml.start_element("spine");
+    for id in &package.spine {
+        xml.start_element("itemref");
+        xml.write_attribute("idref", id);
+        xml.end_element();
+    }
+    xml.end_element();
+
+    xml.end_element();
+    xml.end_document()
+}
+
+/// Writes an element that only contains text.
+fn write_text_element(xml: &mut XmlWriter, name: &str, text: &str) {
+    xml.start_element(name);
+    xml.write_text(text);
+    xml.end_element();
+}
+
+/// Writes the package into an OCF ZIP container.
+fn write_container(package: &Package, opf: String) -> StrResult<Vec<u8>> {
+    let mut xml = XmlWriter::new(xmlwriter::Options::default());
+    xml.write_declaration();
+    xml.start_element("container");
+    xml.write_attribute("version", "1.0");
+    xml.write_attribute("xmlns", "urn:oasis:names:tc:opendocument:xmlns:container");
+    xml.start_element("rootfiles");
+    xml.start_element("rootfile");
+    xml.write_attribute("full-path", &format!("{ROOT}/package.opf"));
+    xml.write_attribute("media-type", "application/oebps-package+xml");
+    let container = xml.end_document();
+
+    let write = || -> zip::result::ZipResult<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let deflated =
+            SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        // The MIME type must come first and be stored uncompressed.
+        zip.start_file(
+            "mimetype",
+            SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        zip.write_all(b"application/epub+zip")?;
+
+        zip.start_file("META-INF/container.xml", deflated)?;
+        zip.write_all(container.as_bytes())?;
+
+        zip.start_file(format!("{ROOT}/package.opf"), deflated)?;
+        zip.write_all(opf.as_bytes())?;
+
+        for item in &package.items {
+            zip.start_file(format!("{ROOT}/{}", item.path), deflated)?;
+            zip.write_all(&item.data)?;
+        }
+
+        Ok(zip.finish()?.into_inner())
+    };
+
+    write().map_err(|err| eco_format!("failed to write EPUB container ({err})"))
+}
+
+/// Formats a locale as a BCP 47 language tag.
+fn format_locale(locale: Locale) -> EcoString {
+    match locale.region {
+        Some(region) => eco_format!("{}-{}", locale.lang.as_str(), region.as_str()),
+        None => locale.lang.as_str().into(),
+    }
+}
+
+/// Formats a datetime as a W3C date, with or without the time.
+///
+/// Returns `None` if the datetime lacks a date.
+fn format_datetime(datetime: Datetime, time: bool) -> Option<EcoString> {
+    let date = eco_format!(
+        "{:04}-{:02}-{:02}",
+        datetime.year()?,
+        datetime.month()?,
+        datetime.day()?
+    );
+    if !time {
+        return Some(date);
+    }
+
+    Some(eco_format!(
+        "{date}T{:02}:{:02}:{:02}Z",
+        datetime.hour().unwrap_or(0),
+        datetime.minute().unwrap_or(0),
+        datetime.second().unwrap_or(0)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split::Chapter;
+
+    fn element(node: &HtmlNode) -> &HtmlElement {
+        match node {
+            HtmlNode::Element(element) => element,
+            _ => panic!("expected element"),
+        }
+    }
+
+    fn chapter(path: &str, nodes: EcoVec<HtmlNode>) -> Chapter {
+        Chapter { path: path.into(), title: path.into(), nodes }
+    }
+
+    fn noteref(href: &str) -> HtmlNode {
+        HtmlElement::new(tag::a)
+            .with_attr(attr::role, "doc-noteref")
+            .with_attr(attr::href, href)
+            .into()
+    }
+
+    fn item(id: &str, path: &str) -> Item {
+        Item {
+            id: id.into(),
+            path: path.into(),
+            media_type: "application/xhtml+xml".into(),
+            properties: vec![],
+            data: vec![],
+        }
+    }
+
+    #[test]
+    fn test_move_footnotes() {
+        let note = |id: &str| -> HtmlNode {
+            HtmlElement::new(tag::li).with_attr(attr::id, id).into()
+        };
+        let section = HtmlElement::new(tag::section)
+            .with_attr(attr::role, "doc-endnotes")
+            .with_children(eco_vec![
+                HtmlElement::new(tag::ol)
+                    .with_children(eco_vec![note("fn-1"), note("fn-2"), note("fn-3")])
+                    .into()
+            ]);
+
+        let mut split = Split {
+            root: HtmlElement::new(tag::html),
+            title: None,
+            index: "index.xhtml".into(),
+            front: EcoVec::new(),
+            chapters: vec![
+                chapter("chapter-1.xhtml", eco_vec![noteref("chapter-2.xhtml#fn-2")]),
+                chapter("chapter-2.xhtml", eco_vec![noteref("#fn-1"), section.into()]),
+            ],
+        };
+        move_footnotes(&mut split);
+
+        // The first chapter gets the footnote it references.
+        let nodes = &split.chapters[0].nodes;
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(
+            element(&nodes[0]).attrs.get(attr::epub_type).map(EcoString::as_str),
+            Some("noteref")
+        );
+        let aside = element(&nodes[1]);
+        assert_eq!(aside.tag, tag::aside);
+        assert_eq!(aside.attrs.get(attr::id).map(EcoString::as_str), Some("fn-2"));
+        assert_eq!(
+            aside.attrs.get(attr::epub_type).map(EcoString::as_str),
+            Some("footnote")
+        );
+
+        // The second chapter loses the section, gets its referenced footnote,
+        // and the unreferenced one at the end.
+        let ids: Vec<_> = split.chapters[1].nodes[1..]
+            .iter()
+            .map(|node| element(node).attrs.get(attr::id).unwrap().as_str())
+            .collect();
+        assert_eq!(ids, ["fn-1", "fn-3"]);
+    }
+
+    #[test]
+    fn test_nav_list() {
+        let entries = [
+            (1, "A".into(), "a.xhtml".into()),
+            (2, "A.1".into(), "a.xhtml#1".into()),
+            (3, "A.1.1".into(), "a.xhtml#2".into()),
+            (2, "A.2".into(), "a.xhtml#3".into()),
+            (1, "B".into(), "b.xhtml".into()),
+        ];
+        let list = nav_list(&entries);
+        assert_eq!(list.tag, tag::ol);
+        assert_eq!(list.children.len(), 2);
+
+        // The first item has a link and a nested list with two items, the
+        // first of which has yet another nested list.
+        let a = element(&list.children[0]);
+        assert_eq!(a.children.len(), 2);
+        let nested = element(&a.children[1]);
+        assert_eq!(nested.tag, tag::ol);
+        assert_eq!(nested.children.len(), 2);
+        assert_eq!(element(&nested.children[0]).children.len(), 2);
+        assert_eq!(element(&nested.children[1]).children.len(), 1);
+
+        // The second item only has a link.
+        let b = element(&list.children[1]);
+        assert_eq!(b.children.len(), 1);
+        assert_eq!(
+            element(&b.children[0]).attrs.get(attr::href).map(EcoString::as_str),
+            Some("b.xhtml")
+        );
+    }
+
+    #[test]
+    fn test_nav_list_starting_deeper() {
+        // A list that starts at a deeper level than it continues does not
+        // lose any entries.
+        let entries =
+            [(2, "A".into(), "a.xhtml".into()), (1, "B".into(), "b.xhtml".into())];
+        assert_eq!(nav_list(&entries).children.len(), 2);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut package = Package::default();
+        package.add(item("chapter-1", "chapter-1.xhtml"));
+        package.spine.push("chapter-1".into());
+        assert!(package.validate(&[]).is_ok());
+        assert!(
+            package
+                .validate(&[("chapter-1.xhtml#a".into(), Span::detached())])
+                .is_ok()
+        );
+        assert!(
+            package
+                .validate(&[("images/a.png".into(), Span::detached())])
+                .is_err()
+        );
+
+        let mut package = Package::default();
+        package.add(item("chapter-1", "chapter-1.xhtml"));
+        assert!(package.validate(&[]).is_err());
+
+        package.spine.push("chapter-2".into());
+        assert!(package.validate(&[]).is_err());
+
+        let mut package = Package::default();
+        package.add(item("chapter-1", "chapter-1.xhtml"));
+        package.add(item("chapter-1", "chapter-2.xhtml"));
+        package.spine.push("chapter-1".into());
+        assert!(package.validate(&[]).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Typst's HTML exporter.
 
+mod assets;
 mod attr;
 mod charsets;
 mod convert;
@@ -7,30 +8,44 @@ mod css;
 mod document;
 mod dom;
 mod encode;
+mod epub;
 mod fragment;
 mod link;
+mod math;
 mod rules;
+mod schema;
+mod split;
 mod tag;
 mod typed;
 
 pub use self::document::html_document;
 pub use self::dom::*;
-pub use self::encode::html;
+pub use self::encode::{HtmlCss, HtmlOptions, HtmlOutput, html, html_with_options};
+pub use self::epub::{EpubOptions, epub};
 pub use self::rules::{html_span_filled, register};
+pub use self::schema::HtmlSchema;
+pub use self::split::{HtmlFile, html_split};
 
 use ecow::EcoString;
 use typst_library::Category;
-use typst_library::foundations::{Content, Module, Scope};
-use typst_library::introspection::Location;
+use typst_library::diag::{SourceResult, bail};
+use typst_library::engine::Engine;
+use typst_library::foundations::{Args, Bytes, Construct, Content, Module, Scope};
+use typst_library::introspection::{Locatable, Location};
 use typst_macros::elem;
 
-/// Creates the module with all HTML definitions.
-pub fn module() -> Module {
+/// Creates the module with all HTML definitions, including typed functions for
+/// the custom elements described by the given [schemas](HtmlSchema).
+///
+/// Schemas that are not valid are ignored.
+pub fn module(schemas: &[Bytes]) -> Module {
     let mut html = Scope::deduplicating();
     html.start_category(Category::Html);
     html.define_elem::<HtmlElem>();
     html.define_elem::<FrameElem>();
-    crate::typed::define(&mut html);
+    html.define_elem::<SplitElem>();
+    html.define_elem::<CssElem>();
+    crate::typed::define(&mut html, schemas);
     Module::new("html", html)
 }
 
@@ -143,3 +158,80 @@ pub struct FrameElem {
     #[required]
     pub body: Content,
 }
+
+/// Marks where a new file starts when exporting HTML to multiple files.
+///
+/// By default, multi-file HTML export starts a new file at each level-1
+/// heading. If a document contains split markers, it instead starts new files
+/// only at these. Markers must be placed at the top level of the document, not
+/// within other elements. In single-file HTML export and all other export
+/// formats, markers have no effect.
+///
+/// ```typ
+/// = Introduction
+/// ...
+///
+/// #html.split(title: "Appendix")
+/// == Measurements
+/// ...
+/// ```
+#[elem(Locatable)]
+pub struct SplitElem {
+    /// The title of the file that starts at this marker, used in the
+    /// navigation and table of contents.
+    ///
+    /// If `{none}`, the title of the file's first heading is used.
+    pub title: Option<EcoString>,
+}
+
+/// Maps styling from set and show rules to CSS in HTML export.
+///
+/// By default, HTML export focuses on semantic markup and ignores most
+/// styling, like the color or size of text. With this function, you can opt
+/// into mapping some of it to CSS. Styles are then emitted wherever they
+/// differ from those of the surrounding element: On the elements themselves
+/// or, for text, on `span` elements around it. Properties that browsers already
+/// style on their own, like the size and weight of headings, are left to them.
+///
+/// This function can only be used with set rules.
+///
+/// ```typ
+/// #set html.css(text: true, spacing: true)
+/// #set text(fill: maroon)
+/// #set par(spacing: 2em)
+///
+/// This is *maroon* and #text(size: 1.5em)[large].
+/// ```
+///
+/// The styles end up in inline `style` attributes. When exporting, they can
+/// instead be collected into a stylesheet with one class per distinct style.
+#[elem(name = "css", title = "CSS", Construct)]
+pub struct CssElem {
+    /// Whether to map the fill, size, and weight of text to CSS.
+    ///
+    /// Only solid fills are mapped. Font sizes are emitted relative to the
+    /// surrounding element's font size.
+    #[ghost]
+    #[default(false)]
+    pub text: bool,
+
+    /// Whether to map the spacing between paragraphs and around blocks to CSS
+    /// margins.
+    ///
+    /// Only spacing that is set to a fixed length is mapped.
+    #[ghost]
+    #[default(false)]
+    pub spacing: bool,
+
+    /// The appearance of the surrounding element, against which the
+    /// appearance of its contents is compared.
+    #[internal]
+    #[ghost]
+    pub base: Option<css::Appearance>,
+}
+
+impl Construct for CssElem {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "can only be used in set rules")
+    }
+}
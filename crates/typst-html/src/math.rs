@@ -0,0 +1,834 @@
+use codex::styling::{MathStyle, to_style};
+use ecow::{EcoString, EcoVec, eco_format, eco_vec};
+use typst_library::diag::{SourceResult, warning};
+use typst_library::engine::Engine;
+use typst_library::foundations::{Content, Packed, Smart, Style, StyleChain, SymbolElem};
+use typst_library::introspection::{SplitLocator, TagElem};
+use typst_library::layout::{HAlignment, HElem, Spacing};
+use typst_library::math::*;
+use typst_library::routines::{Arenas, RealizationKind};
+use typst_library::text::{LinebreakElem, SmartQuoter, SpaceElem, TextElem};
+use typst_library::visualize::Paint;
+use typst_syntax::Span;
+use typst_utils::{LazyHash, Numeric, default_math_class};
+use unicode_math_class::MathClass;
+
+use crate::convert::Whitespace;
+use crate::fragment::html_inline_fragment;
+use crate::{HtmlElement, HtmlNode, HtmlTag, attr, css, tag};
+
+/// Converts the body of a `<math>` element into MathML Core nodes.
+pub fn convert_math(
+    engine: &mut Engine,
+    content: &Content,
+    locator: &mut SplitLocator,
+    styles: StyleChain,
+) -> SourceResult<EcoVec<HtmlNode>> {
+    MathConverter { engine, locator }.row(content, styles)
+}
+
+/// State during conversion.
+struct MathConverter<'a, 'y, 'z> {
+    engine: &'a mut Engine<'y>,
+    locator: &'a mut SplitLocator<'z>,
+}
+
+impl MathConverter<'_, '_, '_> {
+    /// Converts content into a sequence of MathML nodes.
+    ///
+    /// If the content contains line breaks or alignment points, the result is
+    /// a single `<mtable>` with one row per line and one cell per aligned
+    /// column.
+    fn row(
+        &mut self,
+        content: &Content,
+        styles: StyleChain,
+    ) -> SourceResult<EcoVec<HtmlNode>> {
+        let mut rows = self.cells(content, styles)?;
+        if let [row] = rows.as_mut_slice()
+            && let [cell] = row.as_mut_slice()
+        {
+            return Ok(std::mem::take(cell));
+        }
+
+        let aligned = rows.iter().any(|row| row.len() > 1);
+        let rows = rows.into_iter().map(|row| {
+            let cells = row.into_iter().enumerate().map(|(i, cell)| {
+                // Like in laid-out equations, columns are alternately aligned
+                // to the right and to the left of the alignment points.
+                let align = aligned.then_some(if i % 2 == 0 { "right" } else { "left" });
+                mtd(cell, align)
+            });
+            mtr(cells)
+        });
+
+        Ok(eco_vec![table(rows, content.span())])
+    }
+
+    /// Converts content into a single MathML node, grouping it in an `<mrow>`
+    /// if necessary.
+    fn single(
+        &mut self,
+        content: &Content,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let nodes = self.row(content, styles)?;
+        Ok(group(nodes, content.span()))
+    }
+
+    /// Realizes content and converts it into rows of cells, split at line
+    /// breaks and alignment points.
+    fn cells(
+        &mut self,
+        content: &Content,
+        styles: StyleChain,
+    ) -> SourceResult<Vec<Vec<EcoVec<HtmlNode>>>> {
+        let arenas = Arenas::default();
+        let children = (self.engine.routines.realize)(
+            RealizationKind::Math,
+            self.engine,
+            self.locator,
+            &arenas,
+            content,
+            styles,
+        )?;
+
+        let mut rows = vec![];
+        let mut row = vec![];
+        let mut cell = EcoVec::new();
+        for (child, styles) in children {
+            if child.is::<LinebreakElem>() {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            } else if child.is::<AlignPointElem>() {
+                row.push(std::mem::take(&mut cell));
+            } else {
+                self.handle(&mut cell, child, styles)?;
+            }
+        }
+
+        // A trailing line break does not start a new line.
+        if !rows.is_empty() && row.is_empty() && cell.is_empty() {
+            return Ok(rows);
+        }
+
+        row.push(cell);
+        rows.push(row);
+        Ok(rows)
+    }
+
+    /// Converts a realized element into MathML node(s).
+    fn handle(
+        &mut self,
+        out: &mut EcoVec<HtmlNode>,
+        elem: &Content,
+        styles: StyleChain,
+    ) -> SourceResult<()> {
+        if let Some(elem) = elem.to_packed::<TagElem>() {
+            out.push(elem.tag.clone().into());
+        } else if elem.is::<SpaceElem>() {
+            // Spacing around operators is determined by the MathML renderer.
+        } else if let Some(elem) = elem.to_packed::<HElem>() {
+            if let Spacing::Rel(rel) = elem.amount
+                && rel.rel.is_zero()
+                && !rel.abs.is_zero()
+            {
+                out.push(
+                    HtmlElement::new(tag::mspace)
+                        .with_attr(attr::width, eco_format!("{}", css::length(rel.abs)))
+                        .spanned(elem.span())
+                        .into(),
+                );
+            }
+        } else if let Some(elem) = elem.to_packed::<TextElem>() {
+            out.push(convert_text(elem, styles));
+        } else if let Some(elem) = elem.to_packed::<SymbolElem>() {
+            out.push(convert_symbol(&elem.text, elem.span(), styles));
+        } else if let Some(elem) = elem.to_packed::<ClassElem>() {
+            let style = EquationElem::class.set(Some(elem.class)).wrap();
+            out.extend(self.row(&elem.body, styles.chain(&style))?);
+        } else if let Some(elem) = elem.to_packed::<AccentElem>() {
+            out.push(self.convert_accent(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<AttachElem>() {
+            out.push(self.convert_attach(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<PrimesElem>() {
+            let primes: EcoString = match elem.count {
+                1 => "′".into(),
+                2 => "″".into(),
+                3 => "‴".into(),
+                4 => "⁗".into(),
+                count => "′".repeat(count).into(),
+            };
+            out.push(token(tag::mo, primes, elem.span()).into());
+        } else if let Some(elem) = elem.to_packed::<ScriptsElem>() {
+            out.extend(self.row(&elem.body, styles)?);
+        } else if let Some(elem) = elem.to_packed::<LimitsElem>() {
+            out.extend(self.row(&elem.body, styles)?);
+        } else if let Some(elem) = elem.to_packed::<CancelElem>() {
+            out.push(self.convert_cancel(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<FracElem>() {
+            out.push(self.convert_frac(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<BinomElem>() {
+            out.push(self.convert_binom(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<LrElem>() {
+            let body = self.row(&elem.body, styles)?;
+            out.push(mrow(body, elem.span()));
+        } else if let Some(elem) = elem.to_packed::<MidElem>() {
+            // Fences stretch by default, but other operators, like a vertical
+            // bar in the middle of a set, have to be asked to.
+            let mut body = self.row(&elem.body, styles)?;
+            for node in body.make_mut() {
+                if let HtmlNode::Element(element) = node
+                    && element.tag == tag::mo
+                {
+                    element.attrs.push(attr::stretchy, "true");
+                }
+            }
+            out.extend(body);
+        } else if let Some(elem) = elem.to_packed::<VecElem>() {
+            out.push(self.convert_vec(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<MatElem>() {
+            out.push(self.convert_mat(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<CasesElem>() {
+            out.push(self.convert_cases(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<OpElem>() {
+            let text = elem.text.plain_text();
+            let mut op = token(tag::mi, text.clone(), elem.span());
+            if text.chars().count() == 1 {
+                op.attrs.push(attr::mathvariant, "normal");
+            }
+            out.push(op.into());
+        } else if let Some(elem) = elem.to_packed::<RootElem>() {
+            out.push(self.convert_root(elem, styles)?);
+        } else if let Some(elem) = elem.to_packed::<StretchElem>() {
+            out.extend(self.row(&elem.body, styles)?);
+        } else if let Some(elem) = elem.to_packed::<UnderlineElem>() {
+            out.push(self.convert_under_over(&elem.body, None, '_', false, styles)?);
+        } else if let Some(elem) = elem.to_packed::<OverlineElem>() {
+            out.push(self.convert_under_over(&elem.body, None, '‾', true, styles)?);
+        } else if let Some(elem) = elem.to_packed::<UnderbraceElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(
+                self.convert_under_over(&elem.body, annotation, '⏟', false, styles)?,
+            );
+        } else if let Some(elem) = elem.to_packed::<OverbraceElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(self.convert_under_over(&elem.body, annotation, '⏞', true, styles)?);
+        } else if let Some(elem) = elem.to_packed::<UnderbracketElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(
+                self.convert_under_over(&elem.body, annotation, '⎵', false, styles)?,
+            );
+        } else if let Some(elem) = elem.to_packed::<OverbracketElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(self.convert_under_over(&elem.body, annotation, '⎴', true, styles)?);
+        } else if let Some(elem) = elem.to_packed::<UnderparenElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(
+                self.convert_under_over(&elem.body, annotation, '⏝', false, styles)?,
+            );
+        } else if let Some(elem) = elem.to_packed::<OverparenElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(self.convert_under_over(&elem.body, annotation, '⏜', true, styles)?);
+        } else if let Some(elem) = elem.to_packed::<UndershellElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(
+                self.convert_under_over(&elem.body, annotation, '⏡', false, styles)?,
+            );
+        } else if let Some(elem) = elem.to_packed::<OvershellElem>() {
+            let annotation = elem.annotation.get_ref(styles).as_ref();
+            out.push(self.convert_under_over(&elem.body, annotation, '⏠', true, styles)?);
+        } else {
+            out.push(self.convert_external(elem, styles)?);
+        }
+
+        Ok(())
+    }
+
+    /// Converts an [`AttachElem`].
+    fn convert_attach(
+        &mut self,
+        elem: &Packed<AttachElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let merged = elem.merge_base();
+        let elem = merged.as_ref().unwrap_or(elem);
+        let span = elem.span();
+
+        let limits = limits(&elem.base, styles);
+        let base = self.single(&elem.base, styles)?;
+
+        let script = script_style(styles);
+        let styles = styles.chain(&script);
+        let tl = elem.tl.get_cloned(styles);
+        let tr = elem.tr.get_cloned(styles);
+        let bl = elem.bl.get_cloned(styles);
+        let br = elem.br.get_cloned(styles);
+        let t = elem.t.get_cloned(styles);
+        let b = elem.b.get_cloned(styles);
+
+        // Without limits, top and bottom attachments become scripts, just like
+        // in laid-out equations.
+        let primed = tr.as_ref().is_some_and(|content| content.is::<PrimesElem>());
+        let (t, tr) = match (t, tr) {
+            (Some(t), Some(tr)) if primed && !limits => (None, Some(tr + t)),
+            (Some(t), None) if !limits => (None, Some(t)),
+            (t, tr) => (t, tr),
+        };
+        let (b, br) = if limits || br.is_some() { (b, br) } else { (None, b) };
+
+        let mut convert = |content: Option<Content>| {
+            content.map(|content| self.single(&content, styles)).transpose()
+        };
+        let [tl, tr, bl, br, t, b] = [tl, tr, bl, br, t, b].map(&mut convert);
+        let (tl, tr, bl, br, t, b) = (tl?, tr?, bl?, br?, t?, b?);
+
+        let mut base = match (b, t) {
+            (None, None) => base,
+            (Some(b), None) => element(tag::munder, [base, b], span),
+            (None, Some(t)) => element(tag::mover, [base, t], span),
+            (Some(b), Some(t)) => element(tag::munderover, [base, b, t], span),
+        };
+
+        if tl.is_some() || bl.is_some() {
+            base = element(
+                tag::mmultiscripts,
+                [
+                    base,
+                    br.unwrap_or_else(empty),
+                    tr.unwrap_or_else(empty),
+                    HtmlElement::new(tag::mprescripts).into(),
+                    bl.unwrap_or_else(empty),
+                    tl.unwrap_or_else(empty),
+                ],
+                span,
+            );
+        } else {
+            base = match (br, tr) {
+                (None, None) => base,
+                (Some(br), None) => element(tag::msub, [base, br], span),
+                (None, Some(tr)) => element(tag::msup, [base, tr], span),
+                (Some(br), Some(tr)) => element(tag::msubsup, [base, br, tr], span),
+            };
+        }
+
+        Ok(base)
+    }
+
+    /// Converts a [`FracElem`].
+    fn convert_frac(
+        &mut self,
+        elem: &Packed<FracElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        let fraction = fraction_style(styles);
+        let inner = styles.chain(&fraction);
+        Ok(match elem.style.get(styles) {
+            FracStyle::Vertical => element(
+                tag::mfrac,
+                [self.single(&elem.num, inner)?, self.single(&elem.denom, inner)?],
+                span,
+            ),
+            style => {
+                // Horizontal fractions restore the parentheses that the parser
+                // stripped, skewed ones don't need them.
+                let horizontal = style == FracStyle::Horizontal;
+                let mut nodes = EcoVec::new();
+                let parts = [
+                    (&elem.num, elem.num_deparenthesized.get(styles)),
+                    (&elem.denom, elem.denom_deparenthesized.get(styles)),
+                ];
+                for (i, (part, deparenthesized)) in parts.into_iter().enumerate() {
+                    if i == 1 {
+                        nodes.push(token(tag::mo, "/".into(), span).into());
+                    }
+                    let part = self.single(part, styles)?;
+                    if horizontal && deparenthesized {
+                        nodes.push(fenced(Some('('), part, Some(')'), span));
+                    } else {
+                        nodes.push(part);
+                    }
+                }
+                mrow(nodes, span)
+            }
+        })
+    }
+
+    /// Converts a [`BinomElem`].
+    fn convert_binom(
+        &mut self,
+        elem: &Packed<BinomElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        let fraction = fraction_style(styles);
+        let inner = styles.chain(&fraction);
+        let lower = Content::sequence(
+            elem.lower
+                .iter()
+                .flat_map(|c| [SymbolElem::packed(',').spanned(span), c.clone()])
+                .skip(1),
+        );
+
+        let frac = HtmlElement::new(tag::mfrac)
+            .with_attr(attr::linethickness, "0")
+            .with_children(eco_vec![
+                self.single(&elem.upper, inner)?,
+                self.single(&lower, inner)?,
+            ])
+            .spanned(span);
+        Ok(fenced(Some('('), frac.into(), Some(')'), span))
+    }
+
+    /// Converts a [`RootElem`].
+    fn convert_root(
+        &mut self,
+        elem: &Packed<RootElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        Ok(match elem.index.get_ref(styles) {
+            None => HtmlElement::new(tag::msqrt)
+                .with_children(self.row(&elem.radicand, styles)?)
+                .spanned(span)
+                .into(),
+            Some(index) => {
+                let radicand = self.single(&elem.radicand, styles)?;
+                let script = script_style(styles);
+                let index = self.single(index, styles.chain(&script))?;
+                element(tag::mroot, [radicand, index], span)
+            }
+        })
+    }
+
+    /// Converts an [`AccentElem`].
+    fn convert_accent(
+        &mut self,
+        elem: &Packed<AccentElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        let base = self.single(&elem.base, styles)?;
+        let accent = token(tag::mo, spacing_accent(elem.accent.0).into(), span).into();
+        let (kind, flag) = if elem.accent.is_bottom() {
+            (tag::munder, attr::accentunder)
+        } else {
+            (tag::mover, attr::accent)
+        };
+        Ok(HtmlElement::new(kind)
+            .with_attr(flag, "true")
+            .with_children(eco_vec![base, accent])
+            .spanned(span)
+            .into())
+    }
+
+    /// Converts an underline, an overline, or a horizontal brace with an
+    /// optional annotation.
+    fn convert_under_over(
+        &mut self,
+        body: &Content,
+        annotation: Option<&Content>,
+        c: char,
+        over: bool,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = body.span();
+        let kind = if over { tag::mover } else { tag::munder };
+        let body = self.single(body, styles)?;
+        let line = token(tag::mo, c.into(), span).with_attr(attr::stretchy, "true");
+        let mut node = element(kind, [body, line.into()], span);
+        if let Some(annotation) = annotation {
+            let script = script_style(styles);
+            let annotation = self.single(annotation, styles.chain(&script))?;
+            node = element(kind, [node, annotation], span);
+        }
+        Ok(node)
+    }
+
+    /// Converts a [`CancelElem`].
+    ///
+    /// MathML Core has no element for cancellation, so the lines are drawn
+    /// with CSS gradients across the cancelled content.
+    fn convert_cancel(
+        &mut self,
+        elem: &Packed<CancelElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let stroke = elem.stroke.get_cloned(styles);
+        let half = match stroke.thickness {
+            Smart::Custom(thickness) => css::length(thickness / 2.0).to_string(),
+            Smart::Auto => "0.5pt".into(),
+        };
+        let paint = match &stroke.paint {
+            Smart::Custom(Paint::Solid(color)) => css::color(*color).to_string(),
+            _ => "currentcolor".into(),
+        };
+
+        // A gradient towards a corner has its midline along the diagonal
+        // between the two other corners.
+        let line = |corner: &str| {
+            eco_format!(
+                "linear-gradient(to {corner}, transparent calc(50% - {half}), \
+                 {paint} calc(50% - {half}), {paint} calc(50% + {half}), \
+                 transparent calc(50% + {half}))"
+            )
+        };
+        let background = if elem.cross.get(styles) {
+            eco_format!("{}, {}", line("top left"), line("top right"))
+        } else if elem.inverted.get(styles) {
+            line("top right")
+        } else {
+            line("top left")
+        };
+
+        let body = self.row(&elem.body, styles)?;
+        Ok(HtmlElement::new(tag::mrow)
+            .with_styles(css::Properties::new().with("background", background))
+            .with_children(body)
+            .spanned(elem.span())
+            .into())
+    }
+
+    /// Converts a [`VecElem`].
+    fn convert_vec(
+        &mut self,
+        elem: &Packed<VecElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        let align = text_align(elem.align.get(styles));
+        let rows = elem
+            .children
+            .iter()
+            .map(|child| Ok(mtr([mtd(self.row(child, styles)?, align)])))
+            .collect::<SourceResult<Vec<_>>>()?;
+        let delim = elem.delim.get(styles);
+        Ok(fenced(delim.open(), table(rows, span), delim.close(), span))
+    }
+
+    /// Converts a [`MatElem`].
+    fn convert_mat(
+        &mut self,
+        elem: &Packed<MatElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        if elem.augment.get_cloned(styles).is_some() {
+            self.engine.sink.warn(warning!(
+                span,
+                "matrix augmentation was ignored during HTML export"
+            ));
+        }
+
+        let align = text_align(elem.align.get(styles));
+        let rows = elem
+            .rows
+            .iter()
+            .map(|row| {
+                let cells = row
+                    .iter()
+                    .map(|cell| Ok(mtd(self.row(cell, styles)?, align)))
+                    .collect::<SourceResult<Vec<_>>>()?;
+                Ok(mtr(cells))
+            })
+            .collect::<SourceResult<Vec<_>>>()?;
+        let delim = elem.delim.get(styles);
+        Ok(fenced(delim.open(), table(rows, span), delim.close(), span))
+    }
+
+    /// Converts a [`CasesElem`].
+    fn convert_cases(
+        &mut self,
+        elem: &Packed<CasesElem>,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let span = elem.span();
+        let mut rows = vec![];
+        for child in &elem.children {
+            // Alignment points in a branch separate its columns.
+            for row in self.cells(child, styles)? {
+                rows.push(mtr(row.into_iter().map(|cell| mtd(cell, Some("left")))));
+            }
+        }
+
+        let delim = elem.delim.get(styles);
+        let table = table(rows, span);
+        Ok(if elem.reverse.get(styles) {
+            fenced(None, table, delim.close(), span)
+        } else {
+            fenced(delim.open(), table, None, span)
+        })
+    }
+
+    /// Converts content that has no MathML equivalent, like a box.
+    ///
+    /// MathML text elements may contain arbitrary inline HTML, so the content
+    /// is converted as usual and embedded into an `<mtext>`.
+    fn convert_external(
+        &mut self,
+        elem: &Content,
+        styles: StyleChain,
+    ) -> SourceResult<HtmlNode> {
+        let children = html_inline_fragment(
+            self.engine,
+            elem,
+            self.locator,
+            &mut SmartQuoter::new(),
+            styles,
+            Whitespace::Normal,
+        )?;
+        Ok(HtmlElement::new(tag::mtext)
+            .with_children(children)
+            .spanned(elem.span())
+            .into())
+    }
+}
+
+/// Converts a [`TextElem`] in math.
+///
+/// Numbers become `<mn>` elements and all other text becomes `<mtext>`.
+fn convert_text(elem: &Packed<TextElem>, styles: StyleChain) -> HtmlNode {
+    let text = &elem.text;
+    let number = !text.is_empty() && text.chars().all(|c| c.is_ascii_digit() || c == '.');
+
+    // Text is never automatically italicized.
+    let italic = styles.get(EquationElem::italic).or(Some(false));
+    let styled = style_text(text, styles, italic);
+    let kind = if number { tag::mn } else { tag::mtext };
+
+    // MathML trims whitespace at the start and end of token elements, which
+    // would swallow spaces in text like `"for all "`.
+    let styled = if number { styled } else { styled.replace(' ', "\u{a0}").into() };
+    token(kind, styled, elem.span()).into()
+}
+
+/// Converts a symbol in math.
+///
+/// Depending on its class, a symbol becomes an identifier (`<mi>`), a number
+/// (`<mn>`), or an operator (`<mo>`).
+fn convert_symbol(text: &EcoString, span: Span, styles: StyleChain) -> HtmlNode {
+    let class = styles
+        .get(EquationElem::class)
+        .or_else(|| text.chars().next().and_then(default_math_class));
+
+    let italic = styles.get(EquationElem::italic);
+    let styled = style_text(text, styles, italic);
+
+    match class {
+        Some(MathClass::Normal | MathClass::Alphabetic) | None => {
+            if text.chars().all(|c| c.is_ascii_digit()) {
+                return token(tag::mn, styled, span).into();
+            }
+
+            // Single-character identifiers are italicized by the MathML
+            // renderer. If the Typst styling already italicizes the character
+            // in the same way, the plain character is kept as it is easier to
+            // search for and copy. If the styling leaves the character as is,
+            // it must stay upright.
+            let mut chars = text.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                let auto = styles.get(EquationElem::variant).is_none()
+                    && !styles.get(EquationElem::bold)
+                    && italic.is_none();
+                if styled == *text {
+                    let mut mi = token(tag::mi, text.clone(), span);
+                    if c.is_alphabetic() {
+                        mi.attrs.push(attr::mathvariant, "normal");
+                    }
+                    return mi.into();
+                } else if auto {
+                    return token(tag::mi, text.clone(), span).into();
+                }
+            }
+
+            token(tag::mi, styled, span).into()
+        }
+        _ => token(tag::mo, styled, span).into(),
+    }
+}
+
+/// Applies the math font style (variant, boldness, and italics) to text.
+fn style_text(text: &str, styles: StyleChain, italic: Option<bool>) -> EcoString {
+    let variant = styles.get(EquationElem::variant);
+    let bold = styles.get(EquationElem::bold);
+    text.chars()
+        .flat_map(|c| to_style(c, MathStyle::select(c, variant, bold, italic)))
+        .collect()
+}
+
+/// Whether the attachments of the given base should be shown as limits.
+fn limits(base: &Content, styles: StyleChain) -> bool {
+    let mut base = base;
+    while let Some(equation) = base.to_packed::<EquationElem>() {
+        base = &equation.body;
+    }
+
+    let display = styles.get(EquationElem::size) == MathSize::Display;
+    if let Some(elem) = base.to_packed::<LimitsElem>() {
+        elem.inline.get(styles) || display
+    } else if base.is::<ScriptsElem>() {
+        false
+    } else if let Some(elem) = base.to_packed::<OpElem>() {
+        elem.limits.get(styles) && display
+    } else if let Some(elem) = base.to_packed::<ClassElem>() {
+        match elem.class {
+            MathClass::Large => display,
+            MathClass::Relation => true,
+            _ => false,
+        }
+    } else if let Some(elem) = base.to_packed::<SymbolElem>() {
+        let mut chars = elem.text.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else { return false };
+        match default_math_class(c) {
+            Some(MathClass::Large) => !is_integral_char(c) && display,
+            Some(MathClass::Relation) => true,
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+/// Whether the character is an integral, whose attachments are never shown as
+/// limits by default.
+fn is_integral_char(c: char) -> bool {
+    ('∫'..='∳').contains(&c) || ('⨋'..='⨜').contains(&c)
+}
+
+/// The style for attachments and root indices.
+fn script_style(styles: StyleChain) -> LazyHash<Style> {
+    EquationElem::size
+        .set(match styles.get(EquationElem::size) {
+            MathSize::Display | MathSize::Text => MathSize::Script,
+            MathSize::Script | MathSize::ScriptScript => MathSize::ScriptScript,
+        })
+        .wrap()
+}
+
+/// The style for the numerator and denominator of fractions.
+fn fraction_style(styles: StyleChain) -> LazyHash<Style> {
+    EquationElem::size
+        .set(match styles.get(EquationElem::size) {
+            MathSize::Display => MathSize::Text,
+            MathSize::Text => MathSize::Script,
+            MathSize::Script | MathSize::ScriptScript => MathSize::ScriptScript,
+        })
+        .wrap()
+}
+
+/// Turns a combining accent into its spacing form, which MathML renderers
+/// stretch and position more reliably.
+fn spacing_accent(c: char) -> char {
+    match c {
+        '\u{0300}' => '`',
+        '\u{0301}' => '´',
+        '\u{0302}' => '^',
+        '\u{0303}' => '~',
+        '\u{0304}' => '¯',
+        '\u{0305}' => '‾',
+        '\u{0306}' => '˘',
+        '\u{0307}' => '˙',
+        '\u{0308}' => '¨',
+        '\u{030a}' => '˚',
+        '\u{030b}' => '˝',
+        '\u{030c}' => 'ˇ',
+        '\u{20d6}' => '←',
+        '\u{20d7}' => '→',
+        '\u{20e1}' => '↔',
+        c => c,
+    }
+}
+
+/// The CSS `text-align` value for a horizontal alignment, if it differs from
+/// the default centering of table cells.
+fn text_align(align: HAlignment) -> Option<&'static str> {
+    match align {
+        HAlignment::Start => Some("start"),
+        HAlignment::Left => Some("left"),
+        HAlignment::Center => None,
+        HAlignment::Right => Some("right"),
+        HAlignment::End => Some("end"),
+    }
+}
+
+/// Creates a token element (like `<mi>` or `<mo>`) with text.
+fn token(tag: HtmlTag, text: EcoString, span: Span) -> HtmlElement {
+    HtmlElement::new(tag)
+        .with_children(eco_vec![HtmlNode::text(text, span)])
+        .spanned(span)
+}
+
+/// Creates an element with the given children.
+fn element<const N: usize>(
+    tag: HtmlTag,
+    children: [HtmlNode; N],
+    span: Span,
+) -> HtmlNode {
+    HtmlElement::new(tag)
+        .with_children(children.into_iter().collect())
+        .spanned(span)
+        .into()
+}
+
+/// Groups nodes into an `<mrow>`.
+fn mrow(children: EcoVec<HtmlNode>, span: Span) -> HtmlNode {
+    HtmlElement::new(tag::mrow)
+        .with_children(children)
+        .spanned(span)
+        .into()
+}
+
+/// Groups nodes into a single node, creating an `<mrow>` only if necessary.
+fn group(nodes: EcoVec<HtmlNode>, span: Span) -> HtmlNode {
+    match nodes.as_slice() {
+        [node @ HtmlNode::Element(_)] => node.clone(),
+        _ => mrow(nodes, span),
+    }
+}
+
+/// An empty placeholder for a missing script.
+fn empty() -> HtmlNode {
+    HtmlElement::new(tag::mrow).into()
+}
+
+/// Surrounds a node with fences, if any.
+fn fenced(
+    open: Option<char>,
+    node: HtmlNode,
+    close: Option<char>,
+    span: Span,
+) -> HtmlNode {
+    if open.is_none() && close.is_none() {
+        return node;
+    }
+
+    let mut children = EcoVec::new();
+    children.extend(open.map(|c| token(tag::mo, c.into(), span).into()));
+    children.push(node);
+    children.extend(close.map(|c| token(tag::mo, c.into(), span).into()));
+    mrow(children, span)
+}
+
+/// Creates an `<mtable>` from rows.
+fn table(rows: impl IntoIterator<Item = HtmlElement>, span: Span) -> HtmlNode {
+    HtmlElement::new(tag::mtable)
+        .with_children(rows.into_iter().map(HtmlNode::Element).collect())
+        .spanned(span)
+        .into()
+}
+
+/// Creates an `<mtr>` from cells.
+fn mtr(cells: impl IntoIterator<Item = HtmlElement>) -> HtmlElement {
+    HtmlElement::new(tag::mtr)
+        .with_children(cells.into_iter().map(HtmlNode::Element).collect())
+}
+
+/// Creates an `<mtd>` with an optional alignment.
+fn mtd(children: EcoVec<HtmlNode>, align: Option<&str>) -> HtmlElement {
+    let mut styles = css::Properties::new();
+    if let Some(align) = align {
+        styles.push("text-align", align);
+    }
+    HtmlElement::new(tag::mtd).with_styles(styles).with_children(children)
+}
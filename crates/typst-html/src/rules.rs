@@ -1,33 +1,42 @@
 use std::num::NonZeroUsize;
 
 use comemo::Track;
-use ecow::{EcoVec, eco_format};
+use ecow::{EcoString, EcoVec, eco_format};
 use typst_library::diag::{At, SourceResult, bail, error, warning};
 use typst_library::engine::Engine;
 use typst_library::foundations::{
-    Content, Context, NativeElement, NativeRuleMap, ShowFn, Smart, StyleChain, Target,
+    Content, Context, NativeElement, NativeRuleMap, Packed, ShowFn, Smart, StyleChain,
+    Target,
 };
 use typst_library::introspection::Counter;
 use typst_library::layout::resolve::{Cell, CellGrid, Entry};
 use typst_library::layout::{
-    BlockBody, BlockElem, BoxElem, HElem, OuterVAlignment, Sizing,
+    AlignElem, Axes, BlockBody, BlockElem, BoxElem, ColumnSizing, ColumnsElem, Dir,
+    GridCell, GridElem, HAlignment, HElem, Length, MinMax, OuterVAlignment, PadElem,
+    PlaceElem, Rel, Sizing, Spacing, StackChild, StackElem,
 };
+use typst_library::math::EquationElem;
 use typst_library::model::{
     Attribution, BibliographyElem, CiteElem, CiteGroup, CslIndentElem, CslLightElem,
     Destination, DirectLinkElem, EmphElem, EnumElem, FigureCaption, FigureElem,
     FootnoteElem, FootnoteEntry, HeadingElem, LinkElem, LinkTarget, ListElem,
     OutlineElem, OutlineEntry, OutlineNode, ParElem, ParbreakElem, QuoteElem, RefElem,
-    StrongElem, TableCell, TableElem, TermsElem, TitleElem, Works,
+    StrongElem, TableCell, TableElem, TableNote, TermsElem, TitleElem, Works,
 };
 use typst_library::text::{
     HighlightElem, LinebreakElem, OverlineElem, RawElem, RawLine, SmallcapsElem,
     SpaceElem, StrikeElem, SubElem, SuperElem, UnderlineElem,
 };
-use typst_library::visualize::{Color, ImageElem};
+use typst_library::visualize::{
+    CircleElem, Color, CurveComponent, CurveElem, EllipseElem, ImageElem, LineElem,
+    PathElem, PathVertex, PolygonElem, RectElem, SquareElem,
+};
 use typst_macros::elem;
 use typst_utils::singleton;
 
-use crate::{FrameElem, HtmlAttr, HtmlAttrs, HtmlElem, HtmlTag, attr, css, tag};
+use crate::{
+    FrameElem, HtmlAttr, HtmlAttrs, HtmlElem, HtmlTag, SplitElem, attr, css, tag,
+};
 
 /// Registers show rules for the [HTML target](Target::Html).
 pub fn register(rules: &mut NativeRuleMap) {
@@ -58,6 +67,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Html, CSL_LIGHT_RULE);
     rules.register(Html, CSL_INDENT_RULE);
     rules.register(Html, TABLE_RULE);
+    rules.register(Html, TABLE_NOTE_RULE);
 
     // Text.
     rules.register(Html, SUB_RULE);
@@ -70,17 +80,39 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Html, RAW_RULE);
     rules.register(Html, RAW_LINE_RULE);
 
+    // Math.
+    rules.register(Html, EQUATION_RULE);
+
     // Layout.
     rules.register(Html, BLOCK_RULE);
     rules.register(Html, BOX_RULE);
+    rules.register(Html, ALIGN_RULE);
+    rules.register(Html, PAD_RULE);
+    rules.register(Html, COLUMNS_RULE);
+    rules.register(Html, STACK_RULE);
+    rules.register(Html, GRID_RULE);
+    rules.register(Html, PLACE_RULE);
 
     // Visualize.
     rules.register(Html, IMAGE_RULE);
+    rules.register::<RectElem>(Html, show_shape);
+    rules.register::<SquareElem>(Html, show_shape);
+    rules.register::<EllipseElem>(Html, show_shape);
+    rules.register::<CircleElem>(Html, show_shape);
+    rules.register::<LineElem>(Html, show_shape);
+    rules.register::<PolygonElem>(Html, show_shape);
+    rules.register::<CurveElem>(Html, show_shape);
+    rules.register::<PathElem>(Html, show_shape);
 
     // For the HTML target, `html.frame` is a primitive. In the laid-out target,
     // it should be a no-op so that nested frames don't break (things like `show
     // math.equation: html.frame` can result in nested ones).
     rules.register::<FrameElem>(Paged, |elem, _, _| Ok(elem.body.clone()));
+
+    // Split markers are only relevant for multi-file HTML export, which picks
+    // them up from their tags.
+    rules.register::<SplitElem>(Html, |_, _, _| Ok(Content::empty()));
+    rules.register::<SplitElem>(Paged, |_, _, _| Ok(Content::empty()));
 }
 
 const PAR_RULE: ShowFn<ParElem> =
@@ -529,20 +561,88 @@ const CSL_INDENT_RULE: ShowFn<CslIndentElem> = |elem, _, _| {
         .pack())
 };
 
-const TABLE_RULE: ShowFn<TableElem> = |elem, _, styles| {
+const TABLE_RULE: ShowFn<TableElem> = |elem, engine, styles| {
     let grid = elem.grid.as_ref().unwrap();
-    Ok(show_cellgrid(grid, styles))
+    let splits =
+        grid.split_on_align_chars(elem.align_char.get_ref(styles), engine, styles)?;
+    let pads = char_pads(&splits, grid.non_gutter_column_count());
+    let mut realized = show_cellgrid(grid, &pads, styles);
+
+    // List the table's notes below it.
+    let notes = elem.realize_notes(engine, styles)?;
+    if !notes.is_empty() {
+        let entries = notes.into_iter().map(|(num, body)| {
+            HtmlElem::new(tag::p)
+                .with_body(Some(SuperElem::new(num).pack() + body))
+                .pack()
+        });
+        realized += HtmlElem::new(tag::div)
+            .with_body(Some(Content::sequence(entries)))
+            .pack();
+    }
+
+    // Let the notes within the table know which table they belong to.
+    if let Some(loc) = elem.location() {
+        realized = realized.set(TableNote::table, Some(loc));
+    }
+
+    Ok(realized)
 };
 
-fn show_cellgrid(grid: &CellGrid, styles: StyleChain) -> Content {
-    let elem = |tag, body| HtmlElem::new(tag).with_body(Some(body)).pack();
-    let mut rows: Vec<_> = grid.entries.chunks(grid.non_gutter_column_count()).collect();
+const TABLE_NOTE_RULE: ShowFn<TableNote> = |elem, engine, styles| {
+    let num = elem.realize(engine, styles)?;
+    Ok(HElem::hole().clone() + SuperElem::new(num).pack().spanned(elem.span()))
+};
 
-    let tr = |tag, row: &[Entry]| {
-        let row = row
-            .iter()
-            .flat_map(|entry| entry.as_cell())
-            .map(|cell| show_cell(tag, cell, styles));
+/// Determines by how many characters to pad the text of each cell before and
+/// after, such that the points the cells' text was split at line up across
+/// each column.
+fn char_pads(
+    splits: &[Option<(EcoString, EcoString)>],
+    cols: usize,
+) -> Vec<Option<(usize, usize)>> {
+    let counts: Vec<_> = splits
+        .iter()
+        .map(|split| {
+            split
+                .as_ref()
+                .map(|(before, after)| (before.chars().count(), after.chars().count()))
+        })
+        .collect();
+
+    let mut max = vec![(0, 0); cols];
+    for (i, count) in counts.iter().enumerate() {
+        if let Some((before, after)) = *count {
+            let (max_before, max_after) = &mut max[i % cols];
+            *max_before = before.max(*max_before);
+            *max_after = after.max(*max_after);
+        }
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let (max_before, max_after) = max[i % cols];
+            count.map(|(before, after)| (max_before - before, max_after - after))
+        })
+        .collect()
+}
+
+fn show_cellgrid(
+    grid: &CellGrid,
+    pads: &[Option<(usize, usize)>],
+    styles: StyleChain,
+) -> Content {
+    let elem = |tag, body| HtmlElem::new(tag).with_body(Some(body)).pack();
+    let cols = grid.non_gutter_column_count();
+    let mut rows: Vec<_> = grid.entries.chunks(cols).enumerate().collect();
+
+    let tr = |tag, (y, row): (usize, &[Entry])| {
+        let row = row.iter().enumerate().filter_map(|(x, entry)| {
+            let pad = pads.get(y * cols + x).copied().flatten();
+            entry.as_cell().map(|cell| show_cell(tag, cell, pad, styles))
+        });
         elem(tag::tr, Content::sequence(row))
     };
 
@@ -608,7 +708,12 @@ fn show_cellgrid(grid: &CellGrid, styles: StyleChain) -> Content {
     elem(tag::table, Content::sequence(content))
 }
 
-fn show_cell(tag: HtmlTag, cell: &Cell, styles: StyleChain) -> Content {
+fn show_cell(
+    tag: HtmlTag,
+    cell: &Cell,
+    pad: Option<(usize, usize)>,
+    styles: StyleChain,
+) -> Content {
     let cell = cell.body.clone();
     let Some(cell) = cell.to_packed::<TableCell>() else { return cell };
     let mut attrs = HtmlAttrs::new();
@@ -619,13 +724,32 @@ fn show_cell(tag: HtmlTag, cell: &Cell, styles: StyleChain) -> Content {
     if let Some(rowspan) = span(cell.rowspan.get(styles)) {
         attrs.push(attr::rowspan, rowspan);
     }
+    let mut body = cell.body.clone();
+    if let Some((before, after)) = pad {
+        body = char_pad(before) + body + char_pad(after);
+    }
     HtmlElem::new(tag)
-        .with_body(Some(cell.body.clone()))
+        .with_body(Some(body))
         .with_attrs(attrs)
         .pack()
         .spanned(cell.span())
 }
 
+/// Creates an empty inline box which is as wide as the given number of
+/// digits.
+fn char_pad(n: usize) -> Content {
+    if n == 0 {
+        return Content::empty();
+    }
+    HtmlElem::new(tag::span)
+        .with_styles(
+            css::Properties::new()
+                .with("display", "inline-block")
+                .with("width", eco_format!("{n}ch")),
+        )
+        .pack()
+}
+
 const SUB_RULE: ShowFn<SubElem> =
     |elem, _, _| Ok(HtmlElem::new(tag::sub).with_body(Some(elem.body.clone())).pack());
 
@@ -711,6 +835,36 @@ pub fn html_span_filled(content: Content, color: Color) -> Content {
 
 const RAW_LINE_RULE: ShowFn<RawLine> = |elem, _, _| Ok(elem.body.clone());
 
+const EQUATION_RULE: ShowFn<EquationElem> = |elem, engine, styles| {
+    let span = elem.span();
+    let block = elem.block.get(styles);
+
+    // The body is converted to MathML when the `<math>` element is.
+    let math = HtmlElem::new(tag::math)
+        .with_optional_attr(attr::display, block.then_some("block"))
+        .with_optional_attr(attr::alttext, elem.alt.get_cloned(styles))
+        .with_body(Some(elem.body.clone()))
+        .pack()
+        .spanned(span);
+    if !block {
+        return Ok(math);
+    }
+
+    let mut realized = math;
+    if let Some(numbering) = elem.numbering.get_ref(styles).as_ref() {
+        let location = elem.location().unwrap();
+        let number = Counter::of(EquationElem::ELEM)
+            .display_at_loc(engine, location, styles, numbering)?
+            .spanned(span);
+        realized += HtmlElem::new(tag::span)
+            .with_attr(attr::class, "equation-number")
+            .with_body(Some(number))
+            .pack();
+    }
+
+    Ok(HtmlElem::new(tag::div).with_body(Some(realized)).pack())
+};
+
 // TODO: This is rather incomplete.
 const BLOCK_RULE: ShowFn<BlockElem> = |elem, _, styles| {
     let body = match elem.body.get_cloned(styles) {
@@ -737,6 +891,244 @@ const BOX_RULE: ShowFn<BoxElem> = |elem, _, styles| {
         .pack())
 };
 
+const ALIGN_RULE: ShowFn<AlignElem> = |elem, _, styles| {
+    // Vertical alignment has no effect without a fixed height.
+    let Some(x) = elem.alignment.get(styles).x() else {
+        return Ok(elem.body.clone());
+    };
+
+    Ok(HtmlElem::new(tag::div)
+        .with_styles(css::Properties::new().with("text-align", text_align(x)))
+        .with_body(Some(elem.body.clone()))
+        .pack())
+};
+
+const PAD_RULE: ShowFn<PadElem> = |elem, _, styles| {
+    let mut inline = css::Properties::new();
+    for (property, amount) in [
+        ("padding-top", elem.top.get(styles)),
+        ("padding-right", elem.right.get(styles)),
+        ("padding-bottom", elem.bottom.get(styles)),
+        ("padding-left", elem.left.get(styles)),
+    ] {
+        if !amount.is_zero() {
+            inline.push(property, css::rel(amount));
+        }
+    }
+
+    Ok(HtmlElem::new(tag::div)
+        .with_styles(inline)
+        .with_body(Some(elem.body.clone()))
+        .pack())
+};
+
+const COLUMNS_RULE: ShowFn<ColumnsElem> = |elem, _, styles| {
+    Ok(HtmlElem::new(tag::div)
+        .with_styles(
+            css::Properties::new()
+                .with("column-count", elem.count.get(styles))
+                .with("column-gap", css::rel(elem.gutter.get(styles))),
+        )
+        .with_body(Some(elem.body.clone()))
+        .pack())
+};
+
+const STACK_RULE: ShowFn<StackElem> = |elem, _, styles| {
+    let direction = match elem.dir.get(styles) {
+        Dir::LTR => "row",
+        Dir::RTL => "row-reverse",
+        Dir::TTB => "column",
+        Dir::BTT => "column-reverse",
+    };
+
+    // Like in layout, the default spacing is only inserted between two items
+    // without explicit spacing.
+    let spacing = elem.spacing.get(styles);
+    let mut children = vec![];
+    let mut after_item = false;
+    for child in &elem.children {
+        match child {
+            StackChild::Block(body) => {
+                if after_item && let Some(spacing) = spacing {
+                    children.push(stack_spacing(spacing));
+                }
+                children
+                    .push(HtmlElem::new(tag::div).with_body(Some(body.clone())).pack());
+                after_item = true;
+            }
+            StackChild::Spacing(spacing) => {
+                children.push(stack_spacing(*spacing));
+                after_item = false;
+            }
+        }
+    }
+
+    Ok(HtmlElem::new(tag::div)
+        .with_styles(
+            css::Properties::new()
+                .with("display", "flex")
+                .with("flex-direction", direction),
+        )
+        .with_body(Some(Content::sequence(children)))
+        .pack())
+};
+
+/// Creates an empty flex item that takes up the given spacing in a stack.
+fn stack_spacing(spacing: Spacing) -> Content {
+    let inline = match spacing {
+        Spacing::Rel(rel) => {
+            css::Properties::new().with("flex", eco_format!("0 0 {}", css::rel(rel)))
+        }
+        Spacing::Fr(fr) => css::Properties::new().with("flex-grow", fr.get()),
+    };
+    HtmlElem::new(tag::div).with_styles(inline).pack()
+}
+
+const GRID_RULE: ShowFn<GridElem> = |elem, _, styles| {
+    let grid = elem.grid.as_ref().unwrap();
+    let mut inline = css::Properties::new().with("display", "grid");
+
+    let columns = elem.columns.get_ref(styles);
+    if !columns.0.is_empty() {
+        inline.push("grid-template-columns", tracks(columns.0.iter().map(column_track)));
+    }
+
+    // Like in layout, the last row size is repeated for any further rows.
+    let rows = elem.rows.get_ref(styles);
+    if let Some(last) = rows.0.last() {
+        inline.push("grid-template-rows", tracks(rows.0.iter().map(track)));
+        if *last != Sizing::Auto {
+            inline.push("grid-auto-rows", track(last));
+        }
+    }
+
+    // CSS only supports uniform gaps, so only the first gutter is used.
+    if let Some(Sizing::Rel(gap)) = elem.row_gutter.get_ref(styles).0.first()
+        && !gap.is_zero()
+    {
+        inline.push("row-gap", css::rel(*gap));
+    }
+    if let Some(Sizing::Rel(gap)) = elem.column_gutter.get_ref(styles).0.first()
+        && !gap.is_zero()
+    {
+        inline.push("column-gap", css::rel(*gap));
+    }
+
+    let cells = grid
+        .entries
+        .iter()
+        .filter_map(|entry| entry.as_cell())
+        .map(|cell| show_grid_cell(cell, styles));
+
+    Ok(HtmlElem::new(tag::div)
+        .with_styles(inline)
+        .with_body(Some(Content::sequence(cells)))
+        .pack())
+};
+
+/// Shows a cell of a grid as an item of a CSS grid.
+fn show_grid_cell(cell: &Cell, styles: StyleChain) -> Content {
+    let Some(cell) = cell.body.to_packed::<GridCell>() else {
+        return HtmlElem::new(tag::div).with_body(Some(cell.body.clone())).pack();
+    };
+
+    let mut inline = css::Properties::new();
+    let colspan = cell.colspan.get(styles);
+    if colspan != NonZeroUsize::MIN {
+        inline.push("grid-column", eco_format!("span {colspan}"));
+    }
+    let rowspan = cell.rowspan.get(styles);
+    if rowspan != NonZeroUsize::MIN {
+        inline.push("grid-row", eco_format!("span {rowspan}"));
+    }
+    if let Smart::Custom(align) = cell.align.get(styles)
+        && let Some(x) = align.x()
+    {
+        inline.push("text-align", text_align(x));
+    }
+
+    HtmlElem::new(tag::div)
+        .with_styles(inline)
+        .with_body(Some(cell.body.clone()))
+        .pack()
+        .spanned(cell.span())
+}
+
+/// Converts track sizes into a CSS track list.
+fn tracks(sizings: impl IntoIterator<Item = EcoString>) -> EcoString {
+    let mut list = EcoString::new();
+    for (i, sizing) in sizings.into_iter().enumerate() {
+        if i > 0 {
+            list.push(' ');
+        }
+        list.push_str(&sizing);
+    }
+    list
+}
+
+/// Converts a track size into a CSS track size.
+fn track(sizing: &Sizing) -> EcoString {
+    match *sizing {
+        Sizing::Auto => "auto".into(),
+        Sizing::Rel(rel) => eco_format!("{}", css::rel(rel)),
+        Sizing::Fr(fr) => eco_format!("{}fr", fr.get()),
+    }
+}
+
+/// Converts a column size into a CSS track size.
+fn column_track(sizing: &ColumnSizing) -> EcoString {
+    match *sizing {
+        ColumnSizing::Track(sizing) => track(&sizing),
+        ColumnSizing::MinMax(MinMax { min, max }) => {
+            eco_format!("minmax({}, {})", css::rel(min), css::rel(max))
+        }
+    }
+}
+
+const PLACE_RULE: ShowFn<PlaceElem> = |elem, _, styles| {
+    let mut inline = css::Properties::new();
+
+    // There are no pages or fixed-size containers to place content in.
+    // Instead, content placed at a side floats there, so that other content
+    // flows around it rather than being overlapped.
+    if let Smart::Custom(align) = elem.alignment.get(styles)
+        && let Some(x) = align.x()
+    {
+        match x {
+            HAlignment::Start => inline.push("float", "inline-start"),
+            HAlignment::Left => inline.push("float", "left"),
+            HAlignment::Center => inline.push("text-align", "center"),
+            HAlignment::Right => inline.push("float", "right"),
+            HAlignment::End => inline.push("float", "inline-end"),
+        }
+    }
+
+    let dx = elem.dx.get(styles);
+    let dy = elem.dy.get(styles);
+    if !dx.is_zero() || !dy.is_zero() {
+        inline.push("position", "relative");
+        inline.push("left", css::rel(dx));
+        inline.push("top", css::rel(dy));
+    }
+
+    Ok(HtmlElem::new(tag::div)
+        .with_styles(inline)
+        .with_body(Some(elem.body.clone()))
+        .pack())
+};
+
+/// Converts a horizontal alignment into a value of the CSS `text-align`
+/// property.
+fn text_align(align: HAlignment) -> &'static str {
+    match align {
+        HAlignment::Start => "start",
+        HAlignment::Left => "left",
+        HAlignment::Center => "center",
+        HAlignment::Right => "right",
+        HAlignment::End => "end",
+    }
+}
+
 const IMAGE_RULE: ShowFn<ImageElem> = |elem, engine, styles| {
     let image = elem.decode(engine, styles)?;
 
@@ -769,3 +1161,127 @@ const IMAGE_RULE: ShowFn<ImageElem> = |elem, engine, styles| {
 
     Ok(HtmlElem::new(tag::img).with_attrs(attrs).with_styles(inline).pack())
 };
+
+/// Shows a shape as an inline SVG, laid out with the same routines as in paged
+/// export and sized to fit its contents.
+///
+/// Shapes are inline-level in layout, so the frame is wrapped in a `<span>`
+/// to keep it within the surrounding paragraph.
+fn show_shape<T: HtmlShape>(
+    elem: &Packed<T>,
+    engine: &mut Engine,
+    styles: StyleChain,
+) -> SourceResult<Content> {
+    let span = elem.span();
+
+    // The frame is laid out in an infinite region, so there is nothing to
+    // resolve relative sizes against.
+    if elem.is_relative(styles) {
+        engine.sink.warn(warning!(
+            span,
+            "{} was ignored during HTML export",
+            T::ELEM.name();
+            hint: "shapes with relative sizes are not supported in HTML export"
+        ));
+        return Ok(Content::empty());
+    }
+
+    let frame = FrameElem::new(elem.clone().pack()).pack().spanned(span);
+    Ok(HtmlElem::new(tag::span).with_body(Some(frame)).pack().spanned(span))
+}
+
+/// A shape that is exported to HTML as an inline SVG.
+trait HtmlShape: NativeElement {
+    /// Whether the shape's size or points depend on the size of its container.
+    fn is_relative(&self, styles: StyleChain) -> bool;
+}
+
+impl HtmlShape for RectElem {
+    fn is_relative(&self, styles: StyleChain) -> bool {
+        is_relative_size(self.width.get(styles), self.height.get(styles))
+    }
+}
+
+impl HtmlShape for SquareElem {
+    fn is_relative(&self, styles: StyleChain) -> bool {
+        is_relative_size(self.width.get(styles), self.height.get(styles))
+    }
+}
+
+impl HtmlShape for EllipseElem {
+    fn is_relative(&self, styles: StyleChain) -> bool {
+        is_relative_size(self.width.get(styles), self.height.get(styles))
+    }
+}
+
+impl HtmlShape for CircleElem {
+    fn is_relative(&self, styles: StyleChain) -> bool {
+        is_relative_size(self.width.get(styles), self.height.get(styles))
+    }
+}
+
+impl HtmlShape for LineElem {
+    fn is_relative(&self, styles: StyleChain) -> bool {
+        is_relative_point(self.start.get(styles))
+            || match self.end.get(styles) {
+                Some(end) => is_relative_point(end),
+                None => is_relative(self.length.get(styles)),
+            }
+    }
+}
+
+impl HtmlShape for PolygonElem {
+    fn is_relative(&self, _: StyleChain) -> bool {
+        self.vertices.iter().copied().any(is_relative_point)
+    }
+}
+
+impl HtmlShape for CurveElem {
+    fn is_relative(&self, _: StyleChain) -> bool {
+        self.components.iter().any(|component| match component {
+            CurveComponent::Move(elem) => is_relative_point(elem.start),
+            CurveComponent::Line(elem) => is_relative_point(elem.end),
+            CurveComponent::Quad(elem) => {
+                let control = elem.control.custom().flatten();
+                control.is_some_and(is_relative_point) || is_relative_point(elem.end)
+            }
+            CurveComponent::Cubic(elem) => {
+                let control_start = elem.control_start.and_then(Smart::custom);
+                control_start.is_some_and(is_relative_point)
+                    || elem.control_end.is_some_and(is_relative_point)
+                    || is_relative_point(elem.end)
+            }
+            CurveComponent::Close(_) => false,
+        })
+    }
+}
+
+impl HtmlShape for PathElem {
+    fn is_relative(&self, _: StyleChain) -> bool {
+        self.vertices.iter().any(|vertex| match *vertex {
+            PathVertex::Vertex(a) => is_relative_point(a),
+            PathVertex::MirroredControlPoint(a, b) => {
+                is_relative_point(a) || is_relative_point(b)
+            }
+            PathVertex::AllControlPoints(a, b, c) => {
+                is_relative_point(a) || is_relative_point(b) || is_relative_point(c)
+            }
+        })
+    }
+}
+
+/// Whether a shape's width or height is relative to its container.
+fn is_relative_size(width: Smart<Rel<Length>>, height: Sizing) -> bool {
+    width.is_custom_and(is_relative)
+        || matches!(height, Sizing::Rel(rel) if is_relative(rel))
+}
+
+/// Whether a point is relative to the shape's container.
+fn is_relative_point(point: Axes<Rel<Length>>) -> bool {
+    is_relative(point.x) || is_relative(point.y)
+}
+
+/// Whether a length is relative to the shape's container.
+fn is_relative(length: Rel<Length>) -> bool {
+    !length.rel.is_zero()
+}
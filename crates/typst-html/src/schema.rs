@@ -0,0 +1,182 @@
+use ecow::{EcoString, eco_format};
+use serde::Deserialize;
+use typst_assets::html as data;
+use typst_library::diag::{StrResult, bail};
+
+use crate::{HtmlAttr, HtmlTag};
+
+/// Describes custom elements (web components) for the typed HTML API.
+///
+/// Each element described by a schema becomes a function in the `html` module
+/// (e.g. `html.my-widget`), which validates its attributes just like the
+/// functions for the standard HTML elements do.
+///
+/// Schemas are written in JSON:
+///
+/// ```json
+/// {
+///   "elements": [
+///     {
+///       "name": "my-widget",
+///       "description": "An interactive widget.",
+///       "attributes": [
+///         { "name": "size", "type": "positive-int" },
+///         { "name": "variant", "type": ["primary", "secondary"] },
+///         { "name": "disabled", "type": "boolean" }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// An attribute's type is either one of `string`, `boolean` (the attribute's
+/// presence), `true-false`, `yes-no`, `on-off`, `int`, `non-negative-int`,
+/// `positive-int`, `float`, `positive-float`, `char`, `color`, `datetime`,
+/// `duration`, and `tokens` (space-separated strings) or an array of the
+/// allowed strings. Custom elements also accept all global attributes.
+#[derive(Clone)]
+pub struct HtmlSchema {
+    /// The described elements.
+    pub(crate) elements: Vec<CustomElem>,
+}
+
+/// A custom element described by a schema.
+#[derive(Clone)]
+pub(crate) struct CustomElem {
+    /// The element's tag name.
+    pub name: EcoString,
+    /// The element's documentation.
+    pub docs: EcoString,
+    /// The element's own attributes.
+    pub attrs: Vec<CustomAttr>,
+}
+
+/// An attribute of a custom element.
+#[derive(Clone)]
+pub(crate) struct CustomAttr {
+    /// The attribute's name.
+    pub name: EcoString,
+    /// The attribute's documentation.
+    pub docs: EcoString,
+    /// The attribute's type.
+    pub ty: CustomType,
+}
+
+/// The type of an attribute of a custom element.
+#[derive(Clone)]
+pub(crate) enum CustomType {
+    /// A type that is also used by standard HTML attributes.
+    Spec(data::Type),
+    /// One of the given strings.
+    Strings(Vec<EcoString>),
+}
+
+impl HtmlSchema {
+    /// Parses a schema from JSON.
+    pub fn parse(data: &[u8]) -> StrResult<Self> {
+        let repr: SchemaRepr = serde_json::from_slice(data)
+            .map_err(|err| eco_format!("failed to parse HTML schema ({err})"))?;
+
+        let mut elements: Vec<CustomElem> = vec![];
+        for elem in repr.elements {
+            let name = elem.name.as_str();
+            if !name.contains('-') {
+                bail!("custom element name `{name}` must contain a hyphen");
+            }
+            HtmlTag::intern(name)
+                .map_err(|err| eco_format!("invalid custom element `{name}` ({err})"))?;
+            if elements.iter().any(|prev| prev.name == name) {
+                bail!("custom element `{name}` is defined more than once");
+            }
+
+            let mut attrs: Vec<CustomAttr> = vec![];
+            for attr in elem.attributes {
+                let key = attr.name.as_str();
+                HtmlAttr::intern(key).map_err(|err| {
+                    eco_format!("invalid attribute `{key}` of `{name}` ({err})")
+                })?;
+                if attrs.iter().any(|prev| prev.name == key) {
+                    bail!("attribute `{key}` of `{name}` is defined more than once");
+                }
+                let ty = attr.ty.resolve().map_err(|err| {
+                    eco_format!("invalid type of attribute `{key}` of `{name}` ({err})")
+                })?;
+                attrs.push(CustomAttr { name: attr.name, docs: attr.description, ty });
+            }
+
+            elements.push(CustomElem { name: elem.name, docs: elem.description, attrs });
+        }
+
+        Ok(Self { elements })
+    }
+}
+
+/// The JSON representation of a schema.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaRepr {
+    elements: Vec<ElemRepr>,
+}
+
+/// The JSON representation of a custom element.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ElemRepr {
+    name: EcoString,
+    #[serde(default)]
+    description: EcoString,
+    #[serde(default)]
+    attributes: Vec<AttrRepr>,
+}
+
+/// The JSON representation of an attribute of a custom element.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AttrRepr {
+    name: EcoString,
+    #[serde(default)]
+    description: EcoString,
+    #[serde(rename = "type")]
+    ty: TypeRepr,
+}
+
+/// The JSON representation of an attribute's type.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TypeRepr {
+    Named(EcoString),
+    Strings(Vec<EcoString>),
+}
+
+impl TypeRepr {
+    /// Resolves the type's name.
+    fn resolve(self) -> StrResult<CustomType> {
+        use data::Type;
+        let name = match self {
+            Self::Named(name) => name,
+            Self::Strings(strings) if strings.is_empty() => {
+                bail!("list of allowed strings must not be empty")
+            }
+            Self::Strings(strings) => return Ok(CustomType::Strings(strings)),
+        };
+
+        Ok(CustomType::Spec(match name.as_str() {
+            "string" => Type::Str,
+            "boolean" => Type::Presence,
+            "true-false" => Type::TrueFalse,
+            "yes-no" => Type::YesNo,
+            "on-off" => Type::OnOff,
+            "int" => Type::Int,
+            "non-negative-int" => Type::NonNegativeInt,
+            "positive-int" => Type::PositiveInt,
+            "float" => Type::Float,
+            "positive-float" => Type::PositiveFloat,
+            "char" => Type::Char,
+            "color" => Type::Color,
+            "datetime" => Type::Datetime,
+            "duration" => Type::Duration,
+            "tokens" => Type::List(&Type::Str, ' ', true),
+            _ => bail!("unknown type `{name}`"),
+        }))
+    }
+}
@@ -0,0 +1,491 @@
+use ecow::{EcoString, EcoVec, eco_format, eco_vec};
+use rustc_hash::{FxHashMap, FxHashSet};
+use typst_library::diag::SourceResult;
+use typst_library::foundations::{Content, StyleChain};
+use typst_library::introspection::Tag;
+use typst_library::model::{HeadingElem, OutlineElem};
+use typst_library::text::LocalName;
+use typst_syntax::Span;
+
+use crate::assets::Assets;
+use crate::css::Stylesheet;
+use crate::encode::{HtmlCss, HtmlOptions};
+use crate::{HtmlDocument, HtmlElement, HtmlNode, SplitElem, attr, tag};
+
+/// A file produced by multi-file HTML export.
+#[derive(Debug, Clone)]
+pub struct HtmlFile {
+    /// The file's path, relative to the output directory.
+    pub path: EcoString,
+    /// The file's contents, which are HTML or, for an external stylesheet or
+    /// an asset, CSS or image data.
+    pub contents: Vec<u8>,
+    /// For an HTML file, its HTML annotated with source spans, if the options
+    /// ask for it.
+    pub annotated: Option<String>,
+}
+
+/// Encodes an HTML document into multiple files.
+///
+/// The document is split into chapters at its level-1 headings or, if it
+/// contains any, at its [`SplitElem`] markers. Each chapter is written to its
+/// own file and links to the previous and next chapter. Content before the
+/// first chapter ends up in an `index.html` file, followed by a table of
+/// contents. All files share one stylesheet if the options ask for an external
+/// one and one asset directory if they ask for assets in separate files.
+#[typst_macros::time(name = "html split")]
+pub fn html_split(
+    document: &HtmlDocument,
+    options: &HtmlOptions,
+) -> SourceResult<Vec<HtmlFile>> {
+    let mut split = split(document, "html");
+    let mut assets = options.assets.clone().map(Assets::new);
+    if let Some(assets) = &mut assets {
+        for (_, nodes) in split.files_mut() {
+            assets.extract(nodes);
+        }
+    }
+
+    let mut sheet = Stylesheet::new();
+    if options.css != HtmlCss::Inline {
+        for (_, nodes) in split.files_mut() {
+            sheet.extract(nodes);
+        }
+    }
+    let css = crate::encode::link_stylesheet(&mut split.root, &sheet, &options.css);
+
+    let locale = document.info.locale.unwrap_or_default();
+    let contents = OutlineElem::local_name(locale.lang, locale.region);
+
+    let mut files = Vec::with_capacity(split.chapters.len() + 1);
+    let mut index = split.front.clone();
+    if !split.chapters.is_empty() {
+        index.push(toc(&split.chapters, contents).into());
+    }
+    let encode = |path: &EcoString, root: &HtmlElement| -> SourceResult<HtmlFile> {
+        let html = crate::encode::html_root(root, &document.introspector, false)?;
+        let annotated = options
+            .spans
+            .then(|| crate::encode::html_root(root, &document.introspector, true))
+            .transpose()?;
+        Ok(HtmlFile {
+            path: path.clone(),
+            contents: html.into_bytes(),
+            annotated,
+        })
+    };
+
+    files.push(encode(&split.index, &split.root(None, index))?);
+    for (i, chapter) in split.chapters.iter().enumerate() {
+        let mut nodes = chapter.nodes.clone();
+        nodes.push(navigation(&split, i, contents).into());
+        files.push(encode(&chapter.path, &split.root(Some(&chapter.title), nodes))?);
+    }
+
+    if let (HtmlCss::External(path), Some(css)) = (&options.css, css) {
+        files.push(HtmlFile {
+            path: path.clone(),
+            contents: css.into_bytes(),
+            annotated: None,
+        });
+    }
+
+    if let Some(assets) = assets {
+        files.extend(assets.finish());
+    }
+
+    Ok(files)
+}
+
+/// An HTML document split into chapters.
+pub(crate) struct Split {
+    /// The document's root element.
+    pub root: HtmlElement,
+    /// The document's title.
+    pub title: Option<EcoString>,
+    /// The path of the file with the content before the first chapter.
+    pub index: EcoString,
+    /// The content before the first chapter.
+    pub front: EcoVec<HtmlNode>,
+    /// The chapters.
+    pub chapters: Vec<Chapter>,
+}
+
+/// A chapter of a split HTML document.
+pub(crate) struct Chapter {
+    /// The path of the chapter's file.
+    pub path: EcoString,
+    /// The chapter's title.
+    pub title: EcoString,
+    /// The chapter's contents.
+    pub nodes: EcoVec<HtmlNode>,
+}
+
+impl Split {
+    /// Creates the root element of one file with the given body contents.
+    ///
+    /// If the file has a title, it is combined with the document's title.
+    pub fn root(&self, title: Option<&str>, nodes: EcoVec<HtmlNode>) -> HtmlElement {
+        let mut root = self.root.clone();
+        for child in root.children.make_mut() {
+            let HtmlNode::Element(element) = child else { continue };
+            match element.tag {
+                tag::head => {
+                    if let Some(title) = title {
+                        let title = match &self.title {
+                            Some(document) => eco_format!("{title} – {document}"),
+                            None => title.into(),
+                        };
+                        retitle(element, title);
+                    }
+                }
+                tag::body => element.children = nodes.clone(),
+                _ => {}
+            }
+        }
+
+        root
+    }
+
+    /// Iterates over the paths and contents of all files, starting with the
+    /// index.
+    pub fn files_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&EcoString, &mut EcoVec<HtmlNode>)> {
+        std::iter::once((&self.index, &mut self.front)).chain(
+            self.chapters
+                .iter_mut()
+                .map(|chapter| (&chapter.path, &mut chapter.nodes)),
+        )
+    }
+
+    /// Points links to IDs in the split document to the files that define
+    /// them.
+    ///
+    /// This needs to be called again whenever nodes move between files.
+    pub fn relink(&mut self) {
+        let mut ids = FxHashMap::default();
+        collect_ids(&self.front, &self.index, &mut ids);
+        for chapter in &self.chapters {
+            collect_ids(&chapter.nodes, &chapter.path, &mut ids);
+        }
+
+        let paths: FxHashSet<EcoString> = ids.values().cloned().collect();
+        rewrite_links(&mut self.front, &self.index, &ids, &paths);
+        for chapter in &mut self.chapters {
+            rewrite_links(&mut chapter.nodes, &chapter.path, &ids, &paths);
+        }
+    }
+}
+
+/// Splits a document into chapters whose files have the given extension.
+///
+/// Links across files are rewritten to point to the correct file.
+pub(crate) fn split(document: &HtmlDocument, ext: &str) -> Split {
+    let root = document.root.clone();
+    let nodes = body(&root).map(|body| body.children.clone()).unwrap_or_default();
+
+    // If there are explicit split markers, only they start new chapters.
+    let markers = nodes.iter().any(|node| {
+        matches!(node, HtmlNode::Tag(Tag::Start(elem, _)) if elem.is::<SplitElem>())
+    });
+
+    let mut front = EcoVec::new();
+    let mut parts: Vec<(Option<EcoString>, EcoVec<HtmlNode>)> = vec![];
+    for node in nodes {
+        if let HtmlNode::Tag(Tag::Start(elem, _)) = &node
+            && let Some(title) = chapter_start(elem, markers)
+        {
+            parts.push((title, EcoVec::new()));
+        }
+
+        match parts.last_mut() {
+            Some((_, nodes)) => nodes.push(node),
+            None => front.push(node),
+        }
+    }
+
+    let index = eco_format!("index.{ext}");
+    let chapters: Vec<Chapter> = parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, (title, nodes))| Chapter {
+            path: eco_format!("chapter-{}.{ext}", i + 1),
+            title: title
+                .or_else(|| first_heading(&nodes))
+                .unwrap_or_else(|| eco_format!("Chapter {}", i + 1)),
+            nodes,
+        })
+        .collect();
+
+    let mut split = Split {
+        root,
+        title: document.info.title.clone(),
+        index,
+        front,
+        chapters,
+    };
+    split.relink();
+    split
+}
+
+/// Finds the `<body>` element of a document.
+fn body(root: &HtmlElement) -> Option<&HtmlElement> {
+    root.children.iter().find_map(|child| match child {
+        HtmlNode::Element(element) if element.tag == tag::body => Some(element),
+        _ => None,
+    })
+}
+
+/// Checks whether an element starts a new chapter and if so, returns the
+/// chapter's title, if known.
+fn chapter_start(elem: &Content, markers: bool) -> Option<Option<EcoString>> {
+    if markers {
+        elem.to_packed::<SplitElem>()
+            .map(|marker| marker.title.get_cloned(StyleChain::default()))
+    } else {
+        elem.to_packed::<HeadingElem>()
+            .filter(|heading| heading.resolve_level(StyleChain::default()).get() == 1)
+            .map(|heading| Some(heading.body.plain_text()))
+    }
+}
+
+/// Finds the title of the first heading among the top-level nodes.
+fn first_heading(nodes: &[HtmlNode]) -> Option<EcoString> {
+    nodes.iter().find_map(|node| match node {
+        HtmlNode::Tag(Tag::Start(elem, _)) => elem
+            .to_packed::<HeadingElem>()
+            .map(|heading| heading.body.plain_text()),
+        _ => None,
+    })
+}
+
+/// Records in which file each ID is defined.
+pub(crate) fn collect_ids(
+    nodes: &[HtmlNode],
+    path: &EcoString,
+    ids: &mut FxHashMap<EcoString, EcoString>,
+) {
+    for node in nodes {
+        match node {
+            HtmlNode::Element(element) => {
+                if let Some(id) = element.attrs.get(attr::id) {
+                    ids.insert(id.clone(), path.clone());
+                }
+                collect_ids(&element.children, path, ids);
+            }
+            HtmlNode::Frame(frame) => {
+                for id in
+                    frame.id.iter().chain(frame.link_points.iter().map(|(_, id)| id))
+                {
+                    ids.insert(id.clone(), path.clone());
+                }
+            }
+            HtmlNode::Tag(_) | HtmlNode::Text(..) => {}
+        }
+    }
+}
+
+/// Rewrites links to IDs so that they point to the files defining the IDs.
+///
+/// Links may already point to one of the given paths, in which case they are
+/// adjusted if the ID moved to another file.
+fn rewrite_links(
+    nodes: &mut EcoVec<HtmlNode>,
+    path: &str,
+    ids: &FxHashMap<EcoString, EcoString>,
+    paths: &FxHashSet<EcoString>,
+) {
+    for node in nodes.make_mut() {
+        let HtmlNode::Element(element) = node else { continue };
+        if element.tag == tag::a {
+            for (key, value) in element.attrs.0.make_mut() {
+                if *key == attr::href
+                    && let Some((file, id)) = value.split_once('#')
+                    && (file.is_empty() || paths.contains(file))
+                    && let Some(target) = ids.get(id)
+                {
+                    let href = if target == path {
+                        eco_format!("#{id}")
+                    } else {
+                        eco_format!("{target}#{id}")
+                    };
+                    *value = href;
+                }
+            }
+        }
+        rewrite_links(&mut element.children, path, ids, paths);
+    }
+}
+
+/// Replaces the `<title>` in a `<head>` element or adds one.
+fn retitle(head: &mut HtmlElement, title: EcoString) {
+    let text = eco_vec![HtmlNode::Text(title, Span::detached())];
+    for child in head.children.make_mut() {
+        if let HtmlNode::Element(element) = child
+            && element.tag == tag::title
+        {
+            element.children = text;
+            return;
+        }
+    }
+    head.children
+        .push(HtmlElement::new(tag::title).with_children(text).into());
+}
+
+/// Creates the table of contents listing all chapters.
+fn toc(chapters: &[Chapter], contents: &str) -> HtmlElement {
+    let items = chapters
+        .iter()
+        .map(|chapter| {
+            HtmlElement::new(tag::li)
+                .with_children(eco_vec![link(&chapter.path, &chapter.title).into()])
+                .into()
+        })
+        .collect();
+
+    HtmlElement::new(tag::nav).with_children(eco_vec![
+        HtmlElement::new(tag::h2)
+            .with_children(eco_vec![HtmlNode::text(contents, Span::detached())])
+            .into(),
+        HtmlElement::new(tag::ol).with_children(items).into(),
+    ])
+}
+
+/// Creates the navigation to the previous and next chapter and the table of
+/// contents at the end of a chapter.
+fn navigation(split: &Split, i: usize, contents: &str) -> HtmlElement {
+    let mut children = EcoVec::new();
+    if let Some(prev) = i.checked_sub(1).map(|j| &split.chapters[j]) {
+        children.push(link(&prev.path, &prev.title).with_attr(attr::rel, "prev").into());
+        children.push(HtmlNode::text(' ', Span::detached()));
+    }
+
+    children.push(link(&split.index, contents).into());
+
+    if let Some(next) = split.chapters.get(i + 1) {
+        children.push(HtmlNode::text(' ', Span::detached()));
+        children.push(link(&next.path, &next.title).with_attr(attr::rel, "next").into());
+    }
+
+    HtmlElement::new(tag::nav).with_children(children)
+}
+
+/// Creates a link with a textual body.
+fn link(href: &str, text: &str) -> HtmlElement {
+    HtmlElement::new(tag::a)
+        .with_attr(attr::href, href)
+        .with_children(eco_vec![HtmlNode::text(text, Span::detached())])
+}
+
+#[cfg(test)]
+mod tests {
+    use typst_library::introspection::{Introspector, TagFlags};
+    use typst_library::model::DocumentInfo;
+    use typst_library::text::TextElem;
+
+    use super::*;
+
+    fn heading(title: &str) -> EcoVec<HtmlNode> {
+        let elem = HeadingElem::new(TextElem::packed(title)).pack();
+        let flags = TagFlags { introspectable: false, tagged: false };
+        eco_vec![
+            HtmlNode::Tag(Tag::Start(elem, flags)),
+            HtmlElement::new(tag::h2)
+                .with_children(eco_vec![HtmlNode::text(title, Span::detached())])
+                .into(),
+        ]
+    }
+
+    fn link_to(href: &str) -> HtmlNode {
+        HtmlElement::new(tag::a).with_attr(attr::href, href).into()
+    }
+
+    fn target(id: &str) -> HtmlNode {
+        HtmlElement::new(tag::p).with_attr(attr::id, id).into()
+    }
+
+    fn document(nodes: EcoVec<HtmlNode>) -> HtmlDocument {
+        let head = HtmlElement::new(tag::head);
+        let body = HtmlElement::new(tag::body).with_children(nodes);
+        HtmlDocument {
+            root: HtmlElement::new(tag::html)
+                .with_children(eco_vec![head.into(), body.into()]),
+            info: DocumentInfo {
+                title: Some("Book".into()),
+                ..DocumentInfo::default()
+            },
+            introspector: Introspector::default(),
+            fonts: vec![],
+        }
+    }
+
+    fn hrefs(nodes: &[HtmlNode]) -> Vec<&str> {
+        nodes
+            .iter()
+            .filter_map(|node| match node {
+                HtmlNode::Element(element) if element.tag == tag::a => {
+                    element.attrs.get(attr::href).map(EcoString::as_str)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_relinks() {
+        let mut nodes = eco_vec![link_to("#b"), target("front")];
+        nodes.extend(heading("A"));
+        nodes.extend([link_to("#b"), link_to("#front"), link_to("#a"), target("a")]);
+        nodes.extend(heading("B"));
+        nodes.extend([link_to("#b"), target("b"), link_to("https://typst.app/#b")]);
+
+        let split = split(&document(nodes), "html");
+        assert_eq!(split.chapters.len(), 2);
+        assert_eq!(hrefs(&split.front), ["chapter-2.html#b"]);
+        assert_eq!(
+            hrefs(&split.chapters[0].nodes),
+            ["chapter-2.html#b", "index.html#front", "#a"]
+        );
+        assert_eq!(hrefs(&split.chapters[1].nodes), ["#b", "https://typst.app/#b"]);
+    }
+
+    #[test]
+    fn test_split_titles() {
+        let mut nodes = EcoVec::new();
+        nodes.extend(heading("Intro"));
+        nodes.extend(heading("Usage"));
+        let split = split(&document(nodes), "html");
+        assert!(split.front.is_empty());
+        let titles: Vec<_> =
+            split.chapters.iter().map(|chapter| chapter.title.as_str()).collect();
+        assert_eq!(titles, ["Intro", "Usage"]);
+    }
+
+    #[test]
+    fn test_html_split_toc() {
+        let mut nodes = eco_vec![target("front")];
+        nodes.extend(heading("Intro"));
+        nodes.extend(heading("Usage"));
+        let files = html_split(&document(nodes), &HtmlOptions::default()).unwrap();
+        let paths: Vec<_> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["index.html", "chapter-1.html", "chapter-2.html"]);
+
+        let index = std::str::from_utf8(&files[0].contents).unwrap();
+        assert!(!index.contains("<title>"));
+        assert!(index.contains("<h2>Contents</h2>"));
+        assert!(index.contains("<li><a href=\"chapter-1.html\">Intro</a></li>"));
+        assert!(index.contains("<li><a href=\"chapter-2.html\">Usage</a></li>"));
+
+        let first = std::str::from_utf8(&files[1].contents).unwrap();
+        assert!(first.contains("<title>Intro – Book</title>"));
+        assert!(first.contains("<a href=\"index.html\">Contents</a>"));
+        assert!(first.contains("<a href=\"chapter-2.html\" rel=\"next\">Usage</a>"));
+        assert!(!first.contains("rel=\"prev\""));
+
+        let second = std::str::from_utf8(&files[2].contents).unwrap();
+        assert!(second.contains("<a href=\"chapter-1.html\" rel=\"prev\">Intro</a>"));
+        assert!(!second.contains("rel=\"next\""));
+        assert!(files.iter().all(|file| file.annotated.is_none()));
+    }
+}
@@ -14,7 +14,7 @@ use typst_assets::html as data;
 use typst_library::diag::{At, Hint, HintedStrResult, SourceResult, bail};
 use typst_library::engine::Engine;
 use typst_library::foundations::{
-    Args, Array, AutoValue, CastInfo, Content, Context, Datetime, Dict, Duration,
+    Args, Array, AutoValue, Bytes, CastInfo, Content, Context, Datetime, Dict, Duration,
     FromValue, IntoValue, NativeFuncData, NativeFuncPtr, NoneValue, ParamInfo,
     PositiveF64, Reflect, Scope, Str, Type, Value,
 };
@@ -22,13 +22,35 @@ use typst_library::layout::{Axes, Axis, Dir, Length};
 use typst_library::visualize::Color;
 use typst_macros::cast;
 
+use crate::schema::{CustomElem, CustomType, HtmlSchema};
 use crate::{HtmlAttr, HtmlAttrs, HtmlElem, HtmlTag, css, tag};
 
-/// Hook up all typed HTML definitions.
-pub(super) fn define(html: &mut Scope) {
+/// Hook up all typed HTML definitions, including those for the custom
+/// elements described by the given schemas.
+pub(super) fn define(html: &mut Scope, schemas: &[Bytes]) {
     for data in FUNCS.iter() {
         html.define_func_with_data(data);
     }
+
+    if schemas.is_empty() {
+        return;
+    }
+
+    // Like for `FUNCS`, leaking is okay here since the module is created only
+    // once per library.
+    let bump: &'static Bump = Box::leak(Box::new(Bump::new()));
+    for schema in schemas.iter().filter_map(|data| HtmlSchema::parse(data).ok()) {
+        for elem in &schema.elements {
+            // The first schema that describes an element wins.
+            if html.get(&elem.name).is_some() {
+                continue;
+            }
+
+            let info = bump.alloc(CustomElemInfo::new(elem, bump));
+            let data = bump.alloc(create_func_data(ElemDef::Custom(info), bump));
+            html.define_func_with_data(data);
+        }
+    }
 }
 
 /// Lazily created functions for all typed HTML constructors.
@@ -36,27 +58,151 @@ static FUNCS: LazyLock<Vec<NativeFuncData>> = LazyLock::new(|| {
     // Leaking is okay here. It's not meaningfully different from having
     // memory-managed values as `FUNCS` is a static.
     let bump = Box::leak(Box::new(Bump::new()));
-    data::ELEMS.iter().map(|info| create_func_data(info, bump)).collect()
+    data::ELEMS
+        .iter()
+        .map(|info| create_func_data(ElemDef::Spec(info), bump))
+        .collect()
 });
 
+/// An element with a typed constructor.
+#[derive(Copy, Clone)]
+enum ElemDef {
+    /// A standard HTML element.
+    Spec(&'static data::ElemInfo),
+    /// A custom element described by a schema.
+    Custom(&'static CustomElemInfo),
+}
+
+impl ElemDef {
+    /// The element's tag name.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Spec(info) => info.name,
+            Self::Custom(info) => info.name,
+        }
+    }
+
+    /// The element's documentation.
+    fn docs(self) -> &'static str {
+        match self {
+            Self::Spec(info) => info.docs,
+            Self::Custom(info) => info.docs,
+        }
+    }
+
+    /// The element's tag.
+    fn tag(self) -> HtmlTag {
+        match self {
+            Self::Spec(info) => HtmlTag::constant(info.name),
+            Self::Custom(info) => info.tag,
+        }
+    }
+
+    /// The names, documentation, and types of the element's attributes.
+    fn attrs(self) -> Vec<(&'static str, &'static str, AttrType)> {
+        let spec = |info: &'static data::ElemInfo| {
+            info.attributes()
+                .map(|attr| (attr.name, attr.docs, AttrType::convert(attr.ty)))
+        };
+        match self {
+            Self::Spec(info) => spec(info).collect(),
+            Self::Custom(info) => info
+                .attrs
+                .iter()
+                .map(|attr| (attr.name, attr.docs, attr.ty))
+                .chain(spec(globals()).filter(|(name, ..)| info.get_attr(name).is_none()))
+                .collect(),
+        }
+    }
+
+    /// The attribute with the given name and its type.
+    fn get_attr(self, name: &str) -> Option<(HtmlAttr, AttrType)> {
+        let spec = |info: &'static data::ElemInfo| {
+            info.get_attr(name)
+                .map(|attr| (HtmlAttr::constant(attr.name), AttrType::convert(attr.ty)))
+        };
+        match self {
+            Self::Spec(info) => spec(info),
+            Self::Custom(info) => info
+                .get_attr(name)
+                .map(|attr| (attr.attr, attr.ty))
+                .or_else(|| spec(globals())),
+        }
+    }
+}
+
+/// The `div` element, whose attributes are exactly the global attributes.
+fn globals() -> &'static data::ElemInfo {
+    data::ELEMS.iter().find(|info| info.name == "div").unwrap()
+}
+
+/// A custom element with its data leaked for use in a native function.
+struct CustomElemInfo {
+    name: &'static str,
+    tag: HtmlTag,
+    docs: &'static str,
+    attrs: &'static [CustomAttrInfo],
+}
+
+/// An attribute of a custom element.
+struct CustomAttrInfo {
+    name: &'static str,
+    attr: HtmlAttr,
+    docs: &'static str,
+    ty: AttrType,
+}
+
+impl CustomElemInfo {
+    /// Leaks the data of an element from a schema into the bump.
+    ///
+    /// Names were already validated when parsing the schema.
+    fn new(elem: &CustomElem, bump: &'static Bump) -> Self {
+        let attrs = bump.alloc_slice_fill_iter(elem.attrs.iter().map(|attr| {
+            let ty = match &attr.ty {
+                CustomType::Spec(ty) => AttrType::convert(*ty),
+                CustomType::Strings(strings) => {
+                    AttrType::Strings(StringsType(bump.alloc_slice_fill_iter(
+                        strings.iter().map(|string| (&*bump.alloc_str(string), "")),
+                    )))
+                }
+            };
+            CustomAttrInfo {
+                name: bump.alloc_str(&attr.name),
+                attr: HtmlAttr::intern(&attr.name).unwrap(),
+                docs: bump.alloc_str(&attr.docs),
+                ty,
+            }
+        }));
+
+        Self {
+            name: bump.alloc_str(&elem.name),
+            tag: HtmlTag::intern(&elem.name).unwrap(),
+            docs: bump.alloc_str(&elem.docs),
+            attrs,
+        }
+    }
+
+    /// The element's own attribute with the given name.
+    fn get_attr(&self, name: &str) -> Option<&'static CustomAttrInfo> {
+        self.attrs.iter().find(|attr| attr.name == name)
+    }
+}
+
 /// Creates metadata for a native HTML element constructor function.
-fn create_func_data(
-    element: &'static data::ElemInfo,
-    bump: &'static Bump,
-) -> NativeFuncData {
+fn create_func_data(element: ElemDef, bump: &'static Bump) -> NativeFuncData {
     NativeFuncData {
         function: NativeFuncPtr(bump.alloc(
             move |_: &mut Engine, _: Tracked<Context>, args: &mut Args| {
                 construct(element, args)
             },
         )),
-        name: element.name,
+        name: element.name(),
         title: {
-            let title = bump.alloc_str(element.name);
+            let title = bump.alloc_str(element.name());
             title[0..1].make_ascii_uppercase();
             title
         },
-        docs: element.docs,
+        docs: element.docs(),
         keywords: &[],
         contextual: false,
         scope: LazyLock::new(&|| Scope::new()),
@@ -66,13 +212,13 @@ fn create_func_data(
 }
 
 /// Creates parameter signature metadata for an element.
-fn create_param_info(element: &'static data::ElemInfo) -> Vec<ParamInfo> {
+fn create_param_info(element: ElemDef) -> Vec<ParamInfo> {
     let mut params = vec![];
-    for attr in element.attributes() {
+    for (name, docs, ty) in element.attrs() {
         params.push(ParamInfo {
-            name: attr.name,
-            docs: attr.docs,
-            input: AttrType::convert(attr.ty).input(),
+            name,
+            docs,
+            input: ty.input(),
             default: None,
             positional: false,
             named: true,
@@ -81,8 +227,7 @@ fn create_param_info(element: &'static data::ElemInfo) -> Vec<ParamInfo> {
             settable: false,
         });
     }
-    let tag = HtmlTag::constant(element.name);
-    if !tag::is_void(tag) {
+    if !tag::is_void(element.tag()) {
         params.push(ParamInfo {
             name: "body",
             docs: "The contents of the HTML element.",
@@ -99,19 +244,18 @@ fn create_param_info(element: &'static data::ElemInfo) -> Vec<ParamInfo> {
 }
 
 /// The native constructor function shared by all HTML elements.
-fn construct(element: &'static data::ElemInfo, args: &mut Args) -> SourceResult<Value> {
+fn construct(element: ElemDef, args: &mut Args) -> SourceResult<Value> {
     let mut attrs = HtmlAttrs::default();
     let mut errors = eco_vec![];
 
     args.items.retain(|item| {
         let Some(name) = &item.name else { return true };
-        let Some(attr) = element.get_attr(name) else { return true };
+        let Some((attr, ty)) = element.get_attr(name) else { return true };
 
         let span = item.value.span;
         let value = std::mem::take(&mut item.value.v);
-        let ty = AttrType::convert(attr.ty);
         match ty.cast(value).at(span) {
-            Ok(Some(string)) => attrs.push(HtmlAttr::constant(attr.name), string),
+            Ok(Some(string)) => attrs.push(attr, string),
             Ok(None) => {}
             Err(diags) => errors.extend(diags),
         }
@@ -123,7 +267,7 @@ fn construct(element: &'static data::ElemInfo, args: &mut Args) -> SourceResult<
         return Err(errors);
     }
 
-    let tag = HtmlTag::constant(element.name);
+    let tag = element.tag();
     let mut elem = HtmlElem::new(tag);
     if !attrs.0.is_empty() {
         elem.attrs.set(attrs);
@@ -140,6 +284,7 @@ fn construct(element: &'static data::ElemInfo, args: &mut Args) -> SourceResult<
 /// A dynamic representation of an attribute's type.
 ///
 /// See the documentation of [`data::Type`] for more details on variants.
+#[derive(Copy, Clone)]
 enum AttrType {
     Presence,
     Native(NativeType),
@@ -151,7 +296,7 @@ enum AttrType {
 impl AttrType {
     /// Converts the type definition into a representation suitable for casting
     /// and reflection.
-    const fn convert(ty: data::Type) -> AttrType {
+    fn convert(ty: data::Type) -> AttrType {
         use data::Type;
         match ty {
             Type::Presence => Self::Presence,
@@ -176,7 +321,9 @@ impl AttrType {
             Type::IconSize => Self::of::<IconSize>(),
             Type::ImageCandidate => Self::of::<ImageCandidate>(),
             Type::SourceSize => Self::of::<SourceSize>(),
-            Type::Strings(start, end) => Self::Strings(StringsType { start, end }),
+            Type::Strings(start, end) => {
+                Self::Strings(StringsType(&data::ATTR_STRINGS[start..end]))
+            }
             Type::Union(variants) => Self::Union(UnionType(variants)),
             Type::List(inner, separator, shorthand) => {
                 Self::List(ListType { inner, separator, shorthand })
@@ -225,13 +372,11 @@ impl AttrType {
     }
 }
 
-/// An enumeration with generated string variants.
+/// An enumeration of string variants with their descriptions.
 ///
-/// `start` and `end` are used to index into `data::ATTR_STRINGS`.
-struct StringsType {
-    start: usize,
-    end: usize,
-}
+/// For standard attributes, the variants are a slice of `data::ATTR_STRINGS`.
+#[derive(Copy, Clone)]
+struct StringsType(&'static [(&'static str, &'static str)]);
 
 impl StringsType {
     fn input(&self) -> CastInfo {
@@ -259,11 +404,12 @@ impl StringsType {
     }
 
     fn strings(&self) -> &'static [(&'static str, &'static str)] {
-        &data::ATTR_STRINGS[self.start..self.end]
+        self.0
     }
 }
 
 /// A type that accepts any of the contained types.
+#[derive(Copy, Clone)]
 struct UnionType(&'static [data::Type]);
 
 impl UnionType {
@@ -293,6 +439,7 @@ impl UnionType {
 ///
 /// - <https://html.spec.whatwg.org/#space-separated-tokens>
 /// - <https://html.spec.whatwg.org/#comma-separated-tokens>
+#[derive(Copy, Clone)]
 struct ListType {
     inner: &'static data::Type,
     separator: char,
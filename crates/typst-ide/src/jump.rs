@@ -41,10 +41,13 @@ pub fn jump_from_click(
         {
             return Some(match dest {
                 Destination::Url(url) => Jump::Url(url.clone()),
-                Destination::Position(pos) => Jump::Position(*pos),
-                Destination::Location(loc) => {
-                    Jump::Position(document.introspector.position(*loc))
+                Destination::Position(pos) => {
+                    Jump::Position(on_full_page(document, *pos))
                 }
+                Destination::Location(loc) => Jump::Position(on_full_page(
+                    document,
+                    document.introspector.position(*loc),
+                )),
             });
         }
     }
@@ -144,6 +147,16 @@ pub fn jump_from_click(
     None
 }
 
+/// Converts a position on a trimmed page into one on the full page, which may
+/// additionally contain the bleed and printer marks.
+fn on_full_page(document: &PagedDocument, pos: Position) -> Position {
+    let trim = document
+        .pages
+        .get(pos.page.get() - 1)
+        .map_or(Point::zero(), |page| page.trim_box().min);
+    Position { page: pos.page, point: pos.point + trim }
+}
+
 /// Find the output location in the document for a cursor position.
 pub fn jump_from_cursor(
     document: &PagedDocument,
@@ -0,0 +1,93 @@
+use typst_library::diag::SourceResult;
+use typst_library::engine::Engine;
+use typst_library::foundations::{Content, Packed, StyleChain};
+use typst_library::introspection::Locator;
+use typst_library::layout::{
+    BlockBody, BlockElem, Fragment, OuterVAlignment, Regions, VElem,
+};
+use typst_library::model::{FigureElem, ParbreakElem};
+
+/// Realizes the figure's body together with its caption.
+pub fn realize_figure(elem: &Packed<FigureElem>, styles: StyleChain) -> Content {
+    let span = elem.span();
+    let mut realized = elem.body.clone();
+
+    // Build the caption, if any.
+    if let Some(caption) = elem.caption.get_cloned(styles) {
+        let (first, second) = match caption.position.get(styles) {
+            OuterVAlignment::Top => (caption.pack(), realized),
+            OuterVAlignment::Bottom => (realized, caption.pack()),
+        };
+        realized = Content::sequence(vec![
+            first,
+            VElem::new(elem.gap.get(styles).into())
+                .with_weak(true)
+                .pack()
+                .spanned(span),
+            second,
+        ]);
+    }
+
+    // Ensure that the body is considered a paragraph.
+    realized + ParbreakElem::shared().clone().spanned(span)
+}
+
+/// Whether the figure has a top caption which should be repeated on each page
+/// after the first when the figure breaks.
+pub fn has_continued_caption(elem: &Packed<FigureElem>, styles: StyleChain) -> bool {
+    elem.placement.get(styles).is_none()
+        && elem.caption.get_ref(styles).as_ref().is_some_and(|caption| {
+            caption.position.get(styles) == OuterVAlignment::Top
+                && caption.continuation.get_ref(styles).is_some()
+        })
+}
+
+/// Layout a figure, repeating its caption's continuation at the top of each
+/// region after the first.
+#[typst_macros::time(span = elem.span())]
+pub fn layout_figure(
+    elem: &Packed<FigureElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    regions: Regions,
+) -> SourceResult<Fragment> {
+    let body = realize_figure(elem, styles);
+    let continuation = match elem.caption.get_ref(styles) {
+        Some(caption) => caption.realize_continuation(engine, styles)?,
+        None => None,
+    };
+
+    let mut locator = locator.split();
+    let Some(continuation) =
+        continuation.filter(|_| !regions.backlog.is_empty() || regions.last.is_some())
+    else {
+        return crate::layout_fragment(
+            engine,
+            &body,
+            locator.next(&body.span()),
+            styles,
+            regions,
+        );
+    };
+
+    // Keep the same distance to the body as the original caption.
+    let continuation = Content::sequence([
+        BlockElem::new()
+            .with_body(Some(BlockBody::Content(continuation)))
+            .pack(),
+        VElem::new(elem.gap.get(styles).into()).pack(),
+    ])
+    .spanned(elem.span());
+
+    let frames = crate::grid::layout_continued(
+        engine,
+        &body,
+        &continuation,
+        &mut locator,
+        styles,
+        regions,
+    )?;
+
+    Ok(Fragment::frames(frames))
+}
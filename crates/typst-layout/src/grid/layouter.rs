@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 use rustc_hash::FxHashMap;
@@ -20,7 +21,7 @@ use typst_utils::Numeric;
 
 use super::{
     LineSegment, Rowspan, UnbreakableRowGroup, generate_line_segments,
-    hline_stroke_at_column, layout_cell, vline_stroke_at_row,
+    hline_stroke_at_column, layout_cell, pad_cell_body, vline_stroke_at_row,
 };
 
 /// Performs grid layout.
@@ -31,6 +32,9 @@ pub struct GridLayouter<'a> {
     pub(super) regions: Regions<'a>,
     /// The locators for the each cell in the cell grid.
     pub(super) cell_locators: FxHashMap<Axes<usize>, Locator<'a>>,
+    /// How much to pad the bodies of the cells that are aligned on a
+    /// character before and after, keyed by the cells' positions.
+    pub(super) char_pads: FxHashMap<Axes<usize>, (Abs, Abs)>,
     /// The inherited styles.
     pub(super) styles: StyleChain<'a>,
     /// Resolved column sizes.
@@ -52,6 +56,10 @@ pub struct GridLayouter<'a> {
     pub(super) finished: Vec<Frame>,
     /// The amount and height of header rows on each finished region.
     pub(super) finished_header_rows: Vec<FinishedHeaderRowInfo>,
+    /// For each finished region, the row which was split between it and the
+    /// next region, if any. Lines at such a split are drawn with the strokes
+    /// of the split cells instead of the grid's borders.
+    pub(super) split_rows: Vec<Option<usize>>,
     /// Whether this is an RTL grid.
     pub(super) is_rtl: bool,
     /// Currently repeating headers, one per level. Sorted by increasing
@@ -265,6 +273,7 @@ impl<'a> GridLayouter<'a> {
             grid,
             regions,
             cell_locators,
+            char_pads: FxHashMap::default(),
             styles,
             rcols: vec![Abs::zero(); grid.cols.len()],
             width: Abs::zero(),
@@ -273,6 +282,7 @@ impl<'a> GridLayouter<'a> {
             rowspans: vec![],
             finished: vec![],
             finished_header_rows: vec![],
+            split_rows: vec![],
             is_rtl: styles.resolve(TextElem::dir) == Dir::RTL,
             repeating_headers: vec![],
             upcoming_headers: &grid.headers,
@@ -294,6 +304,33 @@ impl<'a> GridLayouter<'a> {
         }
     }
 
+    /// Pads the bodies of the cells at the given positions before and after,
+    /// such that their alignment characters line up.
+    pub(super) fn with_char_pads(
+        mut self,
+        char_pads: FxHashMap<Axes<usize>, (Abs, Abs)>,
+    ) -> Self {
+        self.char_pads = char_pads;
+        self
+    }
+
+    /// Returns the cell at the given position for use in [`layout_cell`],
+    /// with its body padded if it is aligned on a character.
+    pub(super) fn padded_cell<'c>(
+        &self,
+        cell: &'c Cell,
+        pos: Axes<usize>,
+    ) -> Cow<'c, Cell> {
+        match self.char_pads.get(&pos) {
+            Some(&(before, after)) => {
+                let mut cell = cell.clone();
+                pad_cell_body(&mut cell.body, before, after);
+                Cow::Owned(cell)
+            }
+            None => Cow::Borrowed(cell),
+        }
+    }
+
     /// Create a [`Locator`] for use in [`layout_cell`].
     pub(super) fn cell_locator(
         &self,
@@ -479,6 +516,11 @@ impl<'a> GridLayouter<'a> {
                 continue;
             }
 
+            // Rows which were split between this region and the previous or
+            // next one, respectively.
+            let split_above = frame_index.checked_sub(1).and_then(|i| self.split_rows[i]);
+            let split_below = self.split_rows.get(frame_index).copied().flatten();
+
             // Render grid lines.
             // We collect lines into a vector before rendering so we can sort
             // them based on thickness, such that the lines with largest
@@ -754,6 +796,15 @@ impl<'a> GridLayouter<'a> {
                 // regions.
                 let in_last_region = frame_index + 1 == frame_amount;
 
+                // Whether this line is drawn where a row was split across
+                // regions, either at the top or at the bottom of this region.
+                let at_split = match prev_y {
+                    Some(prev_y) => {
+                        y == self.grid.rows.len() && split_below == Some(prev_y)
+                    }
+                    None => split_above == Some(y),
+                };
+
                 // Determine all different line segments we have to draw in
                 // this row, and convert them to points and shapes.
                 let segments = generate_line_segments(
@@ -768,6 +819,7 @@ impl<'a> GridLayouter<'a> {
                             local_top_y,
                             last_repeated_header_end_above,
                             in_last_region,
+                            at_split,
                             y,
                             x,
                             stroke,
@@ -938,7 +990,7 @@ impl<'a> GridLayouter<'a> {
         let available = self.regions.size.x - rel;
         if available >= Abs::zero() {
             // Determine size of auto columns.
-            let (auto, count) = self.measure_auto_columns(engine, available)?;
+            let auto = self.measure_auto_columns(engine, available)?;
 
             // If there is remaining space, distribute it to fractional columns,
             // otherwise shrink auto columns.
@@ -946,7 +998,7 @@ impl<'a> GridLayouter<'a> {
             if remaining >= Abs::zero() {
                 self.grow_fractional_columns(remaining, fr);
             } else {
-                self.shrink_auto_columns(available, count);
+                self.shrink_auto_columns(engine, available)?;
             }
         }
 
@@ -968,134 +1020,163 @@ impl<'a> GridLayouter<'a> {
         &mut self,
         engine: &mut Engine,
         available: Abs,
-    ) -> SourceResult<(Abs, usize)> {
+    ) -> SourceResult<Abs> {
+        let grid = self.grid;
+        let all_frac_cols = self.fractional_columns();
         let mut auto = Abs::zero();
-        let mut count = 0;
-        let all_frac_cols = self
-            .grid
-            .cols
-            .iter()
-            .enumerate()
-            .filter(|(_, col)| col.is_fractional())
-            .map(|(x, _)| x)
-            .collect::<Vec<_>>();
 
         // Determine size of auto columns by laying out all cells in those
         // columns, measuring them and finding the largest one.
-        for (x, &col) in self.grid.cols.iter().enumerate() {
+        for (x, &col) in grid.cols.iter().enumerate() {
             if col != Sizing::Auto {
                 continue;
             }
 
-            let mut resolved = Abs::zero();
-            for y in 0..self.grid.rows.len() {
-                // We get the parent cell in case this is a merged position.
-                let Some(parent) = self.grid.parent_cell_position(x, y) else {
-                    continue;
-                };
-                if parent.y != y {
-                    // Don't check the width of rowspans more than once.
-                    continue;
-                }
-                let cell = self.grid.cell(parent.x, parent.y).unwrap();
-                let colspan = self.grid.effective_colspan_of_cell(cell);
-                if colspan > 1 {
-                    let last_spanned_auto_col = self
-                        .grid
-                        .cols
-                        .iter()
-                        .enumerate()
-                        .skip(parent.x)
-                        .take(colspan)
-                        .rev()
-                        .find(|(_, col)| **col == Sizing::Auto)
-                        .map(|(x, _)| x);
-
-                    if last_spanned_auto_col != Some(x) {
-                        // A colspan only affects the size of the last spanned
-                        // auto column.
-                        continue;
-                    }
-                }
+            let mut resolved =
+                self.measure_auto_column(engine, x, available, &all_frac_cols)?;
 
-                if colspan > 1
-                    && self.regions.size.x.is_finite()
-                    && !all_frac_cols.is_empty()
-                    && all_frac_cols
-                        .iter()
-                        .all(|x| (parent.x..parent.x + colspan).contains(x))
-                {
-                    // Additionally, as a heuristic, a colspan won't affect the
-                    // size of auto columns if it already spans all fractional
-                    // columns, since those would already expand to provide all
-                    // remaining available after auto column sizing to that
-                    // cell. However, this heuristic is only valid in finite
-                    // regions (pages without 'auto' width), since otherwise
-                    // the fractional columns don't expand at all.
+            // Keep columns sized with `minmax` within their bounds.
+            if let Some(bounds) = grid.col_bounds[x] {
+                resolved = resolved.min(self.resolve_column(bounds.max));
+                resolved.set_max(self.resolve_column(bounds.min));
+            }
+
+            self.rcols[x] = resolved;
+            auto += resolved;
+        }
+
+        Ok(auto)
+    }
+
+    /// Measure the width of an auto column by laying out its cells with the
+    /// given width.
+    fn measure_auto_column(
+        &self,
+        engine: &mut Engine,
+        x: usize,
+        width: Abs,
+        all_frac_cols: &[usize],
+    ) -> SourceResult<Abs> {
+        let mut resolved = Abs::zero();
+        for y in 0..self.grid.rows.len() {
+            // We get the parent cell in case this is a merged position.
+            let Some(parent) = self.grid.parent_cell_position(x, y) else {
+                continue;
+            };
+            if parent.y != y {
+                // Don't check the width of rowspans more than once.
+                continue;
+            }
+            let cell = self.grid.cell(parent.x, parent.y).unwrap();
+            let colspan = self.grid.effective_colspan_of_cell(cell);
+            if colspan > 1 {
+                let last_spanned_auto_col = self
+                    .grid
+                    .cols
+                    .iter()
+                    .enumerate()
+                    .skip(parent.x)
+                    .take(colspan)
+                    .rev()
+                    .find(|(_, col)| **col == Sizing::Auto)
+                    .map(|(x, _)| x);
+
+                if last_spanned_auto_col != Some(x) {
+                    // A colspan only affects the size of the last spanned
+                    // auto column.
                     continue;
                 }
+            }
 
-                // Sum the heights of spanned rows to find the expected
-                // available height for the cell, unless it spans a fractional
-                // or auto column.
-                let rowspan = self.grid.effective_rowspan_of_cell(cell);
-                let height = self
-                    .grid
-                    .rows
+            if colspan > 1
+                && self.regions.size.x.is_finite()
+                && !all_frac_cols.is_empty()
+                && all_frac_cols
                     .iter()
-                    .skip(y)
-                    .take(rowspan)
-                    .try_fold(Abs::zero(), |acc, col| {
-                        // For relative rows, we can already resolve the correct
-                        // base and for auto and fr we could only guess anyway.
-                        match col {
-                            Sizing::Rel(v) => Some(
-                                acc + v
-                                    .resolve(self.styles)
-                                    .relative_to(self.regions.base().y),
-                            ),
-                            _ => None,
-                        }
-                    })
-                    .unwrap_or_else(|| self.regions.base().y);
-
-                // Don't expand this auto column more than the cell actually
-                // needs. To do this, we check how much the other, previously
-                // resolved columns provide to the cell in terms of width
-                // (if it is a colspan), and subtract this from its expected
-                // width when comparing with other cells in this column. Note
-                // that, since this is the last auto column spanned by this
-                // cell, all other auto columns will already have been resolved
-                // and will be considered.
-                // Only fractional columns will be excluded from this
-                // calculation, which can lead to auto columns being expanded
-                // unnecessarily when cells span both a fractional column and
-                // an auto column. One mitigation for this is the heuristic
-                // used above to not expand the last auto column spanned by a
-                // cell if it spans all fractional columns in a finite region.
-                let already_covered_width = self.cell_spanned_width(cell, parent.x);
-
-                let size = Size::new(available, height);
-                let pod = Region::new(size, Axes::splat(false));
-                let locator = self.cell_locator(parent, 0);
-                let frame = layout_cell(
-                    cell,
-                    engine,
-                    locator,
-                    self.styles,
-                    pod.into(),
-                    self.row_state.is_being_repeated,
-                )?
-                .into_frame();
-                resolved.set_max(frame.width() - already_covered_width);
+                    .all(|x| (parent.x..parent.x + colspan).contains(x))
+            {
+                // Additionally, as a heuristic, a colspan won't affect the
+                // size of auto columns if it already spans all fractional
+                // columns, since those would already expand to provide all
+                // remaining available after auto column sizing to that
+                // cell. However, this heuristic is only valid in finite
+                // regions (pages without 'auto' width), since otherwise
+                // the fractional columns don't expand at all.
+                continue;
             }
 
-            self.rcols[x] = resolved;
-            auto += resolved;
-            count += 1;
+            // Sum the heights of spanned rows to find the expected
+            // available height for the cell, unless it spans a fractional
+            // or auto column.
+            let rowspan = self.grid.effective_rowspan_of_cell(cell);
+            let height = self
+                .grid
+                .rows
+                .iter()
+                .skip(y)
+                .take(rowspan)
+                .try_fold(Abs::zero(), |acc, col| {
+                    // For relative rows, we can already resolve the correct
+                    // base and for auto and fr we could only guess anyway.
+                    match col {
+                        Sizing::Rel(v) => Some(
+                            acc + v
+                                .resolve(self.styles)
+                                .relative_to(self.regions.base().y),
+                        ),
+                        _ => None,
+                    }
+                })
+                .unwrap_or_else(|| self.regions.base().y);
+
+            // Don't expand this auto column more than the cell actually
+            // needs. To do this, we check how much the other, previously
+            // resolved columns provide to the cell in terms of width
+            // (if it is a colspan), and subtract this from its expected
+            // width when comparing with other cells in this column. Note
+            // that, since this is the last auto column spanned by this
+            // cell, all other auto columns will already have been resolved
+            // and will be considered.
+            // Only fractional columns will be excluded from this
+            // calculation, which can lead to auto columns being expanded
+            // unnecessarily when cells span both a fractional column and
+            // an auto column. One mitigation for this is the heuristic
+            // used above to not expand the last auto column spanned by a
+            // cell if it spans all fractional columns in a finite region.
+            let already_covered_width = self.cell_spanned_width(cell, parent.x);
+
+            let size = Size::new(width, height);
+            let pod = Region::new(size, Axes::splat(false));
+            let locator = self.cell_locator(parent, 0);
+            let frame = layout_cell(
+                &self.padded_cell(cell, parent),
+                engine,
+                locator,
+                self.styles,
+                pod.into(),
+                self.row_state.is_being_repeated,
+            )?
+            .into_frame();
+            resolved.set_max(frame.width() - already_covered_width);
         }
 
-        Ok((auto, count))
+        Ok(resolved)
+    }
+
+    /// The indices of all fractional columns.
+    fn fractional_columns(&self) -> Vec<usize> {
+        self.grid
+            .cols
+            .iter()
+            .enumerate()
+            .filter(|(_, col)| col.is_fractional())
+            .map(|(x, _)| x)
+            .collect()
+    }
+
+    /// Resolve the size of a column relative to the region's width.
+    fn resolve_column(&self, size: Rel) -> Abs {
+        size.resolve(self.styles).relative_to(self.regions.base().x)
     }
 
     /// Distribute remaining space to fractional columns.
@@ -1111,37 +1192,71 @@ impl<'a> GridLayouter<'a> {
         }
     }
 
-    /// Redistribute space to auto columns so that each gets a fair share.
-    fn shrink_auto_columns(&mut self, available: Abs, count: usize) {
-        let mut last;
-        let mut fair = -Abs::inf();
+    /// Shrink auto columns so that they fit into the available space.
+    ///
+    /// Auto columns that fit into a fair share of the available space keep
+    /// their width. Similarly to automatic table layout in HTML, each of the
+    /// remaining, overlarge columns receives at least its minimum content
+    /// width, i.e. the width of its widest piece of content which cannot be
+    /// broken. The rest of the space is distributed in proportion to how much
+    /// wider each of them would like to be. Columns sized with `minmax` never
+    /// become narrower than their minimum.
+    fn shrink_auto_columns(
+        &mut self,
+        engine: &mut Engine,
+        available: Abs,
+    ) -> SourceResult<()> {
+        let grid = self.grid;
+        let is_auto = |x: usize| grid.cols[x] == Sizing::Auto;
+
+        // Iteratively remove columns that fit into a fair share of the space.
+        let mut overlarge: Vec<usize> =
+            (0..grid.cols.len()).filter(|&x| is_auto(x)).collect();
         let mut redistribute = available;
-        let mut overlarge = count;
-        let mut changed = true;
-
-        // Iteratively remove columns that don't need to be shrunk.
-        while changed && overlarge > 0 {
-            changed = false;
-            last = fair;
-            fair = redistribute / (overlarge as f64);
-
-            for (&col, &rcol) in self.grid.cols.iter().zip(&self.rcols) {
-                // Remove an auto column if it is not overlarge (rcol <= fair),
-                // but also hasn't already been removed (rcol > last).
-                if col == Sizing::Auto && rcol <= fair && rcol > last {
-                    redistribute -= rcol;
-                    overlarge -= 1;
-                    changed = true;
+        while !overlarge.is_empty() {
+            let fair = redistribute / overlarge.len() as f64;
+            let before = overlarge.len();
+            overlarge.retain(|&x| {
+                let fits = self.rcols[x] <= fair;
+                if fits {
+                    redistribute -= self.rcols[x];
                 }
+                !fits
+            });
+            if overlarge.len() == before {
+                break;
             }
         }
 
-        // Redistribute space fairly among overlarge columns.
-        for (&col, rcol) in self.grid.cols.iter().zip(&mut self.rcols) {
-            if col == Sizing::Auto && *rcol > fair {
-                *rcol = fair;
-            }
+        // Only the overlarge columns need their minimum content width.
+        let all_frac_cols = self.fractional_columns();
+        let mut mins = Vec::with_capacity(overlarge.len());
+        for &x in &overlarge {
+            let min = match grid.col_bounds[x] {
+                Some(bounds) => self.resolve_column(bounds.min),
+                None => {
+                    self.measure_auto_column(engine, x, Abs::zero(), &all_frac_cols)?
+                }
+            };
+            mins.push(min.min(self.rcols[x]));
+        }
+
+        let min_total: Abs = mins.iter().copied().sum();
+        let flex_total: Abs = overlarge
+            .iter()
+            .zip(&mins)
+            .map(|(&x, &min)| self.rcols[x] - min)
+            .sum();
+
+        // If even the minimum widths don't fit, the columns will overflow.
+        let remaining = (redistribute - min_total).max(Abs::zero());
+        let ratio = if flex_total > Abs::zero() { remaining / flex_total } else { 0.0 };
+        for (&x, min) in overlarge.iter().zip(mins) {
+            let rcol = &mut self.rcols[x];
+            *rcol = min + (*rcol - min) * ratio.min(1.0);
         }
+
+        Ok(())
     }
 
     /// Layout a row with automatic height. Such a row may break across multiple
@@ -1326,7 +1441,7 @@ impl<'a> GridLayouter<'a> {
 
             let locator = self.cell_locator(parent, disambiguator);
             let frames = layout_cell(
-                cell,
+                &self.padded_cell(cell, parent),
                 engine,
                 locator,
                 self.styles,
@@ -1499,7 +1614,7 @@ impl<'a> GridLayouter<'a> {
                     }
                     let locator = self.cell_locator(Axes::new(x, y), disambiguator);
                     let frame = layout_cell(
-                        cell,
+                        &self.padded_cell(cell, Axes::new(x, y)),
                         engine,
                         locator,
                         self.styles,
@@ -1555,7 +1670,7 @@ impl<'a> GridLayouter<'a> {
                     // Push the layouted frames into the individual output frames.
                     let locator = self.cell_locator(Axes::new(x, y), disambiguator);
                     let fragment = layout_cell(
-                        cell,
+                        &self.padded_cell(cell, Axes::new(x, y)),
                         engine,
                         locator,
                         self.styles,
@@ -1662,6 +1777,13 @@ impl<'a> GridLayouter<'a> {
             }
         }
 
+        // The last row in the region continues in the next region if it was
+        // not fully laid out yet.
+        let split_row = match self.current.lrows.last() {
+            Some(Row::Frame(_, y, false)) => Some(*y),
+            _ => None,
+        };
+
         // Determine the height of existing rows in the region.
         let mut used = Abs::zero();
         let mut fr = Fr::zero();
@@ -1802,6 +1924,7 @@ impl<'a> GridLayouter<'a> {
                 last_repeated_header_end: self.current.last_repeated_header_end,
                 repeated_height: repeated_header_row_height,
             },
+            split_row,
         );
 
         if !last {
@@ -1840,9 +1963,11 @@ impl<'a> GridLayouter<'a> {
         output: Frame,
         resolved_rows: Vec<RowPiece>,
         header_row_info: FinishedHeaderRowInfo,
+        split_row: Option<usize>,
     ) {
         self.finished.push(output);
         self.rrows.push(resolved_rows);
+        self.split_rows.push(split_row);
         self.regions.next();
         self.current.initial = self.regions.size;
 
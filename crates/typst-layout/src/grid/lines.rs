@@ -370,6 +370,12 @@ pub fn vline_stroke_at_row(
 /// indicates whether this is the last region of the table. If not and this is
 /// a line at the bottom border, the bottom border's line gains priority.
 ///
+/// The `at_split` parameter indicates whether the hline is drawn where a row
+/// was split across regions, that is, at the bottom of the region before the
+/// split or at the top of the region after it. In that case, neither the top
+/// nor the bottom border's strokes are used, and the split cells' own bottom
+/// and top strokes are drawn instead.
+///
 /// If the one (when at the border) or two (otherwise) cells above and below
 /// the hline have bottom and top stroke overrides, respectively, then the
 /// cells' stroke overrides are folded together with the hline's stroke (with
@@ -398,6 +404,7 @@ pub fn hline_stroke_at_column(
     local_top_y: Option<usize>,
     header_end_above: Option<usize>,
     in_last_region: bool,
+    at_split: bool,
     y: usize,
     x: usize,
     stroke: Option<Option<Arc<Stroke<Abs>>>>,
@@ -443,7 +450,7 @@ pub fn hline_stroke_at_column(
     // When the hline is at the top of the region and this isn't the first
     // region, fold with the top stroke of the topmost cell at this column,
     // that is, the top border.
-    let use_top_border_stroke = local_top_y.is_none() && y != 0;
+    let use_top_border_stroke = local_top_y.is_none() && y != 0 && !at_split;
     let (top_cell_stroke, top_cell_prioritized) = local_top_y
         .or(use_top_border_stroke.then_some(0))
         .and_then(|top_y| {
@@ -465,6 +472,7 @@ pub fn hline_stroke_at_column(
     // region, we have the last index, and (as a failsafe) we don't have the
     // last row of cells above us.
     let use_bottom_border_stroke = !in_last_region
+        && !at_split
         && local_top_y.is_none_or(|top_y| top_y + 1 != grid.rows.len())
         && y == grid.rows.len();
     let bottom_y =
@@ -575,6 +583,7 @@ mod test {
             stroke: Sides::splat(Some(Arc::new(Stroke::default()))),
             stroke_overridden: Sides::splat(false),
             breakable: true,
+            continuation: None,
         }
     }
 
@@ -587,6 +596,7 @@ mod test {
             stroke: Sides::splat(Some(Arc::new(Stroke::default()))),
             stroke_overridden: Sides::splat(false),
             breakable: true,
+            continuation: None,
         }
     }
 
@@ -1267,6 +1277,7 @@ mod test {
                         y.checked_sub(1),
                         None,
                         true,
+                        false,
                         y,
                         x,
                         stroke,
@@ -1461,6 +1472,7 @@ mod test {
                         y.checked_sub(1),
                         None,
                         true,
+                        false,
                         y,
                         x,
                         stroke
@@ -1507,6 +1519,7 @@ mod test {
                     if y == 4 { Some(2) } else { y.checked_sub(1) },
                     None,
                     true,
+                    false,
                     y,
                     x,
                     stroke
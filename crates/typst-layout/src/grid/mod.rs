@@ -5,13 +5,22 @@ mod rowspans;
 
 pub use self::layouter::GridLayouter;
 
-use typst_library::diag::SourceResult;
+use rustc_hash::FxHashMap;
+use typst_library::diag::{SourceResult, warning};
 use typst_library::engine::Engine;
-use typst_library::foundations::{Content, NativeElement, Packed, StyleChain};
+use typst_library::foundations::{
+    Content, NativeElement, Packed, SequenceElem, StyleChain, StyledElem,
+};
 use typst_library::introspection::{Location, Locator, SplitLocator, Tag, TagFlags};
-use typst_library::layout::grid::resolve::Cell;
-use typst_library::layout::{Fragment, FrameItem, GridCell, GridElem, Point, Regions};
-use typst_library::model::{TableCell, TableElem};
+use typst_library::layout::grid::resolve::{Cell, CellGrid};
+use typst_library::layout::{
+    Abs, Axes, BlockElem, Celled, Em, Fragment, Frame, FrameItem, GridCell, GridElem,
+    HElem, Point, Region, Regions, Size, VElem,
+};
+use typst_library::model::{
+    EnumElem, EnumItem, HeadingElem, ListElem, ListItem, ParElem, ParbreakElem,
+    TableCell, TableElem, TermItem, TermsElem,
+};
 
 use self::layouter::RowPiece;
 use self::lines::{
@@ -49,11 +58,25 @@ pub fn layout_cell(
         tags = Some(generate_tags(grid_cell, &mut locator, engine));
     }
 
-    let locator = locator.next(&cell.body.span());
-    let fragment = crate::layout_fragment(engine, &cell.body, locator, styles, regions)?;
+    let mut frames = match &cell.continuation {
+        Some(continuation) if !regions.backlog.is_empty() || regions.last.is_some() => {
+            layout_continued(
+                engine,
+                &cell.body,
+                continuation,
+                &mut locator,
+                styles,
+                regions,
+            )?
+        }
+        _ => {
+            let locator = locator.next(&cell.body.span());
+            crate::layout_fragment(engine, &cell.body, locator, styles, regions)?
+                .into_frames()
+        }
+    };
 
     // Manually insert tags.
-    let mut frames = fragment.into_frames();
     if let Some((elem, loc, key)) = tags
         && let Some((first, remainder)) = frames.split_first_mut()
     {
@@ -69,6 +92,70 @@ pub fn layout_cell(
     Ok(Fragment::frames(frames))
 }
 
+/// Layout content which may break across regions, repeating its continuation
+/// content at the top of each region after the first.
+///
+/// The body is laid out into regions shrunk by the continuation's height, so
+/// that the combined frames still match the heights of the given regions.
+pub(crate) fn layout_continued(
+    engine: &mut Engine,
+    body: &Content,
+    continuation: &Content,
+    locator: &mut SplitLocator,
+    styles: StyleChain,
+    regions: Regions,
+) -> SourceResult<Vec<Frame>> {
+    let body_locator = locator.next(&body.span());
+    let pod =
+        Region::new(Size::new(regions.size.x, regions.base().y), Axes::splat(false));
+    let first = crate::layout_frame(
+        engine,
+        continuation,
+        locator.next(&continuation.span()),
+        styles,
+        pod,
+    )?;
+    let height = first.height();
+
+    let backlog: Vec<Abs> = regions
+        .backlog
+        .iter()
+        .map(|&region| (region - height).max(Abs::zero()))
+        .collect();
+    let mut body_regions = regions;
+    body_regions.backlog = &backlog;
+    body_regions.last = regions.last.map(|last| (last - height).max(Abs::zero()));
+
+    let mut frames =
+        crate::layout_fragment(engine, body, body_locator, styles, body_regions)?
+            .into_frames();
+
+    let mut repeated = Some(first);
+    for frame in frames.iter_mut().skip(1) {
+        // Each repetition is laid out separately, so that it is introspected
+        // with a distinct location.
+        let repeated = match repeated.take() {
+            Some(repeated) => repeated,
+            None => crate::layout_frame(
+                engine,
+                continuation,
+                locator.next(&continuation.span()),
+                styles,
+                pod,
+            )?,
+        };
+
+        let body = std::mem::take(frame);
+        let size = Size::new(body.width(), body.height() + height);
+        let mut output = Frame::soft(size);
+        output.push_frame(Point::zero(), repeated);
+        output.push_frame(Point::with_y(height), body);
+        *frame = output;
+    }
+
+    Ok(frames)
+}
+
 fn generate_tags<T: NativeElement>(
     mut cell: Packed<T>,
     locator: &mut SplitLocator,
@@ -89,8 +176,12 @@ pub fn layout_grid(
     styles: StyleChain,
     regions: Regions,
 ) -> SourceResult<Fragment> {
-    let grid = elem.grid.as_ref().unwrap();
-    GridLayouter::new(grid, regions, locator, styles, elem.span()).layout(engine)
+    let grid: &CellGrid = elem.grid.as_ref().unwrap();
+    let align_char = elem.align_char.get_ref(styles);
+    let pads = align_on_chars(grid, align_char, engine, locator.relayout(), styles)?;
+    GridLayouter::new(grid, regions, locator, styles, elem.span())
+        .with_char_pads(pads)
+        .layout(engine)
 }
 
 /// Layout the table.
@@ -102,6 +193,169 @@ pub fn layout_table(
     styles: StyleChain,
     regions: Regions,
 ) -> SourceResult<Fragment> {
-    let grid = elem.grid.as_ref().unwrap();
-    GridLayouter::new(grid, regions, locator, styles, elem.span()).layout(engine)
+    let grid: &CellGrid = elem.grid.as_ref().unwrap();
+    let align_char = elem.align_char.get_ref(styles);
+    let pads = align_on_chars(grid, align_char, engine, locator.relayout(), styles)?;
+    GridLayouter::new(grid, regions, locator, styles, elem.span())
+        .with_char_pads(pads)
+        .layout(engine)
+}
+
+/// Determines by how much to pad the bodies of the cells which are aligned on
+/// a character before and after, such that the character lines up across each
+/// column. The paddings are keyed by the cells' positions, including gutter.
+fn align_on_chars(
+    grid: &CellGrid,
+    align_char: &Celled<Option<char>>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+) -> SourceResult<FxHashMap<Axes<usize>, (Abs, Abs)>> {
+    let mut pads = FxHashMap::default();
+    let chars = grid.resolve_align_chars(align_char, engine, styles)?;
+    if chars.iter().all(Option::is_none) {
+        return Ok(pads);
+    }
+
+    // Measure the laid-out cells on either side of the character.
+    let mut locator = locator.split();
+    let cols = grid.non_gutter_column_count();
+    let mut widths = vec![None; chars.len()];
+    let mut max = vec![(Abs::zero(), Abs::zero()); cols];
+    for (i, (entry, c)) in grid.entries.iter().zip(chars).enumerate() {
+        let (Some(cell), Some(c)) = (entry.as_cell(), c) else { continue };
+        if !cell_body(&cell.body).is_some_and(is_inline) {
+            engine.sink.warn(warning!(
+                cell.body.span(),
+                "cannot align cell on character `{c}`";
+                hint: "only cells with inline content, like text, can be aligned on a character";
+                hint: "lists, paragraphs, and blocks are laid out as is"
+            ));
+            continue;
+        }
+        let Some((before, after)) =
+            measure_around_char(engine, &cell.body, c, locator.next(&i), styles)?
+        else {
+            continue;
+        };
+        let (max_before, max_after) = &mut max[i % cols];
+        max_before.set_max(before);
+        max_after.set_max(after);
+        widths[i] = Some((before, after));
+    }
+
+    let step = if grid.has_gutter { 2 } else { 1 };
+    for (i, width) in widths.into_iter().enumerate() {
+        let Some((before, after)) = width else { continue };
+        let (max_before, max_after) = max[i % cols];
+        let pos = Axes::new(i % cols * step, i / cols * step);
+        pads.insert(pos, (max_before - before, max_after - after));
+    }
+
+    Ok(pads)
+}
+
+/// Lays out a table or grid cell and measures its width before and after the
+/// first occurrence of the given character, including the cell's inset. If
+/// the character does not occur, the whole width counts as before it.
+///
+/// Returns `None` if the cell contains no text.
+fn measure_around_char(
+    engine: &mut Engine,
+    cell: &Content,
+    c: char,
+    locator: Locator,
+    styles: StyleChain,
+) -> SourceResult<Option<(Abs, Abs)>> {
+    let pod = Region::new(Size::splat(Abs::inf()), Axes::splat(false));
+    let frame = crate::layout_frame(engine, cell, locator, styles, pod)?;
+
+    let mut has_text = false;
+    let offset = find_char(&frame, Point::zero(), c, &mut has_text);
+    if !has_text {
+        return Ok(None);
+    }
+
+    let before = offset.unwrap_or(frame.width()).min(frame.width());
+    Ok(Some((before, frame.width() - before)))
+}
+
+/// Finds the horizontal offset of the first occurrence of the given character
+/// among the text in the frame and records whether the frame contains any text.
+fn find_char(frame: &Frame, origin: Point, c: char, has_text: &mut bool) -> Option<Abs> {
+    for (pos, item) in frame.items() {
+        let pos = origin + *pos;
+        match item {
+            FrameItem::Group(group) => {
+                if let Some(x) = find_char(&group.frame, pos, c, has_text) {
+                    return Some(x);
+                }
+            }
+            FrameItem::Text(text) => {
+                *has_text = true;
+                let Some(index) = text.text.find(c) else { continue };
+                let advance: Em = text
+                    .glyphs
+                    .iter()
+                    .filter(|glyph| glyph.range().start < index)
+                    .map(|glyph| glyph.x_advance)
+                    .sum();
+                return Some(pos.x + advance.at(text.size));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the body of a table or grid cell.
+fn cell_body(cell: &Content) -> Option<&Content> {
+    if let Some(cell) = cell.to_packed::<TableCell>() {
+        Some(&cell.body)
+    } else if let Some(cell) = cell.to_packed::<GridCell>() {
+        Some(&cell.body)
+    } else {
+        None
+    }
+}
+
+/// Whether the content is laid out inline, i.e. whether it doesn't contain
+/// any paragraph breaks or block-level elements.
+fn is_inline(content: &Content) -> bool {
+    if let Some(sequence) = content.to_packed::<SequenceElem>() {
+        sequence.children.iter().all(is_inline)
+    } else if let Some(styled) = content.to_packed::<StyledElem>() {
+        is_inline(&styled.child)
+    } else {
+        !(content.is::<ParbreakElem>()
+            || content.is::<ParElem>()
+            || content.is::<BlockElem>()
+            || content.is::<VElem>()
+            || content.is::<ListElem>()
+            || content.is::<ListItem>()
+            || content.is::<EnumElem>()
+            || content.is::<EnumItem>()
+            || content.is::<TermsElem>()
+            || content.is::<TermItem>()
+            || content.is::<HeadingElem>())
+    }
+}
+
+/// Adds horizontal spacing before and after the body of a table or grid cell.
+///
+/// This must only be used for inline bodies, see [`is_inline`].
+fn pad_cell_body(body: &mut Content, before: Abs, after: Abs) {
+    let pad = |inner: &Content| {
+        Content::sequence([
+            HElem::new(before.into()).pack(),
+            inner.clone(),
+            HElem::new(after.into()).pack(),
+        ])
+    };
+
+    if let Some(cell) = body.to_packed_mut::<TableCell>() {
+        cell.body = pad(&cell.body);
+    } else if let Some(cell) = body.to_packed_mut::<GridCell>() {
+        cell.body = pad(&cell.body);
+    }
 }
@@ -229,6 +229,7 @@ impl<'a> GridLayouter<'a> {
                 Frame::soft(Axes::splat(Abs::zero())),
                 vec![],
                 Default::default(),
+                None,
             );
 
             // TODO(layout model): re-calculate heights of headers and footers
@@ -488,6 +489,7 @@ impl<'a> GridLayouter<'a> {
                 Frame::soft(Axes::splat(Abs::zero())),
                 vec![],
                 Default::default(),
+                None,
             );
             skipped_region = true;
         }
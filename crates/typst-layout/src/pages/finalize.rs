@@ -1,9 +1,14 @@
 use typst_library::diag::SourceResult;
 use typst_library::engine::Engine;
+use typst_library::foundations::Smart;
 use typst_library::introspection::{ManualPageCounter, Tag};
-use typst_library::layout::{Frame, FrameItem, Page, Point};
+use typst_library::layout::{Abs, Frame, FrameItem, Page, Point, Size};
+use typst_library::visualize::Geometry;
+use typst_syntax::Span;
+use typst_utils::Numeric;
 
 use super::LayoutedPage;
+use super::marks::{SLUG, layout_printer_mark};
 
 /// Piece together the inner page frame and the marginals. We can only do this
 /// at the very end because inside/outside margins require knowledge of the
@@ -21,7 +26,9 @@ pub fn finalize(
         footer,
         background,
         foreground,
-        fill,
+        mut fill,
+        bleed,
+        marks,
         numbering,
         supplement,
     }: LayoutedPage,
@@ -33,8 +40,25 @@ pub fn finalize(
         std::mem::swap(&mut margin.left, &mut margin.right);
     }
 
+    // The trimmed page is surrounded by the bleed and, if there are printer
+    // marks, by the slug area holding them.
+    let slug = if marks.is_empty() { Abs::zero() } else { Abs::mm(SLUG) };
+    let trim = Point::splat(bleed + slug);
+    let trim_size = inner.size() + margin.sum_by_axis();
+
     // Create a frame for the full page.
-    let mut frame = Frame::hard(inner.size() + margin.sum_by_axis());
+    let mut frame = Frame::hard(trim_size + Size::splat(2.0 * trim.x));
+
+    // The page fill must not extend into the slug area, so we draw it
+    // ourselves in that case.
+    if !slug.is_zero()
+        && let Smart::Custom(Some(paint)) = &fill
+    {
+        let shape =
+            Geometry::Rect(trim_size + Size::splat(2.0 * bleed)).filled(paint.clone());
+        frame.push(Point::splat(slug), FrameItem::Shape(shape, Span::detached()));
+        fill = Smart::Auto;
+    }
 
     // Add tags.
     for tag in tags.drain(..) {
@@ -45,22 +69,27 @@ pub fn finalize(
     // important as it affects the relative ordering of introspectable elements
     // and thus how counters resolve.
     if let Some(background) = background {
-        frame.push_frame(Point::zero(), background);
+        frame.push_frame(Point::splat(slug), background);
     }
     if let Some(header) = header {
-        frame.push_frame(Point::with_x(margin.left), header);
+        frame.push_frame(trim + Point::with_x(margin.left), header);
     }
 
     // Add the inner contents.
-    frame.push_frame(Point::new(margin.left, margin.top), inner);
+    frame.push_frame(trim + Point::new(margin.left, margin.top), inner);
 
     // Add the "after" marginals.
     if let Some(footer) = footer {
-        let y = frame.height() - footer.height();
-        frame.push_frame(Point::new(margin.left, y), footer);
+        let y = trim_size.y - footer.height();
+        frame.push_frame(trim + Point::new(margin.left, y), footer);
     }
     if let Some(foreground) = foreground {
-        frame.push_frame(Point::zero(), foreground);
+        frame.push_frame(trim, foreground);
+    }
+
+    // Add the printer marks.
+    for mark in marks {
+        layout_printer_mark(&mut frame, mark, bleed, slug);
     }
 
     // Apply counter updates from within the page to the manual page counter.
@@ -70,5 +99,13 @@ pub fn finalize(
     let number = counter.logical();
     counter.step();
 
-    Ok(Page { frame, fill, numbering, supplement, number })
+    Ok(Page {
+        frame,
+        fill,
+        numbering,
+        supplement,
+        number,
+        bleed,
+        slug,
+    })
 }
@@ -0,0 +1,122 @@
+use typst_library::layout::{Abs, Frame, FrameItem, Point, PrinterMark, Size};
+use typst_library::visualize::{Cmyk, Color, Curve, FixedStroke, Geometry};
+use typst_syntax::Span;
+
+/// The width of the area around the bleed which holds the printer marks (in
+/// millimeters).
+pub const SLUG: f64 = 10.0;
+
+/// The distance between the bleed and the printer marks (in millimeters).
+const OFFSET: f64 = 2.0;
+
+/// The length of a crop mark (in millimeters).
+const CROP_LENGTH: f64 = 6.0;
+
+/// The radius of a registration target (in millimeters).
+const TARGET_RADIUS: f64 = 2.0;
+
+/// The size of a patch in the color bar (in millimeters).
+const PATCH_SIZE: f64 = 5.0;
+
+/// The patches of the color bar: the process inks, their overprints, and
+/// tints of black.
+const PATCHES: [(f32, f32, f32, f32); 10] = [
+    (1.0, 0.0, 0.0, 0.0),
+    (0.0, 1.0, 0.0, 0.0),
+    (0.0, 0.0, 1.0, 0.0),
+    (0.0, 0.0, 0.0, 1.0),
+    (0.0, 1.0, 1.0, 0.0),
+    (1.0, 0.0, 1.0, 0.0),
+    (1.0, 1.0, 0.0, 0.0),
+    (0.0, 0.0, 0.0, 0.75),
+    (0.0, 0.0, 0.0, 0.5),
+    (0.0, 0.0, 0.0, 0.25),
+];
+
+/// Draw a printer mark into the slug area of a page frame.
+pub fn layout_printer_mark(frame: &mut Frame, mark: PrinterMark, bleed: Abs, slug: Abs) {
+    match mark {
+        PrinterMark::Crop => layout_crop_marks(frame, bleed, slug),
+        PrinterMark::Registration => layout_registration_targets(frame, slug),
+        PrinterMark::ColorBar => layout_color_bar(frame, bleed, slug),
+    }
+}
+
+/// Draw lines which continue the trimmed page's edges at each corner.
+fn layout_crop_marks(frame: &mut Frame, bleed: Abs, slug: Abs) {
+    let size = frame.size();
+    let trim = bleed + slug;
+    let offset = Abs::mm(OFFSET);
+    let length = Abs::mm(CROP_LENGTH);
+
+    // The position of the trim edge and the start of the mark along an axis.
+    let trim_at = |far: bool, extent: Abs| if far { extent - trim } else { trim };
+    let mark_at = |far: bool, extent: Abs| {
+        if far { extent - slug + offset } else { slug - offset - length }
+    };
+
+    for (right, bottom) in [(false, false), (true, false), (false, true), (true, true)] {
+        let x = trim_at(right, size.x);
+        let y = trim_at(bottom, size.y);
+        push_line(frame, Point::new(mark_at(right, size.x), y), Point::with_x(length));
+        push_line(frame, Point::new(x, mark_at(bottom, size.y)), Point::with_y(length));
+    }
+}
+
+/// Draw a crosshair target at the center of each edge.
+fn layout_registration_targets(frame: &mut Frame, slug: Abs) {
+    let size = frame.size();
+    let radius = Abs::mm(TARGET_RADIUS);
+    let inset = (slug - Abs::mm(OFFSET)) / 2.0;
+    let centers = [
+        Point::new(size.x / 2.0, inset),
+        Point::new(size.x / 2.0, size.y - inset),
+        Point::new(inset, size.y / 2.0),
+        Point::new(size.x - inset, size.y / 2.0),
+    ];
+
+    let arm = radius * 1.5;
+    for center in centers {
+        let circle = Curve::ellipse(Size::splat(2.0 * radius));
+        frame.push(
+            center - Point::splat(radius),
+            FrameItem::Shape(
+                Geometry::Curve(circle).stroked(registration_stroke()),
+                Span::detached(),
+            ),
+        );
+        push_line(frame, center - Point::with_x(arm), Point::with_x(2.0 * arm));
+        push_line(frame, center - Point::with_y(arm), Point::with_y(2.0 * arm));
+    }
+}
+
+/// Draw a row of ink patches into the top slug area, as far as it fits into
+/// the left half of the page.
+fn layout_color_bar(frame: &mut Frame, bleed: Abs, slug: Abs) {
+    let offset = Abs::mm(OFFSET);
+    let size = Abs::mm(PATCH_SIZE);
+    let end = frame.width() / 2.0 - Abs::mm(TARGET_RADIUS) * 1.5 - offset;
+    let mut pos = Point::new(bleed + slug + offset, (slug - offset - size) / 2.0);
+    for (c, m, y, k) in PATCHES {
+        if pos.x + size > end {
+            break;
+        }
+
+        let color = Color::Cmyk(Cmyk { c, m, y, k });
+        let shape = Geometry::Rect(Size::splat(size)).filled(color);
+        frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+        pos.x += size;
+    }
+}
+
+/// Push a hairline in registration color.
+fn push_line(frame: &mut Frame, pos: Point, delta: Point) {
+    let shape = Geometry::Line(delta).stroked(registration_stroke());
+    frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+}
+
+/// The stroke for marks which should appear on every printing plate.
+fn registration_stroke() -> FixedStroke {
+    let registration = Cmyk { c: 1.0, m: 1.0, y: 1.0, k: 1.0 };
+    FixedStroke::from_pair(Color::Cmyk(registration), Abs::pt(0.25))
+}
@@ -2,6 +2,7 @@
 
 mod collect;
 mod finalize;
+mod marks;
 mod run;
 
 use std::num::NonZeroUsize;
@@ -173,11 +174,14 @@ fn introspect_pages(pages: &[Page]) -> Introspector {
     for (i, page) in pages.iter().enumerate() {
         builder.page_numberings.push(page.numbering.clone());
         builder.page_supplements.push(page.supplement.clone());
+        // Positions are relative to the trimmed page, so that they don't
+        // depend on the bleed and printer marks.
+        let trim = page.trim_box().min;
         builder.discover_in_frame(
             &mut elems,
             &page.frame,
             NonZeroUsize::new(1 + i).unwrap(),
-            Transform::identity(),
+            Transform::translate(-trim.x, -trim.y),
         );
     }
 
@@ -10,8 +10,8 @@ use typst_library::introspection::{
 };
 use typst_library::layout::{
     Abs, AlignElem, Alignment, Axes, Binding, ColumnsElem, Dir, Frame, HAlignment,
-    Length, OuterVAlignment, PageElem, Paper, Region, Regions, Rel, Sides, Size,
-    VAlignment,
+    Length, OuterVAlignment, PageElem, Paper, PrinterMark, Region, Regions, Rel, Sides,
+    Size, VAlignment,
 };
 use typst_library::model::Numbering;
 use typst_library::pdf::ArtifactKind;
@@ -36,6 +36,8 @@ pub struct LayoutedPage {
     pub background: Option<Frame>,
     pub foreground: Option<Frame>,
     pub fill: Smart<Option<Paint>>,
+    pub bleed: Abs,
+    pub marks: Vec<PrinterMark>,
     pub numbering: Option<Numbering>,
     pub supplement: Content,
 }
@@ -125,6 +127,8 @@ fn layout_page_run_impl(
         .relative_to(size);
 
     let fill = styles.get_cloned(PageElem::fill);
+    let bleed = styles.resolve(PageElem::bleed);
+    let marks = styles.get_cloned(PageElem::marks);
     let foreground = styles.get_ref(PageElem::foreground);
     let background = styles.get_ref(PageElem::background);
     let header_ascent = styles.resolve(PageElem::header_ascent).relative_to(margin.top);
@@ -212,15 +216,18 @@ fn layout_page_run_impl(
         let header_size = Size::new(inner.width(), margin.top - header_ascent);
         let footer_size = Size::new(inner.width(), margin.bottom - footer_descent);
         let full_size = inner.size() + margin.sum_by_axis();
+        let bleed_size = full_size + Size::splat(2.0 * bleed);
         let mid = HAlignment::Center + VAlignment::Horizon;
         layouted.push(LayoutedPage {
             inner,
             fill: fill.clone(),
+            bleed,
+            marks: marks.clone(),
             numbering: numbering.clone(),
             supplement: supplement.clone(),
             header: layout_marginal(&header, header_size, Alignment::BOTTOM)?,
             footer: layout_marginal(&footer, footer_size, Alignment::TOP)?,
-            background: layout_marginal(&background, full_size, mid)?,
+            background: layout_marginal(&background, bleed_size, mid)?,
             foreground: layout_marginal(foreground, full_size, mid)?,
             margin,
             binding,
@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use comemo::Track;
 use ecow::{EcoVec, eco_format};
 use smallvec::smallvec;
@@ -8,21 +10,24 @@ use typst_library::foundations::{
 };
 use typst_library::introspection::{Counter, Locator, LocatorLink};
 use typst_library::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, ColumnsElem, Em,
-    FixedAlignment, GridCell, GridChild, GridElem, GridItem, HAlignment, HElem, HideElem,
-    InlineElem, LayoutElem, Length, MoveElem, OuterVAlignment, PadElem, PageElem,
-    PlaceElem, PlacementScope, Region, Rel, RepeatElem, RotateElem, ScaleElem, Sides,
-    Size, Sizing, SkewElem, Spacing, StackChild, StackElem, TrackSizings, VElem,
+    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, BoxElem, ColumnSizings,
+    ColumnsElem, Em, FixedAlignment, FlushElem, GridCell, GridChild, GridElem, GridItem,
+    HAlignment, HElem, HideElem, InlineElem, LayoutElem, Length, Margin, MoveElem,
+    OuterVAlignment, PadElem, PageElem, PagebreakElem, PlaceElem, PlacementScope, Region,
+    Rel, RepeatElem, RotateElem, ScaleElem, Sides, Size, Sizing, SkewElem, Spacing,
+    StackChild, StackElem, TrackSizings, VAlignment, VElem,
 };
 use typst_library::math::EquationElem;
 use typst_library::model::{
     Attribution, BibliographyElem, CiteElem, CiteGroup, CslIndentElem, CslLightElem,
     Destination, DirectLinkElem, EmphElem, EnumElem, FigureCaption, FigureElem,
-    FootnoteElem, FootnoteEntry, HeadingElem, LinkElem, LinkMarker, ListElem,
+    FootnoteElem, FootnoteEntry, HeadingElem, LinkElem, LinkMarker, LinkTarget, ListElem,
     OutlineElem, OutlineEntry, ParElem, ParbreakElem, QuoteElem, RefElem, StrongElem,
-    TableCell, TableElem, TermsElem, TitleElem, Works,
+    TableCell, TableElem, TableNote, TermsElem, TitleElem, Url, Works,
+};
+use typst_library::pdf::{
+    ArtifactElem, ArtifactKind, AttachElem, IncludeElem, PdfMarkerTag,
 };
-use typst_library::pdf::{ArtifactElem, ArtifactKind, AttachElem, PdfMarkerTag};
 use typst_library::text::{
     DecoLine, Decoration, HighlightElem, ItalicToggle, LinebreakElem, LocalName,
     OverlineElem, RawElem, RawLine, ScriptKind, ShiftSettings, Smallcaps, SmallcapsElem,
@@ -30,8 +35,8 @@ use typst_library::text::{
     TextSize, UnderlineElem, WeightDelta,
 };
 use typst_library::visualize::{
-    CircleElem, CurveElem, EllipseElem, ImageElem, LineElem, PathElem, PolygonElem,
-    RectElem, SquareElem, Stroke,
+    CircleElem, CurveElem, EllipseElem, ImageElem, ImageFormat, LineElem, PathElem,
+    PolygonElem, RectElem, SquareElem, Stroke, VectorFormat,
 };
 use typst_utils::{Get, Numeric};
 
@@ -64,6 +69,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Paged, CSL_INDENT_RULE);
     rules.register(Paged, TABLE_RULE);
     rules.register(Paged, TABLE_CELL_RULE);
+    rules.register(Paged, TABLE_NOTE_RULE);
 
     // Text.
     rules.register(Paged, SUB_RULE);
@@ -109,6 +115,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Paged, ATTACH_RULE);
     rules.register(Paged, ARTIFACT_RULE);
     rules.register(Paged, PDF_MARKER_TAG_RULE);
+    rules.register(Paged, PDF_INCLUDE_RULE);
 }
 
 const STRONG_RULE: ShowFn<StrongElem> = |elem, _, styles| {
@@ -301,32 +308,20 @@ const HEADING_RULE: ShowFn<HeadingElem> = |elem, engine, styles| {
 
 const FIGURE_RULE: ShowFn<FigureElem> = |elem, _, styles| {
     let span = elem.span();
-    let mut realized = elem.body.clone();
-
-    // Build the caption, if any.
-    if let Some(caption) = elem.caption.get_cloned(styles) {
-        let (first, second) = match caption.position.get(styles) {
-            OuterVAlignment::Top => (caption.pack(), realized),
-            OuterVAlignment::Bottom => (realized, caption.pack()),
-        };
-        realized = Content::sequence(vec![
-            first,
-            VElem::new(elem.gap.get(styles).into())
-                .with_weak(true)
-                .pack()
-                .spanned(span),
-            second,
-        ]);
-    }
-
-    // Ensure that the body is considered a paragraph.
-    realized += ParbreakElem::shared().clone().spanned(span);
 
-    // Wrap the contents in a block.
-    realized = BlockElem::new()
-        .with_body(Some(BlockBody::Content(realized)))
-        .pack()
-        .spanned(span);
+    // Wrap the contents in a block. If the caption has a continuation, the
+    // figure is laid out specially to repeat it on subsequent pages.
+    let mut realized = if crate::figure::has_continued_caption(elem, styles) {
+        BlockElem::multi_layouter(elem.clone(), crate::figure::layout_figure)
+            .pack()
+            .spanned(span)
+    } else {
+        let body = crate::figure::realize_figure(elem, styles);
+        BlockElem::new()
+            .with_body(Some(BlockBody::Content(body)))
+            .pack()
+            .spanned(span)
+    };
 
     // Wrap in a float.
     if let Some(align) = elem.placement.get(styles) {
@@ -495,7 +490,7 @@ const BIBLIOGRAPHY_RULE: ShowFn<BibliographyElem> = |elem, engine, styles| {
         }
 
         let grid = GridElem::new(cells)
-            .with_columns(TrackSizings(smallvec![Sizing::Auto; 2]))
+            .with_columns(ColumnSizings(smallvec![Sizing::Auto.into(); 2]))
             .with_column_gutter(TrackSizings(smallvec![COLUMN_GUTTER.into()]))
             .with_row_gutter(TrackSizings(smallvec![row_gutter.into()]));
         let mut packed = Packed::new(grid).spanned(span);
@@ -535,8 +530,40 @@ const CSL_LIGHT_RULE: ShowFn<CslLightElem> =
 const CSL_INDENT_RULE: ShowFn<CslIndentElem> =
     |elem, _, _| Ok(PadElem::new(elem.body.clone()).pack());
 
-const TABLE_RULE: ShowFn<TableElem> = |elem, _, _| {
-    Ok(BlockElem::multi_layouter(elem.clone(), crate::grid::layout_table).pack())
+const TABLE_RULE: ShowFn<TableElem> = |elem, engine, styles| {
+    let mut realized =
+        BlockElem::multi_layouter(elem.clone(), crate::grid::layout_table).pack();
+
+    // List the table's notes below it.
+    let notes = elem.realize_notes(engine, styles)?;
+    if !notes.is_empty() {
+        let number_gap = Em::new(0.05);
+        let entries = notes.into_iter().map(|(num, body)| {
+            Content::sequence([
+                SuperElem::new(num).pack(),
+                HElem::new(number_gap.into()).with_weak(true).pack(),
+                body,
+                ParbreakElem::shared().clone(),
+            ])
+        });
+        realized += BlockElem::new()
+            .with_body(Some(BlockBody::Content(Content::sequence(entries))))
+            .pack()
+            .set(ParElem::leading, Em::new(0.5).into())
+            .set(TextElem::size, TextSize(Em::new(0.85).into()));
+    }
+
+    // Let the notes within the table know which table they belong to.
+    if let Some(loc) = elem.location() {
+        realized = realized.set(TableNote::table, Some(loc));
+    }
+
+    Ok(realized)
+};
+
+const TABLE_NOTE_RULE: ShowFn<TableNote> = |elem, engine, styles| {
+    let num = elem.realize(engine, styles)?;
+    Ok(HElem::hole().clone() + SuperElem::new(num).pack().spanned(elem.span()))
 };
 
 const TABLE_CELL_RULE: ShowFn<TableCell> = |elem, _, styles| {
@@ -835,3 +862,73 @@ const ATTACH_RULE: ShowFn<AttachElem> = |_, _, _| Ok(Content::empty());
 const ARTIFACT_RULE: ShowFn<ArtifactElem> = |elem, _, _| Ok(elem.body.clone());
 
 const PDF_MARKER_TAG_RULE: ShowFn<PdfMarkerTag> = |elem, _, _| Ok(elem.body.clone());
+
+const PDF_INCLUDE_RULE: ShowFn<IncludeElem> = |elem, _, styles| {
+    let pages = elem.load_pages(styles)?;
+    let mut seq = Vec::with_capacity(4 * pages.len());
+    for (i, page) in pages.iter().enumerate() {
+        let mut body = vec![];
+
+        // The title is only needed for the outline and the bookmarks.
+        if i == 0
+            && let Some(title) = elem.title.get_cloned(styles)
+        {
+            let heading = HeadingElem::new(title)
+                .with_numbering(None)
+                .pack()
+                .spanned(elem.span())
+                .set(HideElem::hidden, true);
+            body.push(PlaceElem::new(heading).pack());
+        }
+
+        // Recreate the links, which are lost when the page becomes an image.
+        for link in page.links() {
+            let Ok(url) = Url::new(link.url) else { continue };
+            let size = link.rect.size();
+            let area = BoxElem::new()
+                .with_width(Sizing::Rel(size.x.into()))
+                .with_height(Smart::Custom(size.y.into()))
+                .pack();
+            let dest = LinkTarget::Dest(Destination::Url(url));
+            body.push(
+                PlaceElem::new(LinkElem::new(dest, area).pack().spanned(elem.span()))
+                    .with_alignment(Smart::Custom(Alignment::Both(
+                        HAlignment::Left,
+                        VAlignment::Top,
+                    )))
+                    .with_dx(link.rect.min.x.into())
+                    .with_dy(link.rect.min.y.into())
+                    .pack(),
+            );
+        }
+
+        let image = ImageElem::new(elem.source.clone())
+            .with_format(Smart::Custom(ImageFormat::Vector(VectorFormat::Pdf)))
+            .with_page(NonZeroUsize::new(page.page_index() + 1).unwrap())
+            .with_width(Smart::Custom(Rel::one()))
+            .with_height(Sizing::Rel(Rel::one()))
+            .with_alt(elem.alt.get_cloned(styles));
+        body.push(image.pack().spanned(elem.span()));
+
+        // Like the `page` constructor, ensure that each included page lives
+        // on a separate page.
+        seq.push(
+            Content::sequence([
+                PagebreakElem::shared_weak().clone(),
+                FlushElem::new().pack(),
+                Content::sequence(body),
+                PagebreakElem::shared_boundary().clone(),
+            ])
+            .set(PageElem::width, Smart::Custom(Abs::pt(page.width().into()).into()))
+            .set(PageElem::height, Smart::Custom(Abs::pt(page.height().into()).into()))
+            .set(PageElem::margin, Margin::splat(Some(Smart::Custom(Rel::zero()))))
+            .set(PageElem::header, Smart::Custom(None))
+            .set(PageElem::footer, Smart::Custom(None))
+            .set(PageElem::background, None)
+            .set(PageElem::foreground, None)
+            .set(PageElem::bleed, Length::zero())
+            .set(PageElem::marks, vec![]),
+        );
+    }
+    Ok(Content::sequence(seq))
+};
@@ -4,6 +4,7 @@ use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::Arc;
 
 use comemo::Track;
+use ecow::{EcoString, eco_format};
 use smallvec::{SmallVec, smallvec};
 use typst_utils::NonZeroExt;
 
@@ -11,7 +12,7 @@ use crate::diag::{At, HintedStrResult, HintedString, SourceResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
     Array, CastInfo, Content, Context, Fold, FromValue, Func, IntoValue, Packed, Reflect,
-    Resolve, Smart, StyleChain, Synthesize, Value, cast, elem, scope,
+    Repr, Resolve, Smart, StyleChain, Synthesize, Value, cast, elem, func, scope, ty,
 };
 use crate::introspection::Tagged;
 use crate::layout::resolve::{CellGrid, grid_to_cellgrid};
@@ -48,12 +49,18 @@ use crate::visualize::{Paint, Stroke};
 ///
 /// - `{auto}`: The track will be sized to fit its contents. It will be at most
 ///   as large as the remaining space. If there is more than one `{auto}` track
-///   width, and together they claim more than the available space, the `{auto}`
-///   tracks will fairly distribute the available space among themselves.
+///   width, and together they claim more than the available space, the
+///   available space is distributed among the `{auto}` tracks based on their
+///   contents: Each column receives at least the width of its widest word and
+///   columns with longer content receive a larger share of the rest.
 ///
 /// - A fixed or relative length (e.g. `{10pt}` or `{20% - 1cm}`): The track
 ///   will be exactly of this size.
 ///
+/// - A [`minmax`] size (e.g. `{minmax(2cm, 5cm)}`): The column will be sized
+///   like an `{auto}` column, but will never be narrower than the minimum or
+///   wider than the maximum. This is only supported for columns.
+///
 /// - A fractional length (e.g. `{1fr}`): Once all other tracks have been sized,
 ///   the remaining space will be divided among the fractional tracks according
 ///   to their fractions. For example, if there are two fractional tracks, each
@@ -190,7 +197,7 @@ pub struct GridElem {
     /// column.
     ///
     /// See the [track size section](#track-size) above for more details.
-    pub columns: TrackSizings,
+    pub columns: ColumnSizings,
 
     /// The row sizes.
     ///
@@ -253,6 +260,24 @@ pub struct GridElem {
     /// In addition, you can find an example at the [`table.align`] parameter.
     pub align: Celled<Smart<Alignment>>,
 
+    /// A character on which to align the cells' text, such as a decimal point.
+    ///
+    /// When set, the text of the cells in a column is padded such that the
+    /// first occurrence of the character lines up across the column. Cells
+    /// without the character are aligned as if it directly followed their
+    /// text. The padded content is then aligned as usual according to
+    /// [`align`]($grid.align). Cells spanning multiple columns are not
+    /// affected, and neither are cells with block-level content, such as
+    /// lists or multiple paragraphs.
+    ///
+    /// Just like the alignment, the character can be specified for all cells,
+    /// per column with an array, or with a function that maps a cell's
+    /// position to its character.
+    ///
+    /// In addition, you can find an example at the [`table.align-char`]
+    /// parameter.
+    pub align_char: Celled<Option<char>>,
+
     /// How to fill the cells.
     ///
     /// This can be:
@@ -458,6 +483,126 @@ cast! {
     values: Array => Self(values.into_iter().map(Value::cast).collect::<HintedStrResult<_>>()?),
 }
 
+/// Column sizing definitions, which can additionally use [`MinMax`] sizes.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct ColumnSizings(pub SmallVec<[ColumnSizing; 4]>);
+
+cast! {
+    ColumnSizings,
+    self => self.0.into_value(),
+    sizing: ColumnSizing => Self(smallvec![sizing]),
+    count: NonZeroUsize => Self(smallvec![Sizing::Auto.into(); count.get()]),
+    values: Array => Self(values.into_iter().map(Value::cast).collect::<HintedStrResult<_>>()?),
+}
+
+/// The size of a single column.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ColumnSizing {
+    /// A size that is also available for rows and gutters.
+    Track(Sizing),
+    /// A size that fits the column's contents, but stays within a minimum and
+    /// a maximum.
+    MinMax(MinMax),
+}
+
+impl ColumnSizing {
+    /// The track size of the column. Columns sized with `minmax` are
+    /// measured like `auto` columns and then kept within their bounds.
+    pub fn sizing(self) -> Sizing {
+        match self {
+            Self::Track(sizing) => sizing,
+            Self::MinMax(_) => Sizing::Auto,
+        }
+    }
+
+    /// The bounds of the column, if it is sized with `minmax`.
+    pub fn bounds(self) -> Option<MinMax> {
+        match self {
+            Self::Track(_) => None,
+            Self::MinMax(minmax) => Some(minmax),
+        }
+    }
+}
+
+impl From<Sizing> for ColumnSizing {
+    fn from(sizing: Sizing) -> Self {
+        Self::Track(sizing)
+    }
+}
+
+cast! {
+    ColumnSizing,
+    self => match self {
+        Self::Track(sizing) => sizing.into_value(),
+        Self::MinMax(minmax) => minmax.into_value(),
+    },
+    sizing: Sizing => Self::Track(sizing),
+    minmax: MinMax => Self::MinMax(minmax),
+}
+
+/// A column size between a minimum and a maximum.
+///
+/// A [grid]($grid) or [table]($table) column sized with `minmax` is sized to
+/// fit its contents like an `{auto}` column, but it will never be narrower
+/// than the minimum or wider than the maximum.
+///
+/// ```example
+/// #table(
+///   columns: (minmax(2cm, 3cm), auto),
+///   [Short], [Still short],
+///   [A much longer cell], lorem(8),
+/// )
+/// ```
+#[ty(scope, name = "minmax", title = "Min-Max Size")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MinMax {
+    /// The minimum size.
+    pub min: Rel<Length>,
+    /// The maximum size.
+    pub max: Rel<Length>,
+}
+
+#[scope]
+impl MinMax {
+    /// Creates a new column size between a minimum and a maximum.
+    ///
+    /// ```example
+    /// #grid(
+    ///   columns: (minmax(1cm, 2cm), 1fr),
+    ///   gutter: 4pt,
+    ///   [Name], [Typst],
+    ///   [Description], [A markup-based typesetting system],
+    /// )
+    /// ```
+    #[func(constructor)]
+    pub fn construct(
+        /// The minimum size of the column.
+        min: Rel<Length>,
+        /// The maximum size of the column.
+        max: Rel<Length>,
+    ) -> MinMax {
+        MinMax { min, max }
+    }
+
+    /// The minimum size of the column.
+    #[func]
+    pub fn min(&self) -> Rel<Length> {
+        self.min
+    }
+
+    /// The maximum size of the column.
+    #[func]
+    pub fn max(&self) -> Rel<Length> {
+        self.max
+    }
+}
+
+impl Repr for MinMax {
+    fn repr(&self) -> EcoString {
+        eco_format!("minmax({}, {})", self.min.repr(), self.max.repr())
+    }
+}
+
 /// Any child of a grid element.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum GridChild {
@@ -862,6 +1007,24 @@ pub struct GridCell {
     /// unbreakable, while a cell spanning at least one `{auto}`-sized row is
     /// breakable.
     pub breakable: Smart<bool>,
+
+    /// Content to repeat at the top of the cell on each page after the first
+    /// when the cell breaks across pages.
+    ///
+    /// This only has an effect on [breakable]($grid.cell.breakable) cells. The
+    /// cell's horizontal inset also applies to this content.
+    ///
+    /// ```example
+    /// #set page(height: 5em)
+    /// #grid(
+    ///   stroke: 0.5pt,
+    ///   inset: 4pt,
+    ///   grid.cell(continuation: emph[(continued)])[
+    ///     #lorem(20)
+    ///   ],
+    /// )
+    /// ```
+    pub continuation: Option<Content>,
 }
 
 cast! {
@@ -2,7 +2,7 @@ use std::num::{NonZeroU32, NonZeroUsize};
 use std::ops::{Deref, DerefMut, Range};
 use std::sync::Arc;
 
-use ecow::eco_format;
+use ecow::{EcoString, eco_format};
 use typst_library::Dir;
 use typst_library::diag::{
     At, Hint, HintedStrResult, HintedString, SourceResult, Trace, Tracepoint, bail,
@@ -10,8 +10,8 @@ use typst_library::diag::{
 use typst_library::engine::Engine;
 use typst_library::foundations::{Content, Fold, Packed, Smart, StyleChain};
 use typst_library::layout::{
-    Abs, Alignment, Axes, Celled, GridCell, GridChild, GridElem, GridItem, Length,
-    OuterHAlignment, OuterVAlignment, Rel, ResolvedCelled, Sides, Sizing,
+    Abs, Alignment, Axes, Celled, ColumnSizing, GridCell, GridChild, GridElem, GridItem,
+    Length, MinMax, OuterHAlignment, OuterVAlignment, Rel, ResolvedCelled, Sides, Sizing,
 };
 use typst_library::model::{TableCell, TableChild, TableElem, TableItem};
 use typst_library::text::TextElem;
@@ -38,7 +38,8 @@ pub fn grid_to_cellgrid(
     let fill = elem.fill.get_ref(styles);
     let stroke = elem.stroke.resolve(styles);
 
-    let tracks = Axes::new(columns.0.as_slice(), rows.0.as_slice());
+    let cols = columns.0.iter().map(|col| col.sizing()).collect::<Vec<_>>();
+    let tracks = Axes::new(cols.as_slice(), rows.0.as_slice());
     let gutter = Axes::new(column_gutter.0.as_slice(), row_gutter.0.as_slice());
     // Use trace to link back to the grid when a specific cell errors
     let tracepoint = || Tracepoint::Call(Some(eco_format!("grid")));
@@ -71,6 +72,7 @@ pub fn grid_to_cellgrid(
         styles,
         elem.span(),
     )
+    .map(|grid| grid.with_column_bounds(&columns.0))
     .trace(engine.world, tracepoint, elem.span())
 }
 
@@ -90,7 +92,8 @@ pub fn table_to_cellgrid(
     let fill = elem.fill.get_ref(styles);
     let stroke = elem.stroke.resolve(styles);
 
-    let tracks = Axes::new(columns.0.as_slice(), rows.0.as_slice());
+    let cols = columns.0.iter().map(|col| col.sizing()).collect::<Vec<_>>();
+    let tracks = Axes::new(cols.as_slice(), rows.0.as_slice());
     let gutter = Axes::new(column_gutter.0.as_slice(), row_gutter.0.as_slice());
     // Use trace to link back to the table when a specific cell errors
     let tracepoint = || Tracepoint::Call(Some(eco_format!("table")));
@@ -123,6 +126,7 @@ pub fn table_to_cellgrid(
         styles,
         elem.span(),
     )
+    .map(|grid| grid.with_column_bounds(&columns.0))
     .trace(engine.world, tracepoint, elem.span())
 }
 
@@ -265,6 +269,11 @@ impl ResolvableCell for Packed<TableCell> {
         );
         cell.breakable.set(Smart::Custom(breakable));
         cell.kind.set(kind);
+        let (cell_inset, cell_align) = (cell.inset.get(styles), cell.align.get(styles));
+        let continuation = cell
+            .continuation
+            .get_cloned(styles)
+            .map(|body| resolve_continuation(body, cell_inset, cell_align));
         Cell {
             body: self.pack(),
             fill,
@@ -273,6 +282,7 @@ impl ResolvableCell for Packed<TableCell> {
             stroke,
             stroke_overridden,
             breakable,
+            continuation,
         }
     }
 
@@ -359,6 +369,11 @@ impl ResolvableCell for Packed<GridCell> {
             }),
         );
         cell.breakable.set(Smart::Custom(breakable));
+        let (cell_inset, cell_align) = (cell.inset.get(styles), cell.align.get(styles));
+        let continuation = cell
+            .continuation
+            .get_cloned(styles)
+            .map(|body| resolve_continuation(body, cell_inset, cell_align));
         Cell {
             body: self.pack(),
             fill,
@@ -367,6 +382,7 @@ impl ResolvableCell for Packed<GridCell> {
             stroke,
             stroke_overridden,
             breakable,
+            continuation,
         }
     }
 
@@ -391,6 +407,23 @@ impl ResolvableCell for Packed<GridCell> {
     }
 }
 
+/// Applies a cell's final inset and alignment to its continuation content.
+///
+/// The bottom inset is left out, as the cell's body already starts with its
+/// top inset below the continuation.
+fn resolve_continuation(
+    continuation: Content,
+    inset: Smart<Sides<Option<Rel<Length>>>>,
+    align: Smart<Alignment>,
+) -> Content {
+    let inset = inset.unwrap_or_default().map(Option::unwrap_or_default);
+    let mut continuation = continuation.padded(Sides { bottom: Rel::zero(), ..inset });
+    if let Smart::Custom(alignment) = align {
+        continuation = continuation.aligned(alignment);
+    }
+    continuation
+}
+
 /// Represents an explicit grid line (horizontal or vertical) specified by the
 /// user.
 #[derive(Debug, Eq, PartialEq, Hash)]
@@ -568,7 +601,7 @@ pub enum ResolvableGridItem<T: ResolvableCell> {
 }
 
 /// Represents a cell in CellGrid, to be laid out by GridLayouter.
-#[derive(Debug, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash)]
 pub struct Cell {
     /// The cell's body.
     pub body: Content,
@@ -595,6 +628,10 @@ pub struct Cell {
     /// By default, a cell spanning only fixed-size rows is unbreakable, while
     /// a cell spanning at least one `auto`-sized row is breakable.
     pub breakable: bool,
+    /// Content repeated at the top of the cell in each region after the first
+    /// when the cell breaks across regions, with the cell's inset and
+    /// alignment already applied.
+    pub continuation: Option<Content>,
 }
 
 impl Cell {
@@ -608,6 +645,7 @@ impl Cell {
             stroke: Sides::splat(None),
             stroke_overridden: Sides::splat(false),
             breakable: true,
+            continuation: None,
         }
     }
 }
@@ -660,6 +698,9 @@ pub struct CellGrid {
     pub entries: Vec<Entry>,
     /// The column tracks including gutter tracks.
     pub cols: Vec<Sizing>,
+    /// The bounds of the column tracks sized with [`minmax`](MinMax), which
+    /// are otherwise sized like `auto` columns. Includes gutter tracks.
+    pub col_bounds: Vec<Option<MinMax>>,
     /// The row tracks including gutter tracks.
     pub rows: Vec<Sizing>,
     /// The vertical lines before each column, or on the end border.
@@ -744,6 +785,7 @@ impl CellGrid {
         }
 
         Self {
+            col_bounds: vec![None; cols.len()],
             cols,
             rows,
             entries,
@@ -755,6 +797,18 @@ impl CellGrid {
         }
     }
 
+    /// Keeps the columns sized with [`minmax`](MinMax) within their bounds.
+    ///
+    /// Like the column sizes, the last size is repeated for any further
+    /// columns.
+    pub fn with_column_bounds(mut self, columns: &[ColumnSizing]) -> Self {
+        let step = if self.has_gutter { 2 } else { 1 };
+        for (x, bounds) in self.col_bounds.iter_mut().step_by(step).enumerate() {
+            *bounds = columns.get(x).or(columns.last()).and_then(|col| col.bounds());
+        }
+        self
+    }
+
     /// Get the grid entry in column `x` and row `y`.
     ///
     /// Returns `None` if it's a gutter cell.
@@ -896,6 +950,63 @@ impl CellGrid {
     pub fn has_repeated_headers(&self) -> bool {
         self.headers.iter().any(|h| h.repeated)
     }
+
+    /// Resolves the character each cell should be aligned on, as configured
+    /// by the grid's `align-char` field.
+    ///
+    /// Returns one entry per cell in the order of [`Self::entries`]. Cells
+    /// without a character or spanning multiple columns yield `None`.
+    pub fn resolve_align_chars(
+        &self,
+        align_char: &Celled<Option<char>>,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Vec<Option<char>>> {
+        let mut chars = vec![None; self.entries.len()];
+        if matches!(align_char, Celled::Value(None)) {
+            return Ok(chars);
+        }
+
+        let cols = self.non_gutter_column_count();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let Some(cell) = entry.as_cell() else { continue };
+            if cell.colspan.get() == 1 {
+                chars[i] = align_char.resolve(engine, styles, i % cols, i / cols)?;
+            }
+        }
+
+        Ok(chars)
+    }
+
+    /// Splits the plain text of each cell around the character it should be
+    /// aligned on, as resolved by [`Self::resolve_align_chars`].
+    ///
+    /// Returns one entry per cell in the order of [`Self::entries`]. Cells
+    /// without a character, without text, or spanning multiple columns yield
+    /// `None`. If the character does not occur in a cell's text, the whole
+    /// text is placed before it.
+    pub fn split_on_align_chars(
+        &self,
+        align_char: &Celled<Option<char>>,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Vec<Option<(EcoString, EcoString)>>> {
+        let chars = self.resolve_align_chars(align_char, engine, styles)?;
+        Ok(self
+            .entries
+            .iter()
+            .zip(chars)
+            .map(|(entry, c)| {
+                let c = c?;
+                let text = entry.as_cell()?.body.plain_text();
+                if text.is_empty() {
+                    return None;
+                }
+                let (before, after) = text.split_at(text.find(c).unwrap_or(text.len()));
+                Some((before.into(), after.into()))
+            })
+            .collect())
+    }
 }
 
 /// Resolves and positions all cells in the grid before creating it.
@@ -76,6 +76,7 @@ pub fn define(global: &mut Scope) {
     global.define_type::<Ratio>();
     global.define_type::<Rel<Length>>();
     global.define_type::<Fr>();
+    global.define_type::<MinMax>();
     global.define_type::<Dir>();
     global.define_type::<Alignment>();
     global.define_elem::<PageElem>();
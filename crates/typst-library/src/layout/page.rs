@@ -12,8 +12,8 @@ use crate::foundations::{
 };
 use crate::introspection::Introspector;
 use crate::layout::{
-    Abs, Alignment, FlushElem, Frame, HAlignment, Length, OuterVAlignment, Ratio, Rel,
-    Sides, SpecificAlignment,
+    Abs, Alignment, FlushElem, Frame, HAlignment, Length, OuterVAlignment, Point, Ratio,
+    Rect, Rel, Sides, Size, SpecificAlignment,
 };
 use crate::model::{DocumentInfo, Numbering};
 use crate::text::LocalName;
@@ -210,6 +210,53 @@ pub struct PageElem {
     #[ghost]
     pub fill: Smart<Option<Paint>>,
 
+    /// How far the page's [fill]($page.fill) and [background]($page.background)
+    /// extend beyond its edges.
+    ///
+    /// Printers cannot reliably print up to the edge of a sheet, so documents
+    /// with content that should reach the edge are printed on a larger sheet
+    /// and trimmed afterwards. The bleed ensures that no white edge remains
+    /// if the trimming is slightly off.
+    ///
+    /// The bleed enlarges the exported page on all sides. The
+    /// [width]($page.width), [height]($page.height), and
+    /// [margins]($page.margin) still refer to the trimmed page, and so do
+    /// positions reported by [`here`]($here) and [`locate`]($locate). In PDF
+    /// export, the trimmed page and the bleed are recorded as the page's trim
+    /// box and bleed box.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 4cm,
+    ///   height: 3cm,
+    ///   bleed: 3mm,
+    ///   fill: aqua,
+    /// )
+    ///
+    /// The fill extends into the bleed.
+    /// ```
+    #[ghost]
+    pub bleed: Length,
+
+    /// Which printer marks to draw around the page.
+    ///
+    /// Printer marks are placed in an additional area outside of the
+    /// [bleed]($page.bleed), which is added to the exported page when any marks
+    /// are enabled.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 4cm,
+    ///   height: 3cm,
+    ///   bleed: 3mm,
+    ///   marks: ("crop", "registration"),
+    /// )
+    ///
+    /// Ready for the print shop.
+    /// ```
+    #[ghost]
+    pub marks: Vec<PrinterMark>,
+
     /// How to number the pages. You can refer to the Page Setup Guide for
     /// [customizing page numbers]($guides/page-setup/#page-numbers).
     ///
@@ -505,6 +552,11 @@ pub struct Page {
     /// The logical page number (controlled by `counter(page)` and may thus not
     /// match the physical number).
     pub number: u64,
+    /// How far the fill and background extend beyond the trimmed page on
+    /// each side.
+    pub bleed: Abs,
+    /// The width of the area around the bleed which holds printer marks.
+    pub slug: Abs,
 }
 
 impl Page {
@@ -515,6 +567,23 @@ impl Page {
         self.fill.clone().unwrap_or(None)
     }
 
+    /// The trimmed page within the page's frame.
+    pub fn trim_box(&self) -> Rect {
+        let offset = self.bleed + self.slug;
+        Rect::new(
+            Point::splat(offset),
+            (self.frame.size() - Size::splat(offset)).to_point(),
+        )
+    }
+
+    /// The trimmed page plus its bleed within the page's frame.
+    pub fn bleed_box(&self) -> Rect {
+        Rect::new(
+            Point::splat(self.slug),
+            (self.frame.size() - Size::splat(self.slug)).to_point(),
+        )
+    }
+
     /// Get the configured background or white if it is `Auto`.
     ///
     /// This is used in raster and SVG export.
@@ -632,6 +701,18 @@ cast! {
     }
 }
 
+/// A mark printed outside of a page's bleed to aid in print production.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PrinterMark {
+    /// Lines at the corners of the page, which show where to trim it.
+    Crop,
+    /// Targets at the center of each edge, which help to align the printing
+    /// plates of the different inks.
+    Registration,
+    /// A row of ink patches, which helps to check the ink density.
+    ColorBar,
+}
+
 /// Specification of the page's binding.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Binding {
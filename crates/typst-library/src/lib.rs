@@ -167,6 +167,7 @@ pub struct LibraryBuilder {
     routines: &'static Routines,
     inputs: Option<Dict>,
     features: Features,
+    html_schemas: Vec<Bytes>,
 }
 
 impl LibraryBuilder {
@@ -177,6 +178,7 @@ impl LibraryBuilder {
             routines,
             inputs: None,
             features: Features::default(),
+            html_schemas: vec![],
         }
     }
 
@@ -194,11 +196,27 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure schemas of custom HTML elements, which become available as
+    /// typed functions in the `html` module if HTML export is enabled.
+    ///
+    /// Schemas that are not valid are ignored, so they should be checked with
+    /// `typst_html::HtmlSchema::parse` beforehand.
+    pub fn with_html_schemas(mut self, schemas: Vec<Bytes>) -> Self {
+        self.html_schemas = schemas;
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
         let inputs = self.inputs.unwrap_or_default();
-        let global = global(self.routines, math.clone(), inputs, &self.features);
+        let global = global(
+            self.routines,
+            math.clone(),
+            inputs,
+            &self.features,
+            &self.html_schemas,
+        );
         Library {
             global: global.clone(),
             math,
@@ -286,6 +304,7 @@ fn global(
     math: Module,
     inputs: Dict,
     features: &Features,
+    html_schemas: &[Bytes],
 ) -> Module {
     let mut global = Scope::deduplicating();
 
@@ -301,7 +320,7 @@ fn global(
     global.define("math", math);
     global.define("pdf", self::pdf::module(features));
     if features.is_enabled(Feature::Html) {
-        global.define("html", (routines.html_module)());
+        global.define("html", (routines.html_module)(html_schemas));
     }
 
     prelude(&mut global);
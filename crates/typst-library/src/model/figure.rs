@@ -19,7 +19,7 @@ use crate::layout::{
     VAlignment,
 };
 use crate::model::{Numbering, NumberingPattern, Outlinable, Refable, Supplement};
-use crate::text::{Lang, Locale, TextElem};
+use crate::text::{Lang, Locale, SpaceElem, TextElem};
 use crate::visualize::ImageElem;
 
 /// A figure with an optional caption.
@@ -536,6 +536,29 @@ pub struct FigureCaption {
     /// ```
     pub separator: Smart<Content>,
 
+    /// Content to show after the figure's number in a caption that is repeated
+    /// on each page after the first when the figure breaks across pages.
+    ///
+    /// This only applies to captions positioned at the top of a non-floating
+    /// figure which is [breakable]($block.breakable). The repeated caption
+    /// consists of the figure's supplement and number, followed by this
+    /// content.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #show figure: set block(breakable: true)
+    /// #show figure.where(kind: table): set figure.caption(
+    ///   position: top,
+    ///   continuation: [(continued)],
+    /// )
+    ///
+    /// #figure(
+    ///   table(columns: 4, ..range(24).map(str)),
+    ///   caption: [Numbers],
+    /// )
+    /// ```
+    pub continuation: Option<Content>,
+
     /// The caption's body.
     ///
     /// Can be used alongside `kind`, `supplement`, `counter`, `numbering`, and
@@ -586,8 +609,35 @@ impl FigureCaption {
         styles: StyleChain,
     ) -> SourceResult<Content> {
         let mut realized = self.body.clone();
+        if let Some(prefix) = self.realize_prefix(engine, styles)? {
+            realized = prefix + self.resolve_separator(styles) + realized;
+        }
+        Ok(realized)
+    }
 
-        if let (
+    /// Realizes the textual content of the caption repeated on subsequent
+    /// pages of a broken figure, if the caption has a continuation.
+    pub fn realize_continuation(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Option<Content>> {
+        let Some(mut realized) = self.continuation.get_cloned(styles) else {
+            return Ok(None);
+        };
+        if let Some(prefix) = self.realize_prefix(engine, styles)? {
+            realized = prefix + SpaceElem::shared().clone() + realized;
+        }
+        Ok(Some(realized))
+    }
+
+    /// Realizes the supplement and number of the figure, if it is numbered.
+    fn realize_prefix(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Option<Content>> {
+        let (
             Some(Some(mut supplement)),
             Some(Some(numbering)),
             Some(Some(counter)),
@@ -597,15 +647,16 @@ impl FigureCaption {
             &self.numbering,
             &self.counter,
             &self.figure_location,
-        ) {
-            let numbers = counter.display_at_loc(engine, *location, styles, numbering)?;
-            if !supplement.is_empty() {
-                supplement += TextElem::packed('\u{a0}');
-            }
-            realized = supplement + numbers + self.resolve_separator(styles) + realized;
-        }
+        )
+        else {
+            return Ok(None);
+        };
 
-        Ok(realized)
+        let numbers = counter.display_at_loc(engine, *location, styles, numbering)?;
+        if !supplement.is_empty() {
+            supplement += TextElem::packed('\u{a0}');
+        }
+        Ok(Some(supplement + numbers))
     }
 
     /// Retrieves the locale separator.
@@ -1,21 +1,27 @@
 use std::num::{NonZeroU32, NonZeroUsize};
+use std::str::FromStr;
 use std::sync::Arc;
 
-use ecow::EcoString;
+use comemo::{Track, Tracked};
+use ecow::{EcoString, eco_format};
+use smallvec::smallvec;
+use typst_syntax::{Span, Spanned};
 use typst_utils::NonZeroExt;
 
-use crate::diag::{HintedStrResult, HintedString, SourceResult, bail};
+use crate::diag::{At, HintedStrResult, HintedString, SourceResult, StrResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Content, Packed, Smart, StyleChain, Synthesize, cast, elem, scope,
+    Array, Content, Context, Dict, Func, IntoValue, NativeElement, Packed, Repr, Smart,
+    Str, StyleChain, Synthesize, Value, cast, elem, func, scope,
 };
-use crate::introspection::{Locatable, Tagged};
+use crate::introspection::{Locatable, Location, Tagged};
 use crate::layout::resolve::{CellGrid, table_to_cellgrid};
 use crate::layout::{
-    Abs, Alignment, Celled, GridCell, GridFooter, GridHLine, GridHeader, GridVLine,
-    Length, OuterHAlignment, OuterVAlignment, Rel, Sides, TrackSizings,
+    Abs, Alignment, Celled, ColumnSizings, GridCell, GridFooter, GridHLine, GridHeader,
+    GridVLine, Length, OuterHAlignment, OuterVAlignment, Rel, Sides, Sizing,
+    TrackSizings,
 };
-use crate::model::Figurable;
+use crate::model::{Figurable, Numbering, NumberingPattern};
 use crate::pdf::TableCellKind;
 use crate::text::LocalName;
 use crate::visualize::{Paint, Stroke};
@@ -139,7 +145,7 @@ use crate::visualize::{Paint, Stroke};
 pub struct TableElem {
     /// The column sizes. See the [grid documentation]($grid/#track-size) for
     /// more information on track sizing.
-    pub columns: TrackSizings,
+    pub columns: ColumnSizings,
 
     /// The row sizes. See the [grid documentation]($grid/#track-size) for more
     /// information on track sizing.
@@ -219,6 +225,27 @@ pub struct TableElem {
     /// ```
     pub align: Celled<Smart<Alignment>>,
 
+    /// A character on which to align the cells' text, such as a decimal point.
+    /// See the [grid documentation]($grid.align-char) for more information.
+    ///
+    /// This works best with [tabular numbers]($text.number-width), whose
+    /// digits all have the same width.
+    ///
+    /// ```example
+    /// #set text(number-width: "tabular")
+    ///
+    /// #table(
+    ///   columns: 2,
+    ///   align: (left, right),
+    ///   align-char: (x, y) => if x == 1 and y > 0 { "." },
+    ///   [*Item*], [*Price*],
+    ///   [Coffee], [3.5],
+    ///   [Cake], [12.75],
+    ///   [Water], [1],
+    /// )
+    /// ```
+    pub align_char: Celled<Option<char>>,
+
     /// How to fill the cells.
     ///
     /// This can be:
@@ -305,6 +332,182 @@ impl TableElem {
 
     #[elem]
     type TableFooter;
+
+    #[elem]
+    type TableNote;
+
+    /// Creates a table from tabular data, such as the rows loaded with
+    /// [`csv`] or an array of dictionaries loaded with [`json`].
+    ///
+    /// Columns in which all non-empty values are numbers are aligned to the
+    /// right. Missing values, like a key that is absent from one of the
+    /// dictionaries, result in empty cells.
+    ///
+    /// ```example
+    /// #table.from(
+    ///   (
+    ///     (fruit: "Apples", price: 1.5, stock: 12),
+    ///     (fruit: "Bananas", price: 0.25, stock: 150),
+    ///     (fruit: "Cherries", price: 4, stock: none),
+    ///   ),
+    ///   columns: ("fruit", "price"),
+    ///   format: (price: v => [#v €]),
+    /// )
+    /// ```
+    #[func]
+    pub fn from(
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        span: Span,
+        /// The rows of the table. Either an array of arrays, where each inner
+        /// array holds the values of one row, or an array of dictionaries,
+        /// which map column names to values.
+        data: Spanned<Array>,
+        /// Whether to create a header. For arrays of arrays, the first row
+        /// forms the header. For arrays of dictionaries, the header consists
+        /// of the column names.
+        #[named]
+        #[default(true)]
+        header: bool,
+        /// Which columns to show and in which order. Each column is either
+        /// referred to by its name (a dictionary key or, for arrays of arrays
+        /// with a header, a header cell) or by its zero-based index. Shows all
+        /// columns by default.
+        #[named]
+        columns: Option<Spanned<Vec<DataColumn>>>,
+        /// Functions which turn the values of a column into content, keyed by
+        /// the column name. They are not called for missing values.
+        #[named]
+        format: Option<Spanned<Dict>>,
+    ) -> SourceResult<Content> {
+        let Spanned { v: data, span: data_span } = data;
+        let dicts = data.iter().all(|row| matches!(row, Value::Dict(_)));
+        let names = data_columns(&data, dicts, header).at(data_span)?;
+        let selected = match columns {
+            Some(Spanned { v: columns, span }) => columns
+                .into_iter()
+                .map(|column| column.resolve(&names))
+                .collect::<StrResult<Vec<_>>>()
+                .at(span)?,
+            None => (0..names.len()).collect(),
+        };
+
+        let Spanned { v: format, span: format_span } =
+            format.unwrap_or(Spanned::new(Dict::new(), span));
+        if let Some((key, _)) = format
+            .iter()
+            .find(|(key, _)| !names.iter().flatten().any(|name| name == *key))
+        {
+            bail!(format_span, "data has no column named {}", key.repr());
+        }
+
+        let formats = selected
+            .iter()
+            .map(|&x| {
+                let Some(name) = &names[x] else { return Ok(None) };
+                format.get(name).ok().cloned().map(Value::cast::<Func>).transpose()
+            })
+            .collect::<HintedStrResult<Vec<_>>>()
+            .at(format_span)?;
+
+        let cell = |body: Content| {
+            TableItem::Cell(Packed::new(TableCell::new(body)).spanned(span))
+        };
+
+        let mut children = Vec::with_capacity(data.len() * selected.len() + 1);
+        if header {
+            let items = selected
+                .iter()
+                .map(|&x| {
+                    cell(Value::Str(names[x].clone().unwrap_or_default()).display())
+                })
+                .collect();
+            children.push(TableChild::Header(
+                Packed::new(TableHeader::new(items)).spanned(span),
+            ));
+        }
+
+        // Whether the non-empty values seen so far in each selected column are
+        // all numbers, or `None` as long as all of them were empty.
+        let mut numeric: Vec<Option<bool>> = vec![None; selected.len()];
+        let skip = usize::from(header && !dicts);
+        for row in data.iter().skip(skip) {
+            for (i, (&x, func)) in selected.iter().zip(&formats).enumerate() {
+                let value = match row {
+                    Value::Dict(dict) => {
+                        names[x].as_ref().and_then(|name| dict.get(name).ok())
+                    }
+                    Value::Array(array) => array.as_slice().get(x),
+                    _ => None,
+                };
+                let value = value.cloned().unwrap_or_default();
+                if let Some(is_number) = is_numeric(&value) {
+                    numeric[i] = Some(numeric[i].unwrap_or(true) && is_number);
+                }
+                let body = match (func, value) {
+                    (_, Value::None) => Content::empty(),
+                    (Some(func), value) => func.call(engine, context, [value])?.display(),
+                    (None, value) => value.display(),
+                };
+                children.push(TableChild::Item(cell(body)));
+            }
+        }
+
+        // Numeric columns are aligned to the right, including their header.
+        let align = |x: usize, item: &mut TableItem| {
+            if let TableItem::Cell(cell) = item
+                && numeric[x] == Some(true)
+            {
+                cell.align.set(Smart::Custom(Alignment::RIGHT));
+            }
+        };
+        let mut i = 0;
+        for child in &mut children {
+            match child {
+                TableChild::Header(header) => {
+                    for (x, item) in header.children.iter_mut().enumerate() {
+                        align(x, item);
+                    }
+                }
+                TableChild::Item(item) => {
+                    align(i % selected.len(), item);
+                    i += 1;
+                }
+                TableChild::Footer(_) => {}
+            }
+        }
+
+        Ok(TableElem::new(children)
+            .with_columns(ColumnSizings(smallvec![Sizing::Auto.into(); selected.len()]))
+            .pack()
+            .spanned(span))
+    }
+}
+
+impl Packed<TableElem> {
+    /// Realizes the numbers and bodies of the notes placed within this
+    /// table, in order of their first appearance.
+    pub fn realize_notes(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Vec<(Content, Content)>> {
+        let Some(loc) = self.location() else { return Ok(vec![]) };
+        let default = StyleChain::default();
+        collect_notes(engine, loc)
+            .into_iter()
+            .enumerate()
+            .map(|(i, note)| {
+                let context = Context::new(note.location(), Some(styles));
+                let num = note
+                    .numbering
+                    .get_ref(default)
+                    .apply(engine, context.track(), &[i as u64 + 1])?
+                    .display();
+                Ok((num, note.body.clone()))
+            })
+            .collect()
+    }
 }
 
 impl Synthesize for Packed<TableElem> {
@@ -772,6 +975,21 @@ pub struct TableCell {
     /// breakable.
     pub breakable: Smart<bool>,
 
+    /// Content to repeat at the top of the cell on each page after the first
+    /// when the cell breaks across pages. Functions identically to the
+    /// `continuation` field in [`grid.cell`].
+    ///
+    /// ```example
+    /// #set page(height: 6em)
+    /// #set table.cell(continuation: emph[(continued)])
+    ///
+    /// #table(
+    ///   columns: (auto, 1fr),
+    ///   [Terms], lorem(25),
+    /// )
+    /// ```
+    pub continuation: Option<Content>,
+
     #[internal]
     #[parse(Some(Smart::Auto))]
     pub kind: Smart<TableCellKind>,
@@ -805,3 +1023,183 @@ impl From<Content> for TableCell {
         value.unpack::<Self>().unwrap_or_else(Self::new)
     }
 }
+
+/// A note attached to a table.
+///
+/// Table notes work similarly to [footnotes]($footnote), but instead of being
+/// collected at the bottom of the page, they are listed directly below the
+/// table they are placed in. Notes are numbered separately for each table and
+/// notes with the same body share a single number.
+///
+/// ```example
+/// #table(
+///   columns: 2,
+///   [Country], [Population],
+///   [France], [68.4M #table.note[Estimate.]],
+///   [Germany], [84.5M #table.note[Estimate.]],
+///   [Monaco], [38K #table.note[Residents only.]],
+/// )
+/// ```
+///
+/// A note placed outside of a table only displays its marker.
+#[elem(name = "note", title = "Table Note", Locatable)]
+pub struct TableNote {
+    /// How to number the notes of a table. Accepts a
+    /// [numbering pattern or function]($numbering) taking a single number.
+    ///
+    /// ```example
+    /// #set table.note(numbering: "*")
+    ///
+    /// #table(
+    ///   columns: 2,
+    ///   [Tea], [2.50 #table.note[Refills are free.]],
+    ///   [Cake], [4.00 #table.note[While supplies last.]],
+    /// )
+    /// ```
+    #[default(Numbering::Pattern(NumberingPattern::from_str("a").unwrap()))]
+    pub numbering: Numbering,
+
+    /// The content to list below the table.
+    #[required]
+    pub body: Content,
+
+    /// The location of the table the note is placed in.
+    #[internal]
+    pub table: Option<Location>,
+}
+
+impl Packed<TableNote> {
+    /// Realizes the note's number, which is determined by its position among
+    /// the notes of its table.
+    pub fn realize(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Content> {
+        let n = self.table.get(styles).map_or(1, |table| {
+            collect_notes(engine, table)
+                .iter()
+                .position(|note| note.body == self.body)
+                .map_or(1, |i| i + 1)
+        });
+
+        let context = Context::new(self.location(), Some(styles));
+        Ok(self
+            .numbering
+            .get_ref(styles)
+            .apply(engine, context.track(), &[n as u64])?
+            .display())
+    }
+}
+
+/// A column of the data passed to [`table.from`](TableElem::from).
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum DataColumn {
+    /// The column with the given zero-based index.
+    Index(usize),
+    /// The column with the given name.
+    Name(Str),
+}
+
+impl DataColumn {
+    /// Resolves the column's index among the columns with the given names.
+    fn resolve(self, names: &[Option<Str>]) -> StrResult<usize> {
+        match self {
+            Self::Index(x) if x < names.len() => Ok(x),
+            Self::Index(x) => {
+                bail!("column index out of bounds (index: {x}, len: {})", names.len())
+            }
+            Self::Name(name) => names
+                .iter()
+                .position(|other| other.as_ref() == Some(&name))
+                .ok_or_else(|| eco_format!("data has no column named {}", name.repr())),
+        }
+    }
+}
+
+cast! {
+    DataColumn,
+    self => match self {
+        Self::Index(x) => x.into_value(),
+        Self::Name(name) => name.into_value(),
+    },
+    x: usize => Self::Index(x),
+    name: Str => Self::Name(name),
+}
+
+/// Determines the names of the columns of the data passed to
+/// [`table.from`](TableElem::from), which is an array of dictionaries if
+/// `dicts` is set and must be an array of arrays otherwise.
+///
+/// Columns of arrays of arrays only have names if there is a header.
+fn data_columns(data: &Array, dicts: bool, header: bool) -> StrResult<Vec<Option<Str>>> {
+    if dicts {
+        let mut names: Vec<Option<Str>> = vec![];
+        for row in data.iter() {
+            let Value::Dict(dict) = row else { continue };
+            for (key, _) in dict.iter() {
+                if !names.iter().flatten().any(|name| name == key) {
+                    names.push(Some(key.clone()));
+                }
+            }
+        }
+        return Ok(names);
+    }
+
+    let mut len = 0;
+    for row in data.iter() {
+        match row {
+            Value::Array(row) => len = len.max(row.len()),
+            v => bail!(
+                "expected rows to be either all arrays or all dictionaries, found {}",
+                v.ty()
+            ),
+        }
+    }
+
+    let mut names = vec![None; len];
+    if header && let Some(Value::Array(row)) = data.as_slice().first() {
+        for (name, value) in names.iter_mut().zip(row.iter()) {
+            *name = Some(match value {
+                Value::Str(text) => text.clone(),
+                v => v.repr().into(),
+            });
+        }
+    }
+
+    Ok(names)
+}
+
+/// Whether the value is a number, or `None` if it is empty.
+fn is_numeric(value: &Value) -> Option<bool> {
+    match value {
+        Value::None => None,
+        Value::Int(_) | Value::Float(_) | Value::Decimal(_) => Some(true),
+        Value::Str(text) => {
+            let text = text.trim();
+            if text.is_empty() {
+                return None;
+            }
+            Some(
+                text.parse::<f64>().is_ok()
+                    && text.contains(|c: char| c.is_ascii_digit()),
+            )
+        }
+        _ => Some(false),
+    }
+}
+
+/// Collects the notes placed within the table at the given location. Notes
+/// with equal bodies are only included once.
+fn collect_notes(engine: &Engine, table: Location) -> Vec<Packed<TableNote>> {
+    let mut notes: Vec<Packed<TableNote>> = vec![];
+    for elem in engine.introspector.query(&TableNote::ELEM.select()) {
+        let Ok(note) = elem.into_packed::<TableNote>() else { continue };
+        if note.table.get(StyleChain::default()) == Some(table)
+            && !notes.iter().any(|other| other.body == note.body)
+        {
+            notes.push(note);
+        }
+    }
+    notes
+}
@@ -0,0 +1,105 @@
+use std::num::NonZeroUsize;
+
+use ecow::EcoString;
+use typst_syntax::Spanned;
+
+use crate::diag::{SourceResult, bail};
+use crate::foundations::{Content, Derived, Packed, Smart, StyleChain, elem};
+use crate::loading::{DataSource, Load, Loaded};
+use crate::visualize::{PdfDocument, PdfImage};
+
+/// Includes the pages of an existing PDF document.
+///
+/// Each included page becomes a page of the document with the size of the
+/// original page. The pages are embedded as vector graphics, so their text
+/// stays selectable and searchable, and web links on them keep working. This
+/// is useful to assemble appendices or attachments from external PDFs.
+///
+/// The included pages count towards the [page counter]($counter) like any
+/// other page, but no header, footer, background, or foreground is drawn on
+/// them, and they have neither a [bleed]($page.bleed) nor
+/// [printer marks]($page.marks). Give them a [`title`]($pdf.include.title) to
+/// list them in the [outline] and the PDF's bookmarks.
+///
+/// # Example
+/// ```typ
+/// #outline()
+///
+/// = Results
+/// ...
+///
+/// #pdf.include(
+///   "measurements.pdf",
+///   pages: range(2, 5),
+///   title: [Appendix: Measurements],
+/// )
+/// ```
+///
+/// # Notes
+/// - Links to other places within the included PDF are not preserved.
+/// - Like [PDF images]($image.format), included pages are not supported when
+///   exporting with a specific PDF standard.
+/// - This element is ignored if exporting to HTML.
+#[elem(name = "include", title = "Include PDF")]
+pub struct IncludeElem {
+    /// A [path]($syntax/#paths) to a PDF file or raw bytes making up a PDF
+    /// file.
+    #[required]
+    #[parse(
+        let source = args.expect::<Spanned<DataSource>>("source")?;
+        let loaded = source.load(engine.world)?;
+        Derived::new(source.v, loaded)
+    )]
+    pub source: Derived<DataSource, Loaded>,
+
+    /// The numbers of the pages to include, starting from 1. By default, all
+    /// pages are included.
+    ///
+    /// ```typ
+    /// #pdf.include("report.pdf", pages: (1, 3))
+    /// #pdf.include("report.pdf", pages: range(5, 10))
+    /// ```
+    pub pages: Smart<Vec<NonZeroUsize>>,
+
+    /// A title for the included pages, which is listed in the [outline] and
+    /// the PDF's bookmarks and points to the first included page.
+    ///
+    /// The title is not shown on the pages themselves.
+    pub title: Option<Content>,
+
+    /// An alternative description of the included pages.
+    ///
+    /// The pages are embedded as untagged vector graphics, so while their text
+    /// stays selectable, their structure is not accessible to Assistive
+    /// Technology on its own.
+    pub alt: Option<EcoString>,
+}
+
+impl Packed<IncludeElem> {
+    /// Loads the PDF and returns the pages to include.
+    pub fn load_pages(&self, styles: StyleChain) -> SourceResult<Vec<PdfImage>> {
+        let span = self.span();
+        let document = PdfDocument::load(self.source.derived.data.clone(), span)?;
+        let num_pages = document.num_pages();
+        let numbers = match self.pages.get_ref(styles) {
+            Smart::Custom(numbers) => numbers.clone(),
+            Smart::Auto => (1..=num_pages).filter_map(NonZeroUsize::new).collect(),
+        };
+
+        numbers
+            .into_iter()
+            .map(|number| {
+                let Some(image) = PdfImage::new(document.clone(), number.get() - 1)
+                else {
+                    let s = if num_pages == 1 { "" } else { "s" };
+                    bail!(
+                        span,
+                        "page {number} does not exist";
+                        hint: "the document only has {num_pages} page{s}"
+                    );
+                };
+                Ok(image)
+            })
+            .collect()
+    }
+}
@@ -2,9 +2,11 @@
 
 mod accessibility;
 mod attach;
+mod include;
 
 pub use self::accessibility::*;
 pub use self::attach::*;
+pub use self::include::*;
 
 use crate::foundations::{Deprecation, Element, Module, Scope};
 use crate::{Feature, Features};
@@ -21,6 +23,7 @@ pub fn module(features: &Features) -> Module {
             .with_until("0.15.0"),
     );
     pdf.define_elem::<ArtifactElem>();
+    pdf.define_elem::<IncludeElem>();
     if features.is_enabled(Feature::A11yExtras) {
         pdf.define_func::<table_summary>();
         pdf.define_func::<header_cell>();
@@ -9,8 +9,8 @@ use crate::World;
 use crate::diag::SourceResult;
 use crate::engine::{Engine, Route, Sink, Traced};
 use crate::foundations::{
-    Args, Closure, Content, Context, Func, Module, NativeRuleMap, Scope, StyleChain,
-    Styles, Value,
+    Args, Bytes, Closure, Content, Context, Func, Module, NativeRuleMap, Scope,
+    StyleChain, Styles, Value,
 };
 use crate::introspection::{Introspector, Locator, SplitLocator};
 use crate::layout::{Frame, Region};
@@ -94,8 +94,9 @@ routines! {
         region: Region,
     ) -> SourceResult<Frame>
 
-    /// Constructs the `html` module.
-    fn html_module() -> Module
+    /// Constructs the `html` module, including typed functions for the custom
+    /// elements described by the given schemas.
+    fn html_module(schemas: &[Bytes]) -> Module
 
     /// Wraps content in a span with a color.
     ///
@@ -14,6 +14,7 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::{Arc, OnceLock};
 
+use ttf_parser::os2::Permissions;
 use ttf_parser::{GlyphId, name_id};
 
 use self::book::find_name;
@@ -134,6 +135,23 @@ impl Font {
         find_name(&self.0.ttf, id)
     }
 
+    /// The restriction that the font's license places on embedding the font
+    /// into documents, as declared in the `fsType` field of its OS/2 table.
+    pub fn embedding_restriction(&self) -> Option<EmbeddingRestriction> {
+        // Without an OS/2 table, the font is installable.
+        let os2 = self.0.ttf.tables().os2?;
+        if os2.permissions() == Some(Permissions::Restricted) {
+            Some(EmbeddingRestriction::Restricted)
+        } else if !os2.is_bitmap_embedding_allowed() {
+            // Despite its name, this checks the "Bitmap embedding only" bit.
+            Some(EmbeddingRestriction::BitmapOnly)
+        } else if !os2.is_subsetting_allowed() {
+            Some(EmbeddingRestriction::NoSubsetting)
+        } else {
+            None
+        }
+    }
+
     /// A reference to the underlying `ttf-parser` face.
     pub fn ttf(&self) -> &ttf_parser::Face<'_> {
         // We can't implement Deref because that would leak the
@@ -212,6 +230,29 @@ impl PartialEq for Font {
     }
 }
 
+/// A restriction that a font's license places on embedding the font into
+/// documents.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EmbeddingRestriction {
+    /// The font must not be embedded at all.
+    Restricted,
+    /// Only the font's bitmaps may be embedded, not its outlines.
+    BitmapOnly,
+    /// The font may only be embedded as a whole, not as a subset.
+    NoSubsetting,
+}
+
+impl EmbeddingRestriction {
+    /// A human-readable description of the restriction.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Restricted => "the font must not be embedded",
+            Self::BitmapOnly => "only the font's bitmaps may be embedded",
+            Self::NoSubsetting => "the font must not be subset",
+        }
+    }
+}
+
 /// Metrics of a font.
 #[derive(Debug, Clone)]
 pub struct FontMetrics {
@@ -603,3 +644,53 @@ pub enum TextEdgeBounds<'a> {
     /// Use the dimension of the given frame for the bounds.
     Frame(&'a Frame),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a bundled font with the given `fsType` written into its OS/2
+    /// table.
+    fn font_with_fs_type(fs_type: u16) -> Font {
+        // Older OS/2 tables ignore some of the flags.
+        let (mut data, os2) = typst_assets::fonts()
+            .find_map(|data| {
+                let os2 = os2_offset(data)?;
+                let version = u16::from_be_bytes([data[os2], data[os2 + 1]]);
+                (version >= 3).then(|| (data.to_vec(), os2))
+            })
+            .unwrap();
+        data[os2 + 8..os2 + 10].copy_from_slice(&fs_type.to_be_bytes());
+        Font::new(Bytes::new(data), 0).unwrap()
+    }
+
+    /// Finds the offset of the OS/2 table in the table directory of a font.
+    fn os2_offset(data: &[u8]) -> Option<usize> {
+        let num_tables = u16::from_be_bytes([*data.get(4)?, *data.get(5)?]);
+        (0..usize::from(num_tables)).find_map(|i| {
+            let record = data.get(12 + 16 * i..28 + 16 * i)?;
+            (&record[..4] == b"OS/2").then(|| {
+                u32::from_be_bytes([record[8], record[9], record[10], record[11]])
+                    as usize
+            })
+        })
+    }
+
+    #[test]
+    fn test_embedding_restriction() {
+        #[track_caller]
+        fn test(fs_type: u16, restriction: Option<EmbeddingRestriction>) {
+            assert_eq!(font_with_fs_type(fs_type).embedding_restriction(), restriction);
+        }
+
+        test(0x0000, None);
+        test(0x0004, None);
+        test(0x0008, None);
+        test(0x0002, Some(EmbeddingRestriction::Restricted));
+        test(0x0302, Some(EmbeddingRestriction::Restricted));
+        test(0x0200, Some(EmbeddingRestriction::BitmapOnly));
+        test(0x0304, Some(EmbeddingRestriction::BitmapOnly));
+        test(0x0100, Some(EmbeddingRestriction::NoSubsetting));
+        test(0x0108, Some(EmbeddingRestriction::NoSubsetting));
+    }
+}
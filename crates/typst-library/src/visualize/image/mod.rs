@@ -4,7 +4,7 @@ mod pdf;
 mod raster;
 mod svg;
 
-pub use self::pdf::PdfImage;
+pub use self::pdf::{PdfDocument, PdfImage, PdfLink};
 pub use self::raster::{
     ExchangeFormat, PixelEncoding, PixelFormat, RasterFormat, RasterImage,
 };
@@ -16,7 +16,6 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use ecow::EcoString;
-use hayro_syntax::LoadPdfError;
 use typst_syntax::{Span, Spanned};
 use typst_utils::{LazyHash, NonZeroExt};
 
@@ -31,7 +30,6 @@ use crate::layout::{Length, Rel, Sizing};
 use crate::loading::{DataSource, Load, LoadSource, Loaded, Readable};
 use crate::model::Figurable;
 use crate::text::{LocalName, Locale, families};
-use crate::visualize::image::pdf::PdfDocument;
 
 /// A raster or vector graphic.
 ///
@@ -316,27 +314,7 @@ impl Packed<ImageElem> {
                 )
             }
             ImageFormat::Vector(VectorFormat::Pdf) => {
-                let document = match PdfDocument::new(loaded.data.clone()) {
-                    Ok(doc) => doc,
-                    Err(e) => match e {
-                        // TODO: the `DecyptionError` is currently not public
-                        LoadPdfError::Decryption(_) => {
-                            bail!(
-                                span,
-                                "the PDF is encrypted or password-protected";
-                                hint: "such PDFs are currently not supported";
-                                hint: "preprocess the PDF to remove the encryption"
-                            );
-                        }
-                        LoadPdfError::Invalid => {
-                            bail!(
-                                span,
-                                "the PDF could not be loaded";
-                                hint: "perhaps the PDF file is malformed"
-                            );
-                        }
-                    },
-                };
+                let document = PdfDocument::load(loaded.data.clone(), span)?;
 
                 // The user provides the page number start from 1, but further
                 // down the pipeline, page numbers are 0-based.
@@ -1,10 +1,16 @@
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use hayro_syntax::page::Page;
+use ecow::EcoString;
+use hayro_syntax::object::dict::keys::{A, ANNOTS, RECT, S, SUBTYPE, URI};
+use hayro_syntax::object::{Array, Dict, Name, Rect as PdfRect, String as PdfString};
+use hayro_syntax::page::{Page, Rotation};
 use hayro_syntax::{LoadPdfError, Pdf};
+use typst_syntax::Span;
 
+use crate::diag::{SourceResult, bail};
 use crate::foundations::Bytes;
+use crate::layout::{Abs, Point, Rect};
 
 /// A PDF document.
 #[derive(Clone, Hash)]
@@ -25,6 +31,32 @@ impl PdfDocument {
         Ok(Self(Arc::new(DocumentRepr { data, pdf })))
     }
 
+    /// Loads a PDF document, failing with a user-facing error if it cannot be
+    /// read.
+    pub fn load(data: Bytes, span: Span) -> SourceResult<PdfDocument> {
+        match Self::new(data) {
+            Ok(doc) => Ok(doc),
+            Err(e) => match e {
+                // TODO: the `DecyptionError` is currently not public
+                LoadPdfError::Decryption(_) => {
+                    bail!(
+                        span,
+                        "the PDF is encrypted or password-protected";
+                        hint: "such PDFs are currently not supported";
+                        hint: "preprocess the PDF to remove the encryption"
+                    );
+                }
+                LoadPdfError::Invalid => {
+                    bail!(
+                        span,
+                        "the PDF could not be loaded";
+                        hint: "perhaps the PDF file is malformed"
+                    );
+                }
+            },
+        }
+    }
+
     /// Returns the underlying PDF document.
     pub fn pdf(&self) -> &Arc<Pdf> {
         &self.0.pdf
@@ -88,6 +120,61 @@ impl PdfImage {
     pub fn page_index(&self) -> usize {
         self.0.page_index
     }
+
+    /// Returns the web links on the page.
+    ///
+    /// Links are annotations rather than part of the page's content, so they
+    /// are lost when the page is drawn as an image and must be recreated.
+    /// Links on rotated pages are not supported.
+    pub fn links(&self) -> Vec<PdfLink> {
+        let page = self.page();
+        if !matches!(page.rotation(), Rotation::None) {
+            return vec![];
+        }
+
+        let Some(annotations) = page.raw().get::<Array>(ANNOTS) else {
+            return vec![];
+        };
+
+        // Annotation rectangles are in PDF user space, where the y-axis
+        // points upwards.
+        let crop = page.intersected_crop_box();
+        annotations
+            .iter::<Dict>()
+            .filter(|annot| {
+                annot.get::<Name>(SUBTYPE).is_some_and(|name| &*name == b"Link")
+            })
+            .filter_map(|annot| {
+                let rect = annot.get::<PdfRect>(RECT)?;
+                let action = annot.get::<Dict>(A)?;
+                if &*action.get::<Name>(S)? != b"URI" {
+                    return None;
+                }
+                let url = action.get::<PdfString>(URI)?;
+                let min = Point::new(
+                    Abs::pt(rect.x0.min(rect.x1) - crop.x0),
+                    Abs::pt(crop.y1 - rect.y0.max(rect.y1)),
+                );
+                let max = Point::new(
+                    Abs::pt(rect.x0.max(rect.x1) - crop.x0),
+                    Abs::pt(crop.y1 - rect.y0.min(rect.y1)),
+                );
+                Some(PdfLink {
+                    rect: Rect::new(min, max),
+                    url: String::from_utf8_lossy(&url.get()).into(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A web link on a PDF page.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct PdfLink {
+    /// The area of the link, relative to the top-left corner of the page.
+    pub rect: Rect,
+    /// The URL the link points to.
+    pub url: EcoString,
 }
 
 impl Hash for ImageRepr {
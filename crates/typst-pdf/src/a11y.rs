@@ -0,0 +1,282 @@
+use ecow::{EcoString, EcoVec, eco_format};
+use rustc_hash::FxHashSet;
+use typst_library::diag::SourceDiagnostic;
+use typst_library::foundations::{NativeElement, Smart, StyleChain};
+use typst_library::layout::{
+    Abs, Frame, FrameItem, Page, PagedDocument, Point, Rect, Transform,
+};
+use typst_library::model::{FigureElem, FigureKind, HeadingElem, TableChild, TableElem};
+use typst_library::text::{FontWeight, TextItem};
+use typst_library::visualize::{Color, ImageElem, Paint};
+use typst_syntax::Span;
+
+/// The minimum contrast ratio between normal text and its background, as
+/// required by WCAG 2.2 success criterion 1.4.3.
+const MIN_CONTRAST: f32 = 4.5;
+
+/// The minimum contrast ratio between large text and its background.
+const MIN_CONTRAST_LARGE: f32 = 3.0;
+
+/// Checks a document for common accessibility issues and reports them as
+/// warnings.
+pub(crate) fn check(document: &PagedDocument) -> EcoVec<SourceDiagnostic> {
+    let mut checker = Checker { diags: EcoVec::new() };
+    checker.check_metadata(document);
+    checker.check_alt_text(document);
+    checker.check_headings(document);
+    checker.check_tables(document);
+    for page in &document.pages {
+        checker.check_contrast(page);
+    }
+    checker.diags
+}
+
+/// Collects accessibility issues.
+struct Checker {
+    diags: EcoVec<SourceDiagnostic>,
+}
+
+impl Checker {
+    /// Checks that the document has a title and a language.
+    fn check_metadata(&mut self, document: &PagedDocument) {
+        if document.info.title.is_none() {
+            self.issue(
+                Span::detached(),
+                "document title is missing",
+                "set the title with `set document(title: ..)`",
+            );
+        }
+
+        if document.info.locale.is_auto() {
+            self.issue(
+                Span::detached(),
+                "document language is not set",
+                "set the language with `set text(lang: ..)` at the top of the document",
+            );
+        }
+    }
+
+    /// Checks that images and image figures have alternative descriptions.
+    fn check_alt_text(&mut self, document: &PagedDocument) {
+        let introspector = &document.introspector;
+        let image_selector = ImageElem::ELEM.select();
+
+        // Images in figures with an alternative description don't need their
+        // own.
+        let mut described = FxHashSet::default();
+        for elem in introspector.query(&FigureElem::ELEM.select()) {
+            let figure = elem.to_packed::<FigureElem>().unwrap();
+            let image = figure.body.query_first_naive(&image_selector);
+            if figure.alt.get_ref(StyleChain::default()).is_some() {
+                described.extend(image.map(|image| image.span()));
+            } else if image.is_none()
+                && matches!(
+                    figure.kind.get_ref(StyleChain::default()),
+                    Smart::Custom(FigureKind::Elem(func)) if *func == ImageElem::ELEM
+                )
+            {
+                self.issue(
+                    figure.span(),
+                    "figure is missing an alternative description",
+                    "describe the figure with its `alt` parameter",
+                );
+            }
+        }
+
+        for elem in introspector.query(&image_selector) {
+            let image = elem.to_packed::<ImageElem>().unwrap();
+            if image.alt.get_ref(StyleChain::default()).is_none()
+                && !described.contains(&image.span())
+            {
+                self.issue(
+                    image.span(),
+                    "image is missing an alternative description",
+                    "describe the image with its `alt` parameter",
+                );
+            }
+        }
+    }
+
+    /// Checks that no heading levels are skipped.
+    fn check_headings(&mut self, document: &PagedDocument) {
+        let mut prev_level = 0;
+        for elem in document.introspector.query(&HeadingElem::ELEM.select()) {
+            let heading = elem.to_packed::<HeadingElem>().unwrap();
+            let level = heading.resolve_level(StyleChain::default()).get();
+            if level > prev_level + 1 {
+                let message = if prev_level == 0 {
+                    eco_format!("the first heading must be of level 1")
+                } else {
+                    eco_format!("skipped from heading level {prev_level} to {level}")
+                };
+                self.issue(heading.span(), message, "heading levels must be consecutive");
+            }
+            prev_level = level;
+        }
+    }
+
+    /// Checks that tables have a header row.
+    fn check_tables(&mut self, document: &PagedDocument) {
+        for elem in document.introspector.query(&TableElem::ELEM.select()) {
+            let table = elem.to_packed::<TableElem>().unwrap();
+            if !table
+                .children
+                .iter()
+                .any(|child| matches!(child, TableChild::Header(_)))
+            {
+                self.issue(
+                    table.span(),
+                    "table has no header row",
+                    "wrap the table's first row in `table.header`",
+                );
+            }
+        }
+    }
+
+    /// Checks that text contrasts sufficiently with its background.
+    fn check_contrast(&mut self, page: &Page) {
+        let background = match &page.fill {
+            Smart::Custom(Some(Paint::Solid(color))) => Some(*color),
+            Smart::Custom(Some(_)) => None,
+            Smart::Custom(None) | Smart::Auto => Some(Color::WHITE),
+        };
+
+        let mut state = ContrastState {
+            backgrounds: vec![],
+            page: background,
+            reported: FxHashSet::default(),
+        };
+        self.check_frame_contrast(&mut state, &page.frame, Transform::identity());
+    }
+
+    /// Checks the contrast of text in a frame.
+    fn check_frame_contrast(
+        &mut self,
+        state: &mut ContrastState,
+        frame: &Frame,
+        ts: Transform,
+    ) {
+        for (pos, item) in frame.items() {
+            let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+            match item {
+                FrameItem::Group(group) => {
+                    let ts = ts.pre_concat(group.transform);
+                    self.check_frame_contrast(state, &group.frame, ts);
+                }
+                FrameItem::Shape(shape, _) => {
+                    // Shapes drawn later cover those drawn earlier, so only
+                    // opaque solid fills are considered as backgrounds. Other
+                    // fills make the background unknown.
+                    let Some(fill) = &shape.fill else { continue };
+                    let color = match fill {
+                        Paint::Solid(color)
+                            if color.alpha().is_none_or(|alpha| alpha >= 1.0) =>
+                        {
+                            Some(*color)
+                        }
+                        _ => None,
+                    };
+                    let bbox = transform_rect(shape.geometry.bbox(), ts);
+                    state.backgrounds.push((bbox, color));
+                }
+                FrameItem::Text(text) => self.check_text_contrast(state, text, ts),
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks the contrast of a text item.
+    fn check_text_contrast(
+        &mut self,
+        state: &mut ContrastState,
+        text: &TextItem,
+        ts: Transform,
+    ) {
+        let Paint::Solid(fill) = &text.fill else { return };
+        let Some(span) = text.glyphs.first().map(|glyph| glyph.span.0) else { return };
+        if span.is_detached() || state.reported.contains(&span) {
+            return;
+        }
+
+        // The center of the text's x-height approximates where its
+        // background is.
+        let center = Point::new(text.width() / 2.0, -text.size * 0.25).transform(ts);
+        let background = state
+            .backgrounds
+            .iter()
+            .rev()
+            .find(|(bbox, _)| contains(*bbox, center))
+            .map_or(state.page, |&(_, color)| color);
+        let Some(background) = background else { return };
+
+        let bold = text.font.info().variant.weight >= FontWeight::BOLD;
+        let large = text.size >= Abs::pt(18.0) || (bold && text.size >= Abs::pt(14.0));
+        let min = if large { MIN_CONTRAST_LARGE } else { MIN_CONTRAST };
+        let ratio = contrast_ratio(*fill, background);
+        if ratio < min {
+            state.reported.insert(span);
+            self.diags.push(
+                SourceDiagnostic::warning(
+                    span,
+                    eco_format!(
+                        "text has low contrast with its background \
+                         (ratio: {ratio:.2}:1, minimum: {min}:1)"
+                    ),
+                )
+                .with_hint(
+                    "WCAG requires a contrast ratio of at least 4.5:1 for normal \
+                     text and 3:1 for large text",
+                ),
+            );
+        }
+    }
+
+    /// Reports an issue.
+    fn issue(&mut self, span: Span, message: impl Into<EcoString>, hint: &str) {
+        self.diags
+            .push(SourceDiagnostic::warning(span, message.into()).with_hint(hint));
+    }
+}
+
+/// State for checking the contrast of the text on a page.
+struct ContrastState {
+    /// The bounding boxes of filled shapes on the page, in painting order,
+    /// with their color if it is opaque and solid.
+    backgrounds: Vec<(Rect, Option<Color>)>,
+    /// The page's background color, if it is solid.
+    page: Option<Color>,
+    /// Spans for which low contrast was already reported.
+    reported: FxHashSet<Span>,
+}
+
+/// The bounding box of a transformed rectangle.
+fn transform_rect(rect: Rect, ts: Transform) -> Rect {
+    let corners = [
+        rect.min,
+        Point::new(rect.max.x, rect.min.y),
+        Point::new(rect.min.x, rect.max.y),
+        rect.max,
+    ]
+    .map(|corner| corner.transform(ts));
+    let min = corners.iter().fold(corners[0], |acc, &p| acc.min(p));
+    let max = corners.iter().fold(corners[0], |acc, &p| acc.max(p));
+    Rect::new(min, max)
+}
+
+/// Whether a point lies within a rectangle.
+fn contains(rect: Rect, point: Point) -> bool {
+    (rect.min.x..=rect.max.x).contains(&point.x)
+        && (rect.min.y..=rect.max.y).contains(&point.y)
+}
+
+/// The contrast ratio between two colors, as defined by WCAG.
+fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// The relative luminance of a color, as defined by WCAG.
+fn relative_luminance(color: Color) -> f32 {
+    let rgb = color.to_linear_rgb();
+    0.2126 * rgb.red + 0.7152 * rgb.green + 0.0722 * rgb.blue
+}
@@ -23,24 +23,26 @@ use typst_library::model::HeadingElem;
 use typst_library::text::{Font, Locale};
 use typst_library::visualize::{Geometry, Paint};
 use typst_syntax::Span;
+use typst_utils::Numeric;
 
-use crate::PdfOptions;
 use crate::attach::attach_files;
 use crate::image::handle_image;
 use crate::link::{LinkAnnotation, handle_link};
 use crate::metadata::build_metadata;
 use crate::outline::build_outline;
 use crate::page::PageLabelExt;
+use crate::report::{font_report, name_subsets};
 use crate::shape::handle_shape;
 use crate::tags::{self, GroupId, Tags};
 use crate::text::handle_text;
-use crate::util::{AbsExt, TransformExt, convert_path, display_font};
+use crate::util::{AbsExt, RectExt, TransformExt, convert_path, display_font};
+use crate::{PdfImageSummary, PdfOptions, PdfReport};
 
 #[typst_macros::time(name = "convert document")]
 pub fn convert(
     typst_document: &PagedDocument,
     options: &PdfOptions,
-) -> SourceResult<Vec<u8>> {
+) -> SourceResult<(Vec<u8>, PdfReport)> {
     let (mut document, mut gc) = setup(typst_document, options)?;
 
     convert_pages(&mut gc, &mut document)?;
@@ -51,7 +53,14 @@ pub fn convert(
     document.set_metadata(build_metadata(&gc, doc_lang));
     document.set_tag_tree(tree);
 
-    finish(document, gc, options.standards.config)
+    let mut report = PdfReport {
+        fonts: font_report(&gc),
+        images: gc.image_summary,
+        warnings: std::mem::take(&mut gc.warnings),
+    };
+    let bytes = finish(document, gc, options.standards.config)?;
+    name_subsets(&bytes, &mut report.fonts);
+    Ok((bytes, report))
 }
 
 pub fn tag_tree(
@@ -123,6 +132,13 @@ fn convert_pages(gc: &mut GlobalContext, document: &mut Document) -> SourceResul
                 typst_page.frame.height().to_f32(),
             );
 
+            // Pages with bleed or printer marks are trimmed after printing.
+            if !typst_page.bleed.is_zero() || !typst_page.slug.is_zero() {
+                settings = settings
+                    .with_trim_box(typst_page.trim_box().to_krilla())
+                    .with_bleed_box(typst_page.bleed_box().to_krilla());
+            }
+
             if let Some(label) = typst_page
                 .numbering
                 .as_ref()
@@ -269,6 +285,8 @@ pub(crate) struct GlobalContext<'a> {
     /// Cache the conversion between krilla and Typst fonts (forward and backward).
     pub(crate) fonts_forward: FxHashMap<Font, krilla::text::Font>,
     pub(crate) fonts_backward: FxHashMap<krilla::text::Font, Font>,
+    /// The glyphs used from each font, in order of the fonts' first use.
+    pub(crate) used_glyphs: IndexMap<Font, FxHashSet<u16>, FxBuildHasher>,
     /// Mapping between images and their span.
     // Note: In theory, the same image can have multiple spans
     // if it appears in the document multiple times. We just store the
@@ -277,6 +295,10 @@ pub(crate) struct GlobalContext<'a> {
     /// The spans of all images that appear in the document. We use this so
     /// we can give more accurate error messages.
     pub(crate) image_spans: FxHashSet<Span>,
+    /// The hashes of the raster images that were downsampled or re-encoded,
+    /// and a summary of the bytes this saved.
+    pub(crate) processed_images: FxHashSet<u128>,
+    pub(crate) image_summary: PdfImageSummary,
     /// The document to convert.
     pub(crate) document: &'a PagedDocument,
     /// Options for PDF export.
@@ -286,6 +308,8 @@ pub(crate) struct GlobalContext<'a> {
     pub(crate) page_index_converter: PageIndexConverter,
     /// Tagged PDF context.
     pub(crate) tags: Tags,
+    /// Warnings that occurred during export.
+    pub(crate) warnings: EcoVec<SourceDiagnostic>,
 }
 
 impl<'a> GlobalContext<'a> {
@@ -299,13 +323,17 @@ impl<'a> GlobalContext<'a> {
         Self {
             fonts_forward: FxHashMap::default(),
             fonts_backward: FxHashMap::default(),
+            used_glyphs: IndexMap::default(),
             document,
             options,
             loc_to_names,
             image_to_spans: FxHashMap::default(),
             image_spans: FxHashSet::default(),
+            processed_images: FxHashSet::default(),
+            image_summary: PdfImageSummary::default(),
             page_index_converter,
             tags,
+            warnings: EcoVec::new(),
         }
     }
 }
@@ -736,7 +764,7 @@ fn collect_named_destinations(
     for (loc, label) in matches {
         // Only add named destination if page belonging to the position is exported.
         let pos = document.introspector.position(loc);
-        if let Some(dest) = crate::link::pos_to_xyz(pic, pos) {
+        if let Some(dest) = crate::link::pos_to_xyz(document, pic, pos) {
             let named = NamedDestination::new(label.resolve().to_string(), dest);
             locs_to_names.insert(loc, named);
         }
@@ -1,13 +1,16 @@
 use std::hash::{Hash, Hasher};
+use std::io::Cursor;
 use std::sync::{Arc, OnceLock};
 
-use image::{DynamicImage, EncodableLayout, GenericImageView, Rgba};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, EncodableLayout, GenericImageView, ImageFormat, Rgba};
 use krilla::image::{BitsPerComponent, CustomImage, ImageColorspace};
 use krilla::pdf::PdfDocument;
 use krilla::surface::Surface;
 use krilla_svg::{SurfaceExt, SvgSettings};
 use typst_library::diag::{SourceResult, bail};
-use typst_library::foundations::Smart;
+use typst_library::foundations::{Bytes, Smart};
 use typst_library::layout::{Abs, Angle, Ratio, Size, Transform};
 use typst_library::visualize::{
     ExchangeFormat, Image, ImageKind, ImageScaling, PdfImage, RasterFormat, RasterImage,
@@ -39,10 +42,11 @@ pub(crate) fn handle_image(
 
     match image.kind() {
         ImageKind::Raster(raster) => {
-            let (exif_transform, new_size) = exif_transform(raster, size);
+            let raster = shrink_raster(gc, fc, raster, size);
+            let (exif_transform, new_size) = exif_transform(&raster, size);
             surface.push_transform(&exif_transform.to_krilla());
 
-            let image = match convert_raster(raster.clone(), interpolate) {
+            let image = match convert_raster(raster, interpolate) {
                 None => bail!(span, "failed to process image"),
                 Some(i) => i,
             };
@@ -207,6 +211,106 @@ fn convert_raster(
     }
 }
 
+/// Downsamples and re-encodes a raster image as configured in the export
+/// options, if that makes it smaller.
+fn shrink_raster(
+    gc: &mut GlobalContext,
+    fc: &FrameContext,
+    raster: &RasterImage,
+    size: Size,
+) -> RasterImage {
+    let options = &gc.options.images;
+    if options.max_ppi.is_none() && options.jpeg_quality.is_none() {
+        return raster.clone();
+    }
+
+    // Determine the image's size on the page, taking scaling into account.
+    let ts = fc.state().transform();
+    let placed_x = size.x.to_inches() * ts.sx.get().hypot(ts.ky.get());
+    let placed_y = size.y.to_inches() * ts.kx.get().hypot(ts.sy.get());
+
+    // Downsample uniformly, such that the image's resolution along both axes
+    // is at least the maximum resolution.
+    let (width, height) = (raster.width(), raster.height());
+    let factor = options.max_ppi.map_or(1.0, |ppi| {
+        (placed_x * ppi / width as f64).max(placed_y * ppi / height as f64)
+    });
+    let (width, height) = if factor < 1.0 {
+        let scale = |v: u32| ((v as f64 * factor).ceil() as u32).max(1);
+        (scale(width), scale(height))
+    } else {
+        (width, height)
+    };
+
+    let Some(processed) = process_raster(
+        raster.clone(),
+        width,
+        height,
+        options.jpeg_quality,
+        options.lossless,
+    ) else {
+        return raster.clone();
+    };
+
+    if gc.processed_images.insert(typst_utils::hash128(&processed)) {
+        let summary = &mut gc.image_summary;
+        summary.count += 1;
+        summary.original_bytes += raster.data().len();
+        summary.new_bytes += processed.data().len();
+    }
+
+    processed
+}
+
+/// Resizes a raster image to the given size and re-encodes it.
+///
+/// JPEG images are re-encoded as JPEG images, unless they are downsampled and
+/// `lossless` is set. All other images are re-encoded as PNG images. Returns
+/// `None` if there is nothing to do or if the result would not be smaller.
+#[comemo::memoize]
+fn process_raster(
+    raster: RasterImage,
+    width: u32,
+    height: u32,
+    jpeg_quality: Option<u8>,
+    lossless: bool,
+) -> Option<RasterImage> {
+    let is_jpeg = raster.format() == RasterFormat::Exchange(ExchangeFormat::Jpg);
+    let downsample = width < raster.width() || height < raster.height();
+    if !downsample && !(is_jpeg && jpeg_quality.is_some()) {
+        return None;
+    }
+
+    let mut image = raster.dynamic().as_ref().clone();
+    if downsample {
+        image = image.resize_exact(width, height, FilterType::Lanczos3);
+    }
+
+    let mut data = vec![];
+    let format = if is_jpeg && !(downsample && lossless) {
+        // The JPEG encoder only supports 8-bit RGB and luma images.
+        let image = if image.color().has_color() {
+            DynamicImage::ImageRgb8(image.to_rgb8())
+        } else {
+            DynamicImage::ImageLuma8(image.to_luma8())
+        };
+        let quality = jpeg_quality.unwrap_or(85).clamp(1, 100);
+        let encoder = JpegEncoder::new_with_quality(&mut data, quality);
+        image.write_with_encoder(encoder).ok()?;
+        ExchangeFormat::Jpg
+    } else {
+        image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png).ok()?;
+        ExchangeFormat::Png
+    };
+
+    if data.len() >= raster.data().len() {
+        return None;
+    }
+
+    let icc = raster.icc().cloned().map_or(Smart::Auto, Smart::Custom);
+    RasterImage::new(Bytes::new(data), format, icc).ok()
+}
+
 #[comemo::memoize]
 fn convert_pdf(pdf: &PdfImage) -> PdfDocument {
     PdfDocument::new(pdf.document().pdf().clone())
@@ -1,5 +1,6 @@
 //! Exporting Typst documents to PDF.
 
+mod a11y;
 mod attach;
 mod convert;
 mod image;
@@ -8,30 +9,60 @@ mod metadata;
 mod outline;
 mod page;
 mod paint;
+mod report;
 mod shape;
 mod tags;
 mod text;
 mod util;
 
 pub use self::metadata::{Timestamp, Timezone};
+pub use self::report::{PdfFontReport, PdfImageSummary, PdfReport};
 
 use std::fmt::{self, Debug, Formatter};
 
-use ecow::eco_format;
+use ecow::{EcoVec, eco_format};
 use krilla::configure::Validator;
 use serde::{Deserialize, Serialize};
-use typst_library::diag::{SourceResult, StrResult, bail};
+use typst_library::diag::{SourceDiagnostic, SourceResult, StrResult, Warned, bail};
 use typst_library::foundations::Smart;
 use typst_library::layout::{PageRanges, PagedDocument};
 
 /// Export a document into a PDF file.
 ///
-/// Returns the raw bytes making up the PDF file.
+/// Returns the raw bytes making up the PDF file, alongside warnings that
+/// occurred during export, for example because a font's license does not
+/// permit embedding it.
 #[typst_macros::time(name = "pdf")]
-pub fn pdf(document: &PagedDocument, options: &PdfOptions) -> SourceResult<Vec<u8>> {
+pub fn pdf(
+    document: &PagedDocument,
+    options: &PdfOptions,
+) -> SourceResult<Warned<Vec<u8>>> {
+    let (output, report) = convert::convert(document, options)?;
+    Ok(Warned { output, warnings: report.warnings })
+}
+
+/// Export a document into a PDF file and report how its resources were
+/// embedded.
+///
+/// Returns the raw bytes making up the PDF file and the report.
+#[typst_macros::time(name = "pdf")]
+pub fn pdf_with_report(
+    document: &PagedDocument,
+    options: &PdfOptions,
+) -> SourceResult<(Vec<u8>, PdfReport)> {
     convert::convert(document, options)
 }
 
+/// Check a document for common accessibility issues, such as missing
+/// alternative descriptions, skipped heading levels, tables without header
+/// rows, and text with low contrast.
+///
+/// Returns the issues as warnings.
+#[typst_macros::time(name = "check accessibility")]
+pub fn check_accessibility(document: &PagedDocument) -> EcoVec<SourceDiagnostic> {
+    a11y::check(document)
+}
+
 /// Generate the document tag tree and display it in a human readable form.
 #[doc(hidden)]
 pub fn pdf_tags(document: &PagedDocument, options: &PdfOptions) -> SourceResult<String> {
@@ -66,6 +97,10 @@ pub struct PdfOptions<'a> {
     /// circumstances, for example when trying to reduce the size of a document,
     /// it can be desirable to disable tagged PDF.
     pub disable_tags: bool,
+    /// How raster images are embedded.
+    pub images: PdfImageOptions,
+    /// How to handle fonts whose license restricts embedding them.
+    pub restricted_fonts: PdfRestrictedFonts,
 }
 
 impl PdfOptions<'_> {
@@ -76,6 +111,38 @@ impl PdfOptions<'_> {
     }
 }
 
+/// Settings for reducing the size of embedded raster images.
+///
+/// By default, raster images are embedded at their original resolution and,
+/// for JPEG images, with their original encoding.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfImageOptions {
+    /// If not `None`, images with a higher resolution at their placed size
+    /// are downsampled to this many pixels per inch.
+    pub max_ppi: Option<f64>,
+    /// If not `None`, JPEG images are re-encoded with this quality between 1
+    /// and 100 if that makes them smaller. Downsampled JPEG images are always
+    /// re-encoded, with a quality of 85 if none is given.
+    pub jpeg_quality: Option<u8>,
+    /// Whether downsampled JPEG images are re-encoded losslessly instead.
+    pub lossless: bool,
+}
+
+/// How to handle fonts whose license restricts embedding them, as declared in
+/// the `fsType` field of their OS/2 table.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PdfRestrictedFonts {
+    /// Embed the fonts anyway and emit a warning.
+    #[default]
+    Warn,
+    /// Fail the export.
+    Error,
+    /// Convert text set in the fonts to outlines, so that the fonts are not
+    /// embedded. The text cannot be selected, copied, or searched anymore.
+    Outline,
+}
+
 /// Encapsulates a list of compatible PDF standards.
 #[derive(Clone)]
 pub struct PdfStandards {
@@ -3,7 +3,7 @@ use krilla::annotation::Target;
 use krilla::destination::XyzDestination;
 use krilla::geom as kg;
 use typst_library::diag::{SourceResult, bail};
-use typst_library::layout::{Abs, Point, Position, Size};
+use typst_library::layout::{Abs, PagedDocument, Point, Position, Size};
 use typst_library::model::Destination;
 use typst_syntax::Span;
 
@@ -37,7 +37,7 @@ pub(crate) fn handle_link(
             Target::Action(Action::Link(LinkAction::new(u.to_string())))
         }
         Destination::Position(p) => {
-            let Some(dest) = pos_to_xyz(&gc.page_index_converter, *p) else {
+            let Some(dest) = pos_to_xyz(gc.document, &gc.page_index_converter, *p) else {
                 return Ok(());
             };
             Target::Destination(krilla::destination::Destination::Xyz(dest))
@@ -49,7 +49,8 @@ pub(crate) fn handle_link(
                 Target::Destination(krilla::destination::Destination::Named(nd.clone()))
             } else {
                 let pos = gc.document.introspector.position(*loc);
-                let Some(dest) = pos_to_xyz(&gc.page_index_converter, pos) else {
+                let Some(dest) = pos_to_xyz(gc.document, &gc.page_index_converter, pos)
+                else {
                     return Ok(());
                 };
                 Target::Destination(krilla::destination::Destination::Xyz(dest))
@@ -164,15 +165,24 @@ fn to_quadrilateral(fc: &FrameContext, size: Size) -> kg::Quadrilateral {
 ///
 /// - Takes into account page index conversion (if only part of the document is
 ///   exported)
+/// - Converts the position from the trimmed page to the full page, which may
+///   additionally contain the bleed and printer marks
 /// - Consistently shifts the link by 10pt because the position of e.g.
 ///   backlinks to footnotes is always at the baseline and if you link directly
 ///   to it, the text will not be visible since it is right above.
 pub(crate) fn pos_to_xyz(
+    document: &PagedDocument,
     pic: &PageIndexConverter,
     pos: Position,
 ) -> Option<XyzDestination> {
     let page_index = pic.pdf_page_index(pos.page.get() - 1)?;
-    let adjusted =
-        Point::new(pos.point.x, (pos.point.y - Abs::pt(10.0)).max(Abs::zero()));
+    let trim = document
+        .pages
+        .get(pos.page.get() - 1)
+        .map_or(Point::zero(), |page| page.trim_box().min);
+    let adjusted = Point::new(
+        trim.x + pos.point.x,
+        (trim.y + pos.point.y - Abs::pt(10.0)).max(Abs::zero()),
+    );
     Some(XyzDestination::new(page_index, adjusted.to_krilla()))
 }
@@ -60,7 +60,9 @@ fn convert_node(
         None => text.to_string(),
     };
 
-    if let Some(dest) = crate::link::pos_to_xyz(&gc.page_index_converter, pos) {
+    if let Some(dest) =
+        crate::link::pos_to_xyz(gc.document, &gc.page_index_converter, pos)
+    {
         let mut outline_node = KrillaOutlineNode::new(title, dest);
         for child in convert_list(&node.children, gc) {
             outline_node.push_child(child);
@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use ecow::{EcoString, EcoVec, eco_format};
+use hayro_syntax::Pdf;
+use hayro_syntax::object::dict::keys::{BASE_FONT, FONT, RESOURCES, XOBJECT};
+use hayro_syntax::object::{Dict, Name, Stream};
+use typst_library::diag::SourceDiagnostic;
+use typst_library::text::EmbeddingRestriction;
+
+use crate::PdfRestrictedFonts;
+use crate::convert::GlobalContext;
+
+/// Information about how a document was exported to PDF.
+#[derive(Debug, Clone, Default)]
+pub struct PdfReport {
+    /// The fonts used in the document, in order of first use.
+    pub fonts: Vec<PdfFontReport>,
+    /// How raster images were downsampled and re-encoded.
+    pub images: PdfImageSummary,
+    /// Warnings that occurred during export.
+    pub warnings: EcoVec<SourceDiagnostic>,
+}
+
+/// A font that is used in the exported PDF.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PdfFontReport {
+    /// The name of the font as embedded in the PDF, e.g. `ABCDEF+Roboto`.
+    /// This is the font's PostScript name, prefixed with a tag that is unique
+    /// to the embedded subset. If the font is not embedded, the name has no
+    /// tag. If the font has no PostScript name, this is its family name.
+    pub name: EcoString,
+    /// The number of distinct glyphs used from the font.
+    pub glyphs: usize,
+    /// Whether the font is embedded. This is not the case when text set in
+    /// the font is converted to outlines.
+    pub embedded: bool,
+    /// The restriction that the font's license places on embedding it.
+    pub restriction: Option<EmbeddingRestriction>,
+}
+
+/// A summary of the raster images that were downsampled or re-encoded to
+/// reduce their size.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct PdfImageSummary {
+    /// The number of images that were downsampled or re-encoded.
+    pub count: usize,
+    /// The combined size of the images' original encoded data, in bytes.
+    pub original_bytes: usize,
+    /// The combined size of the images' new encoded data, in bytes.
+    pub new_bytes: usize,
+}
+
+impl PdfImageSummary {
+    /// The number of bytes that were saved.
+    pub fn saved_bytes(&self) -> usize {
+        self.original_bytes.saturating_sub(self.new_bytes)
+    }
+}
+
+/// Reports the fonts used in the document.
+pub(crate) fn font_report(gc: &GlobalContext) -> Vec<PdfFontReport> {
+    let outline = gc.options.restricted_fonts == PdfRestrictedFonts::Outline;
+    gc.used_glyphs
+        .iter()
+        .map(|(font, glyphs)| {
+            let restriction = font.embedding_restriction();
+            PdfFontReport {
+                name: font
+                    .find_name(ttf_parser::name_id::POST_SCRIPT_NAME)
+                    .map_or_else(|| font.info().family.clone().into(), Into::into),
+                glyphs: glyphs.len(),
+                embedded: !(outline && restriction.is_some()),
+                restriction,
+            }
+        })
+        .collect()
+}
+
+/// Replaces the names of the embedded fonts with the names of their subsets,
+/// as found in the exported PDF.
+///
+/// The subsets are named by krilla when the PDF is written, so the names can
+/// only be looked up afterwards.
+pub(crate) fn name_subsets(bytes: &[u8], fonts: &mut [PdfFontReport]) {
+    if !fonts.iter().any(|font| font.embedded) {
+        return;
+    }
+
+    let Ok(pdf) = Pdf::new(Arc::new(bytes.to_vec())) else { return };
+    let mut subsets = vec![];
+    for page in pdf.pages().iter() {
+        if let Some(resources) = page.raw().get::<Dict>(RESOURCES) {
+            collect_subsets(&resources, &mut subsets, 0);
+        }
+    }
+
+    for font in fonts.iter_mut().filter(|font| font.embedded) {
+        if let Some(tag) = subsets.iter().find_map(|subset| subset_tag(subset, font)) {
+            font.name = eco_format!("{tag}+{}", font.name);
+        }
+    }
+}
+
+/// Collects the base font names of the fonts used in the given resources and
+/// in the resources of the XObjects they use.
+fn collect_subsets(resources: &Dict, subsets: &mut Vec<EcoString>, depth: usize) {
+    // XObjects can be nested, but not arbitrarily deep in practice.
+    if depth > 8 {
+        return;
+    }
+
+    if let Some(fonts) = resources.get::<Dict>(FONT) {
+        for key in fonts.keys() {
+            let Some(font) = fonts.get::<Dict>(&*key) else { continue };
+            let Some(name) = font.get::<Name>(BASE_FONT) else { continue };
+            let name = EcoString::from(String::from_utf8_lossy(&*name));
+            if !subsets.contains(&name) {
+                subsets.push(name);
+            }
+        }
+    }
+
+    if let Some(xobjects) = resources.get::<Dict>(XOBJECT) {
+        for key in xobjects.keys() {
+            let Some(xobject) = xobjects.get::<Stream>(&*key) else { continue };
+            if let Some(resources) = xobject.dict().get::<Dict>(RESOURCES) {
+                collect_subsets(&resources, subsets, depth + 1);
+            }
+        }
+    }
+}
+
+/// Returns the subset tag of the base font name if it belongs to a subset of
+/// the font, i.e. if it has the form `ABCDEF+Name`, optionally followed by the
+/// name of its CMap.
+fn subset_tag<'a>(base_font: &'a str, font: &PdfFontReport) -> Option<&'a str> {
+    let (tag, rest) = base_font.split_once('+')?;
+    let suffix = rest.strip_prefix(font.name.as_str())?;
+    (tag.len() == 6
+        && tag.bytes().all(|b| b.is_ascii_uppercase())
+        && matches!(suffix, "" | "-Identity-H" | "-Identity-V"))
+    .then_some(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font(name: &str, embedded: bool) -> PdfFontReport {
+        PdfFontReport {
+            name: name.into(),
+            glyphs: 1,
+            embedded,
+            restriction: None,
+        }
+    }
+
+    /// Writes a PDF with one page that uses the given base fonts.
+    fn pdf_with_fonts(base_fonts: &[&str]) -> Vec<u8> {
+        let fonts: String = (0..base_fonts.len())
+            .map(|i| format!("/F{i} {} 0 R ", 4 + i))
+            .collect();
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 100 100] \
+                 /Resources << /Font << {fonts}>> >> >>"
+            ),
+        ];
+        objects.extend(
+            base_fonts.iter().map(|name| {
+                format!("<< /Type /Font /Subtype /Type0 /BaseFont /{name} >>")
+            }),
+        );
+
+        let mut pdf = String::from("%PDF-1.7\n");
+        let mut offsets = vec![];
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf += &format!("{} 0 obj\n{object}\nendobj\n", i + 1);
+        }
+        let xref = pdf.len();
+        pdf += &format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            pdf += &format!("{offset:010} 00000 n \n");
+        }
+        pdf += &format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        pdf.into_bytes()
+    }
+
+    #[test]
+    fn test_subset_tag() {
+        let roboto = font("Roboto", true);
+        assert_eq!(subset_tag("ABCDEF+Roboto", &roboto), Some("ABCDEF"));
+        assert_eq!(subset_tag("ABCDEF+Roboto-Identity-H", &roboto), Some("ABCDEF"));
+        assert_eq!(subset_tag("Roboto", &roboto), None);
+        assert_eq!(subset_tag("ABCDEF+RobotoMono", &roboto), None);
+        assert_eq!(subset_tag("ABCDEF+Roboto-Bold", &roboto), None);
+        assert_eq!(subset_tag("ABCDEF+Libertinus", &roboto), None);
+        assert_eq!(subset_tag("abcdef+Roboto", &roboto), None);
+        assert_eq!(subset_tag("ABCD+Roboto", &roboto), None);
+    }
+
+    #[test]
+    fn test_name_subsets() {
+        let pdf = pdf_with_fonts(&[
+            "ZXCVBN+Roboto-Bold",
+            "QWERTY+Roboto-Identity-H",
+            "ASDFGH+Libertinus",
+        ]);
+        let mut fonts = [
+            font("Libertinus", true),
+            font("Roboto", true),
+            font("Roboto", false),
+            font("Unused", true),
+        ];
+        name_subsets(&pdf, &mut fonts);
+        let names: Vec<_> = fonts.iter().map(|font| font.name.as_str()).collect();
+        assert_eq!(names, ["ASDFGH+Libertinus", "QWERTY+Roboto", "Roboto", "Unused"]);
+    }
+}
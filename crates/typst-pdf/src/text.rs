@@ -2,17 +2,18 @@ use std::ops::Range;
 use std::sync::Arc;
 
 use bytemuck::TransparentWrapper;
+use krilla::geom::PathBuilder;
 use krilla::surface::{Location, Surface};
 use krilla::text::GlyphId;
-use typst_library::diag::{SourceResult, bail};
+use typst_library::diag::{SourceResult, bail, warning};
 use typst_library::layout::Size;
-use typst_library::text::{Font, Glyph, TextItem};
+use typst_library::text::{EmbeddingRestriction, Font, Glyph, TextItem};
 use typst_library::visualize::FillRule;
 use typst_syntax::Span;
 
 use crate::convert::{FrameContext, GlobalContext};
 use crate::util::{AbsExt, TransformExt, display_font};
-use crate::{paint, tags};
+use crate::{PdfRestrictedFonts, paint, tags};
 
 #[typst_macros::time(name = "handle text")]
 pub(crate) fn handle_text(
@@ -24,7 +25,7 @@ pub(crate) fn handle_text(
     let mut handle = tags::text(gc, fc, surface, t);
     let surface = handle.surface();
 
-    let font = convert_font(gc, t.font.clone())?;
+    let outline = check_font(gc, t)?;
     let fill = paint::convert_fill(
         gc,
         &t.fill,
@@ -49,20 +50,152 @@ pub(crate) fn handle_text(
     surface.push_transform(&fc.state().transform().to_krilla());
     surface.set_fill(Some(fill));
     surface.set_stroke(stroke);
-    surface.draw_glyphs(
-        krilla::geom::Point::from_xy(0.0, 0.0),
-        glyphs,
-        font.clone(),
-        text,
-        size.to_f32(),
-        false,
-    );
+
+    if outline {
+        draw_outlines(t, surface);
+    } else {
+        let font = convert_font(gc, t.font.clone())?;
+        surface.draw_glyphs(
+            krilla::geom::Point::from_xy(0.0, 0.0),
+            glyphs,
+            font,
+            text,
+            size.to_f32(),
+            false,
+        );
+    }
 
     surface.pop();
 
     Ok(())
 }
 
+/// Records the glyphs used from the text's font and checks whether the
+/// font's license permits embedding it.
+///
+/// Returns whether the text should be converted to outlines instead.
+fn check_font(gc: &mut GlobalContext, t: &TextItem) -> SourceResult<bool> {
+    let first_use = !gc.used_glyphs.contains_key(&t.font);
+    gc.used_glyphs
+        .entry(t.font.clone())
+        .or_default()
+        .extend(t.glyphs.iter().map(|glyph| glyph.id));
+
+    let Some(restriction) = t.font.embedding_restriction() else {
+        return Ok(false);
+    };
+
+    let span = t.glyphs.first().map_or(Span::detached(), |glyph| glyph.span.0);
+    let font_str = display_font(&t.font);
+    // Fonts are always embedded as subsets, which only some licenses forbid.
+    let (action, done) = match restriction {
+        EmbeddingRestriction::NoSubsetting => ("subsetting", "subset"),
+        EmbeddingRestriction::Restricted | EmbeddingRestriction::BitmapOnly => {
+            ("embedding", "embedded")
+        }
+    };
+    match gc.options.restricted_fonts {
+        PdfRestrictedFonts::Warn => {
+            if first_use {
+                gc.warnings.push(warning!(
+                    span,
+                    "the license of font {font_str} does not permit {action} it";
+                    hint: "{}", restriction.description();
+                    hint: "the font was {done} anyway"
+                ));
+            }
+            Ok(false)
+        }
+        PdfRestrictedFonts::Error => bail!(
+            span,
+            "the license of font {font_str} does not permit {action} it";
+            hint: "{}", restriction.description();
+            hint: "convert text set in the font to outlines or use a different font"
+        ),
+        PdfRestrictedFonts::Outline => Ok(true),
+    }
+}
+
+/// Draws the glyphs of a text item as paths, so that its font does not need
+/// to be embedded.
+fn draw_outlines(t: &TextItem, surface: &mut Surface) {
+    let size = t.size.to_f32();
+    let mut builder = OutlineBuilder {
+        path: PathBuilder::new(),
+        scale: size / t.font.units_per_em() as f32,
+        origin: (0.0, 0.0),
+        last: (0.0, 0.0),
+    };
+
+    let (mut x, mut y) = (0.0, 0.0);
+    for glyph in &t.glyphs {
+        // Glyph offsets and advances are Y-up.
+        builder.origin = (
+            x + glyph.x_offset.get() as f32 * size,
+            y - glyph.y_offset.get() as f32 * size,
+        );
+        t.font
+            .ttf()
+            .outline_glyph(ttf_parser::GlyphId(glyph.id), &mut builder);
+        x += glyph.x_advance.get() as f32 * size;
+        y -= glyph.y_advance.get() as f32 * size;
+    }
+
+    if let Some(path) = builder.path.finish() {
+        surface.draw_path(&path);
+    }
+}
+
+/// Converts glyph outlines in font units into a krilla path.
+struct OutlineBuilder {
+    path: PathBuilder,
+    /// The scale from font units to points.
+    scale: f32,
+    /// The position of the current glyph's origin.
+    origin: (f32, f32),
+    /// The last point in font units, needed to convert quadratic curves.
+    last: (f32, f32),
+}
+
+impl OutlineBuilder {
+    /// Converts a point from Y-up font units to the text's coordinate system.
+    fn p(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.origin.0 + x * self.scale, self.origin.1 - y * self.scale)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for OutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (px, py) = self.p(x, y);
+        self.path.move_to(px, py);
+        self.last = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (px, py) = self.p(x, y);
+        self.path.line_to(px, py);
+        self.last = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        // Elevate the quadratic curve to a cubic one.
+        let (x0, y0) = self.last;
+        let c1 = (x0 + 2.0 / 3.0 * (x1 - x0), y0 + 2.0 / 3.0 * (y1 - y0));
+        let c2 = (x + 2.0 / 3.0 * (x1 - x), y + 2.0 / 3.0 * (y1 - y));
+        self.curve_to(c1.0, c1.1, c2.0, c2.1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p1, p2, p) = (self.p(x1, y1), self.p(x2, y2), self.p(x, y));
+        self.path.cubic_to(p1.0, p1.1, p2.0, p2.1, p.0, p.1);
+        self.last = (x, y);
+    }
+
+    fn close(&mut self) {
+        self.path.close();
+    }
+}
+
 fn convert_font(
     gc: &mut GlobalContext,
     typst_font: Font,
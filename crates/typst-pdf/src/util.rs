@@ -4,7 +4,7 @@ use krilla::geom as kg;
 use krilla::geom::PathBuilder;
 use krilla::paint as kp;
 use krilla::tagging as kt;
-use typst_library::layout::{Abs, Point, Sides, Size, Transform};
+use typst_library::layout::{Abs, Point, Rect, Sides, Size, Transform};
 use typst_library::text::Font;
 use typst_library::visualize::{Curve, CurveItem, FillRule, LineCap, LineJoin};
 
@@ -44,6 +44,21 @@ impl PointExt for Point {
     }
 }
 
+pub(crate) trait RectExt {
+    fn to_krilla(&self) -> Option<kg::Rect>;
+}
+
+impl RectExt for Rect {
+    fn to_krilla(&self) -> Option<kg::Rect> {
+        kg::Rect::from_ltrb(
+            self.min.x.to_f32(),
+            self.min.y.to_f32(),
+            self.max.x.to_f32(),
+            self.max.y.to_f32(),
+        )
+    }
+}
+
 pub(crate) trait LineCapExt {
     fn to_krilla(&self) -> kp::LineCap;
 }
@@ -266,6 +266,8 @@ mod exceptions {
         "h5",
         "h6",
         "historical-ligatures",
+        "linethickness",
+        "mmultiscripts",
         "number-clearance",
         "number-margin",
         "numbering-scope",
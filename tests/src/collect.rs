@@ -69,6 +69,7 @@ bitflags! {
         const PDFTAGS = 1 << 2;
         const LARGE = 1 << 3;
         const NOPDFUA = 1 << 4;
+        const A11Y = 1 << 5;
     }
 }
 
@@ -94,6 +95,7 @@ impl AttrFlags {
 pub struct Attrs {
     pub large: bool,
     pub pdf_ua: bool,
+    pub a11y: bool,
     pub targets: Targets,
 }
 
@@ -366,6 +368,7 @@ impl<'a> Parser<'a> {
                 "render" => AttrFlags::RENDER,
                 "pdftags" => AttrFlags::PDFTAGS,
                 "nopdfua" => AttrFlags::NOPDFUA,
+                "a11y" => AttrFlags::A11Y,
                 found => {
                     self.error(format!(
                         "expected attribute or closing ---, found `{found}`"
@@ -383,6 +386,7 @@ impl<'a> Parser<'a> {
         Attrs {
             large: parsed.contains(AttrFlags::LARGE),
             pdf_ua: !parsed.contains(AttrFlags::NOPDFUA),
+            a11y: parsed.contains(AttrFlags::A11Y),
             targets: parsed.targets(),
         }
     }
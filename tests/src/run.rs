@@ -413,8 +413,14 @@ impl OutputType for PagedDocument {
         format!("{}/{}.png", crate::REF_PATH, name).into()
     }
 
-    fn compile(world: &dyn World, _: &Test) -> Warned<SourceResult<Self>> {
-        typst::compile(world)
+    fn compile(world: &dyn World, test: &Test) -> Warned<SourceResult<Self>> {
+        let mut warned = typst::compile::<Self>(world);
+        if test.attrs.a11y
+            && let Ok(document) = &warned.output
+        {
+            warned.warnings.extend(typst_pdf::check_accessibility(document));
+        }
+        warned
     }
 
     fn is_skippable(&self) -> Result<bool, ()> {
@@ -457,7 +463,7 @@ impl OutputType for PagedDocument {
         // Write PDF if requested.
         if crate::ARGS.pdf() {
             let pdf_path = format!("{}/pdf/{}.pdf", crate::STORE_PATH, name);
-            let pdf = typst_pdf::pdf(self, &PdfOptions::default())?;
+            let pdf = typst_pdf::pdf(self, &PdfOptions::default())?.output;
             std::fs::write(pdf_path, pdf).unwrap();
         }
 
@@ -192,6 +192,22 @@ pub(crate) fn read(path: &Path) -> FileResult<Cow<'static, [u8]>> {
     }
 }
 
+/// Custom HTML elements for testing the typed HTML API.
+const HTML_SCHEMA: &str = r#"{
+  "elements": [
+    {
+      "name": "test-widget",
+      "description": "A widget for testing.",
+      "attributes": [
+        { "name": "size", "type": "positive-int" },
+        { "name": "variant", "type": ["primary", "secondary"] },
+        { "name": "disabled", "type": "boolean" },
+        { "name": "tags", "type": "tokens" }
+      ]
+    }
+  ]
+}"#;
+
 /// The extended standard library for testing.
 fn library() -> Library {
     // Set page width to 120pt with 10pt margins, so that the inner page is
@@ -199,6 +215,7 @@ fn library() -> Library {
     // that it multiplies to nice round numbers.
     let mut lib = Library::builder()
         .with_features([Feature::Html, Feature::A11yExtras].into_iter().collect())
+        .with_html_schemas(vec![Bytes::from_string(HTML_SCHEMA)])
         .build();
 
     // Hook up helpers into the global scope.
@@ -0,0 +1,26 @@
+--- html-css-text html ---
+#set html.css(text: true)
+#set text(fill: maroon)
+
+Plain *strong* and #text(size: 2em, weight: "bold")[big] text.
+
+= Heading
+
+--- html-css-spacing html ---
+#set html.css(spacing: true)
+#set par(spacing: 2em)
+#set block(above: 3em)
+
+First
+
+Second
+
+#html.div[Third]
+
+--- html-css-disabled html ---
+// Without opting in, styles are not mapped to CSS.
+#text(fill: red)[Red]
+
+--- html-css-constructor ---
+// Error: 2-12 can only be used in set rules
+#html.css()
@@ -0,0 +1,13 @@
+--- html-split html ---
+// Split markers have no effect in single-file HTML export.
+= Introduction
+Hello
+
+#html.split(title: "Appendix")
+
+= Details
+World
+
+--- html-split-title-wrong-type ---
+// Error: 20-22 expected string or none, found integer
+#html.split(title: 12)
@@ -185,3 +185,15 @@
 --- html-typed-invalid-body html ---
 // Error: 10-14 unexpected argument
 #html.img[hi]
+
+--- html-typed-custom html ---
+#html.test-widget(id: "w", size: 3, variant: "primary", disabled: true)[Body]
+#html.test-widget(tags: ("a", "b"))
+
+--- html-typed-custom-wrong-variant html ---
+// Error: 28-33 expected "primary" or "secondary"
+#html.test-widget(variant: "big")
+
+--- html-typed-custom-wrong-size html ---
+// Error: 25-26 number must be positive
+#html.test-widget(size: 0)
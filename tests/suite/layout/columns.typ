@@ -129,3 +129,6 @@ Hallo
 A
 #colbreak(weak: true)
 B
+
+--- columns-html html ---
+#columns(3)[Text]
@@ -22,6 +22,10 @@ Apart
 // Test fr box.
 Hello #box(width: 1fr, rect(height: 0.7em, width: 100%)) World
 
+--- box-minmax-width ---
+// Error: 19-35 expected auto, relative length, or fraction, found min-max size
+Hello #box(width: minmax(1pt, 2pt))
+
 --- block-fr-height ---
 #set page(height: 100pt)
 #rect(height: 10pt, width: 100%)
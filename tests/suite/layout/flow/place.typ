@@ -387,3 +387,7 @@ Paragraph after place.
 #place(auto, float: true, block(height: 100%, width: 100%, fill: aqua))
 #place(auto, float: true, block(height: 100%, width: 100%, fill: red))
 #lines(7)
+
+--- place-html html ---
+#place(right, dx: -5pt)[Note]
+Text
@@ -127,6 +127,42 @@
   )
 }
 
+--- grid-cell-continuation ---
+// The continuation is repeated in each region after the first when the cell
+// breaks, but not when the cell fits into a single region.
+#let cont = metadata("continuation")
+#set block(spacing: 0pt)
+#place(hide(block(height: 25pt, columns(3, grid(
+  grid.cell(continuation: cont)[
+    #block(height: 20pt)
+    #block(height: 20pt)
+    #block(height: 20pt)
+  ],
+)))))
+#place(hide(grid(grid.cell(continuation: cont)[A])))
+#context test(query(metadata).len(), 2)
+
+--- grid-cell-continuation-stroke ---
+// A row that breaks across pages repeats the continuation at the top of the
+// next page and draws the strokes of its cells at the break instead of
+// leaving the cells open.
+#set page(width: 120pt, height: 80pt)
+#table(
+  columns: 2,
+  stroke: (x, y) => if x == 0 { 2pt + blue } else { 1pt },
+  table.cell(continuation: emph[(cont.)])[
+    First
+    #v(60pt)
+    Second
+  ],
+  [Side],
+)
+
+--- grid-cell-continuation-set ---
+#set grid.cell(continuation: [(continued)])
+#show grid.cell: it => test(it.continuation, [(continued)])
+#grid[A]
+
 --- table-cell-in-grid ---
 // Error: 7-19 cannot use `table.cell` as a grid cell
 // Hint: 7-19 use `grid.cell` instead
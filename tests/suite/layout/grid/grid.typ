@@ -214,6 +214,44 @@
   [Hello there, my friends! Hi! What is going on right now?],
 )
 
+--- grid-auto-shrink-min-content ---
+// Auto columns don't shrink below the width of their widest word.
+#set page(width: 200pt)
+#let word = [Supercalifragilistic]
+#place(hide(grid(
+  columns: 2,
+  [#word #layout(size => metadata(size.width))],
+  [Hello there, my friends! Hi! What is going on right now?],
+)))
+#context test(query(metadata).first().value >= measure(word).width, true)
+
+--- grid-columns-minmax ---
+#let width(body) = measure(grid(columns: minmax(2cm, 3cm), body)).width
+#context {
+  test(width[A], 2cm)
+  test(width(box(width: 2.5cm)), 2.5cm)
+  test(width(lorem(20)), 3cm)
+}
+
+--- grid-columns-minmax-repr ---
+#test(repr(minmax(1pt, 50% + 2pt)), "minmax(1pt, 50% + 2pt)")
+#test(minmax(1pt, 2pt).min(), 1pt)
+#test(minmax(1pt, 2pt).max(), 2pt)
+
+--- grid-columns-minmax-shrink ---
+// Columns sized with minmax stay within their bounds while auto columns
+// shrink to fit the page.
+#set page(width: 180pt)
+#table(
+  columns: (minmax(1.5cm, 2cm), auto, auto),
+  [Typst], [A markup-based typesetting system], lorem(6),
+  [A long name], [Short], [Shorter],
+)
+
+--- grid-rows-minmax ---
+// Error: 13-29 expected auto, relative length, fraction, integer, or array, found min-max size
+#grid(rows: minmax(1pt, 2pt))[A]
+
 --- issue-grid-base-auto-row ---
 // Test that grid base for auto rows makes sense.
 #set page(height: 150pt)
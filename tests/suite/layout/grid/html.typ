@@ -132,3 +132,12 @@
     [Ending], [Table],
   ),
 )
+
+--- grid-html html ---
+#grid(
+  columns: (1fr, 2fr),
+  gutter: 5pt,
+  align: center,
+  [A], [B],
+  grid.cell(colspan: 2)[C],
+)
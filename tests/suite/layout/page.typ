@@ -74,6 +74,44 @@
 #set page(fill: none)
 #rect(fill: green)
 
+--- page-bleed ---
+// The bleed surrounds the trimmed page on all sides, but positions are still
+// relative to the trimmed page.
+#set page(width: 60pt, height: 40pt, margin: 10pt, bleed: 5pt)
+#context {
+  test(page.bleed, 5pt)
+  test(here().position(), (page: 1, x: 10pt, y: 10pt))
+}
+
+--- page-bleed-fill ---
+// The fill and background extend into the bleed.
+#set page(
+  width: 60pt,
+  height: 40pt,
+  margin: 10pt,
+  bleed: 5pt,
+  fill: eastern,
+  background: rect(width: 100%, height: 100%, stroke: white),
+)
+#text(fill: white)[Bleed]
+
+--- page-marks ---
+// Printer marks are drawn in an area around the bleed.
+#set page(
+  width: 60pt,
+  height: 40pt,
+  margin: 10pt,
+  bleed: 5pt,
+  fill: aqua,
+  marks: ("crop", "registration", "color-bar"),
+)
+Marks
+
+--- page-marks-without-bleed ---
+// Crop marks also work without a bleed.
+#set page(width: 60pt, height: 40pt, margin: 10pt, marks: ("crop",))
+Crop
+
 --- page-margin-uniform ---
 // Set all margins at once.
 #[
@@ -343,3 +343,120 @@ Hello
   rows: 16pt,
   ..range(6).map(str).flatten(),
 )
+
+--- table-note ---
+// Notes are numbered separately for each table and notes with the same body
+// share a number.
+#set page(width: 120pt)
+#table(
+  columns: 3,
+  [A #table.note[X]],
+  [B #table.note[Y]],
+  [C #table.note[X]],
+)
+#table[D #table.note[Z]]
+
+--- table-note-numbering ---
+// Each table numbers its notes from one and reuses the number of a note with
+// the same body.
+#set table.note(numbering: n => metadata(n))
+#place(hide[
+  #table(
+    [A #table.note[X]],
+    [B #table.note[Y]],
+    [C #table.note[X]],
+  )
+  #table[D #table.note[Z]]
+])
+#context test(query(metadata).map(m => m.value), (1, 2, 1, 1, 2, 1, 1))
+
+--- table-note-html html ---
+#table(
+  columns: 2,
+  [A#table.note[X]], [B#table.note[Y]],
+)
+
+--- table-align-char ---
+// Cells are padded such that their decimal points line up, widening the
+// column.
+#set text(number-width: "tabular")
+#set table(inset: 0pt, stroke: none)
+#context {
+  let cells = ([1.25], [10.5])
+  let plain = measure(table(..cells)).width
+  let aligned = measure(table(align-char: ".", ..cells)).width
+  let expected = measure[10].width + measure[.25].width
+  assert(plain < aligned)
+  assert(calc.abs(aligned - expected) < 0.01pt)
+}
+
+--- table-align-char-styled ---
+// The cells are measured as laid out, so styled text is taken into account.
+#set text(number-width: "tabular")
+#set table(inset: 0pt, stroke: none)
+#context {
+  let cells = ([#text(3em)[1].25], [10.5])
+  let aligned = measure(table(align-char: ".", ..cells)).width
+  let expected = measure(text(3em)[1]).width + measure[.25].width
+  assert(calc.abs(aligned - expected) < 0.01pt)
+}
+
+--- table-align-char-block ---
+// Only cells with inline content can be aligned on a character.
+// Warning: 36-58 cannot align cell on character `.`
+// Hint: 36-58 only cells with inline content, like text, can be aligned on a character
+// Hint: 36-58 lists, paragraphs, and blocks are laid out as is
+#place(hide(table(align-char: ".", table.cell(block[1.5]), [2.25])))
+
+--- table-align-char-invalid ---
+// Error: 20-24 expected exactly one character
+#table(align-char: "..")[A]
+
+--- table-align-char-html html ---
+#table(align-char: ".", [1.5], [10.25], [3])
+
+--- table-from-dicts ---
+#let t = table.from((
+  (name: "A", count: 1),
+  (name: "B", price: "2.5"),
+))
+#test(t.columns, (auto, auto, auto))
+#test(t.children.first().children.map(c => c.body), ([name], [count], [price]))
+#test(t.children.slice(1).map(c => c.body), ([A], [1], [], [B], [], [2.5]))
+#test(
+  t.children.slice(1).map(c => c.has("align")),
+  (false, true, true, false, true, true),
+)
+
+--- table-from-arrays ---
+#let t = table.from(
+  (("Item", "Qty"), ("Pen", "3"), ("Ink", "")),
+  columns: ("Qty", 0),
+  format: (Qty: v => strong(v)),
+)
+#test(t.columns, (auto, auto))
+#test(t.children.first().children.map(c => c.body), ([Qty], [Item]))
+#test(t.children.slice(1).map(c => c.body), (strong("3"), [Pen], strong(""), [Ink]))
+
+--- table-from-no-header ---
+#let t = table.from(((1, "x"), (3,)), header: false)
+#test(t.children.map(c => c.body), ([1], [x], [3], []))
+
+--- table-from-mixed-rows ---
+// Error: 13-24 expected rows to be either all arrays or all dictionaries, found integer
+#table.from(((1, 2), 3))
+
+--- table-from-unknown-column ---
+// Error: 33-39 data has no column named "b"
+#table.from(((a: 1),), columns: ("b",))
+
+--- table-from-format-missing ---
+#let t = table.from(
+  ((a: 1, b: 2), (b: 3), (a: none, b: 4)),
+  format: (a: v => v + 1),
+)
+#test(t.children.slice(1).map(c => c.body), ([2], [2], [], [3], [], [4]))
+
+--- table-from-format-unknown-column ---
+// Error: 32-43 data has no column named "c"
+#table.from(((a: 1),), format: (c: v => v))
@@ -304,3 +304,15 @@ Looks at the @quadratic formula.
 #set text(stroke: green + 0.5pt)
 
 A $B^2$ $ grave(C)' $
+
+--- math-equation-html-inline html ---
+Let $a$ and #math.equation(alt: "x squared", $x^2$) be given.
+
+--- math-equation-html-block html ---
+#set math.equation(numbering: "(1)")
+$ a^2 + b^2 = c^2 $
+
+--- math-equation-html-structures html ---
+$ sum_(i=1)^n i = (n(n+1))/2 $
+
+$ sqrt(x) + root(3, y) + vec(1, 2) $
@@ -300,3 +300,38 @@ HI#footnote.entry(clearance: 2.5em)[There]
 )
 
 #c
+
+--- figure-caption-continuation ---
+// The continuation caption is repeated when the figure breaks, but not for
+// a figure that fits.
+#set page(width: 120pt, height: 100pt)
+#show figure: set block(breakable: true)
+#set figure.caption(position: top, continuation: [(continued)])
+#figure(
+  caption: [A],
+  table(columns: 2, ..range(12).map(str)),
+)
+#figure(caption: [B], rect(height: 10pt))
+
+--- figure-caption-continuation-count ---
+// Only the figure that breaks repeats its caption.
+#show figure: set block(breakable: true)
+#set figure.caption(position: top, continuation: metadata("continued"))
+#place(hide(block(height: 40pt, columns(2, figure(
+  caption: [A],
+  grid(block(height: 15pt), block(height: 15pt)),
+)))))
+#place(hide(figure(caption: [B], block(height: 15pt))))
+#context test(query(metadata).len(), 1)
+
+--- figure-caption-continuation-set ---
+// A show rule can display the continuation next to the caption.
+#set figure.caption(continuation: [(continued)])
+#show figure.caption: it => [#it.body #it.continuation]
+#figure(rect(), caption: [A])
+
+--- figure-caption-continuation-field ---
+// The continuation set on the caption is available in show rules.
+#set figure.caption(continuation: [(continued)])
+#show figure.caption: it => test(it.continuation, [(continued)])
+#place(hide(figure(rect(), caption: [A])))
@@ -0,0 +1,54 @@
+--- a11y-metadata a11y ---
+// Warning: document title is missing
+// Hint: set the title with `set document(title: ..)`
+// Warning: document language is not set
+// Hint: set the language with `set text(lang: ..)` at the top of the document
+
+--- a11y-image-alt a11y ---
+#set document(title: [Accessibility])
+#set text(lang: "en")
+#place(hide[
+  // Warning: 4-50 image is missing an alternative description
+  // Hint: 4-50 describe the image with its `alt` parameter
+  #image("/assets/images/tiger.jpg", width: 10pt)
+  #image("/assets/images/tiger.jpg", width: 10pt, alt: "A tiger")
+  #figure(image("/assets/images/tiger.jpg", width: 10pt), alt: "A tiger")
+])
+
+--- a11y-heading-skip a11y ---
+#set document(title: [Accessibility])
+#set text(lang: "en")
+#place(hide[
+  = Introduction
+  // Warning: 3-14 skipped from heading level 1 to 3
+  // Hint: 3-14 heading levels must be consecutive
+  === Details
+  == Details
+])
+
+--- a11y-heading-first a11y ---
+#set document(title: [Accessibility])
+#set text(lang: "en")
+#place(hide[
+  // Warning: 3-11 the first heading must be of level 1
+  // Hint: 3-11 heading levels must be consecutive
+  == Start
+])
+
+--- a11y-table-header a11y ---
+#set document(title: [Accessibility])
+#set text(lang: "en")
+#place(hide[
+  // Warning: 4-19 table has no header row
+  // Hint: 4-19 wrap the table's first row in `table.header`
+  #table([A], [B])
+  #table(table.header[A], [B])
+])
+
+--- a11y-contrast a11y ---
+#set document(title: [Accessibility])
+#set text(lang: "en")
+// Warning: 20-25 text has low contrast with its background (ratio: 1.00:1, minimum: 4.5:1)
+// Hint: 20-25 WCAG requires a contrast ratio of at least 4.5:1 for normal text and 3:1 for large text
+#text(fill: white)[Faint]
+#box(fill: black, text(fill: white)[Dark])
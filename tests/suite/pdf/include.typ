@@ -0,0 +1,25 @@
+--- pdf-include-pages ---
+// The included pages sit between the surrounding pages, have the size of the
+// original pages, and start at their top-left corner.
+#set page(width: 80pt, height: 60pt)
+Before
+#pdf.include("/assets/images/diagrams.pdf", pages: (3, 1), title: [Diagrams])
+After
+#context {
+  test(here().page(), 4)
+  test(counter(page).get(), (4,))
+  test(page.width, 80pt)
+  test(page.height, 60pt)
+  let title = query(heading).first()
+  test(title.location().position(), (page: 2, x: 0pt, y: 0pt))
+}
+
+--- pdf-include-invalid-page ---
+// Error: 2-61 page 2 does not exist
+// Hint: 2-61 the document only has 1 page
+#pdf.include("/assets/images/matplotlib.pdf", pages: (1, 2))
+
+--- pdf-include-not-a-pdf ---
+// Error: 2-41 the PDF could not be loaded
+// Hint: 2-41 perhaps the PDF file is malformed
+#pdf.include("/assets/images/tiger.jpg")
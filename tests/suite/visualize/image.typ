@@ -240,19 +240,11 @@ A #box(image("/assets/images/tiger.jpg", height: 1cm, width: 80%)) B
   img("pixelated"),
 )
 
-#context if target() == "html" {
-  // TODO: Remove this once `stack` is supported in HTML export.
-  html.div(
-    style: "display: flex; flex-direction: row; gap: 4pt",
-    images.join(),
-  )
-} else {
-  stack(
-    dir: ltr,
-    spacing: 4pt,
-    ..images,
-  )
-}
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  ..images,
+)
 
 --- image-natural-dpi-sizing ---
 // Test that images aren't upscaled.
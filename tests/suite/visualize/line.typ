@@ -95,3 +95,8 @@
 --- line-infinite-length ---
 // Error: 2-54 cannot create line with infinite length
 #line(start: (0pt, 0pt), end: (float.inf * 1pt, 0pt))
+
+--- line-html-relative html ---
+// Warning: 4-22 line was ignored during HTML export
+// Hint: 4-22 shapes with relative sizes are not supported in HTML export
+A #line(length: 100%)
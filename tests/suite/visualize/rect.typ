@@ -127,3 +127,6 @@
 #rect()
 #rect(height: 60pt)
 #rect(width: 60pt)
+
+--- rect-html html ---
+A #rect() in a paragraph.